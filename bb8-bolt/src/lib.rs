@@ -1,127 +1,232 @@
 #![warn(rust_2018_idioms)]
 
-use std::{collections::HashMap, convert::TryFrom, net::SocketAddr};
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::{collections::HashMap, ops::Deref, time::Duration};
 
 use async_trait::async_trait;
 use bb8::{ManageConnection, PooledConnection};
-use thiserror::Error;
-use tokio::{
-    io::BufStream,
-    net::{lookup_host, ToSocketAddrs},
-};
-use tokio_util::compat::*;
+use tokio::net::ToSocketAddrs;
 
-use bolt_client::*;
-use bolt_proto::{version::*, *};
+use bolt_client::{
+    pool::{AuthProvider, Connection, ConnectionManager, Connector},
+    Stream, TcpConfig, TlsConfig,
+};
+use bolt_proto::Value;
 
+pub use ::bolt_proto;
 pub use bolt_client;
-pub use bolt_proto;
+pub use bolt_client::pool::ManagerError as Error;
 
-pub struct BoltConnectionManager {
-    addr: SocketAddr,
-    domain: Option<String>,
-    preferred_versions: [u32; 4],
-    metadata: HashMap<String, Value>,
-}
+/// Configures the [`Connector`] used by a manager, resolving the server's host name each
+/// time a connection is opened. See [`ConnectorBuilder`](bolt_client::pool::ConnectorBuilder).
+///
+/// # Example
+/// ```
+/// use bolt_client::AuthToken;
+/// use bb8_bolt::*;
+///
+/// let manager = BoltConnectionManager::from(
+///     ManagerBuilder::new("db.example.com:7687")
+///         .with_auth_token(AuthToken::basic("neo4j", "password"))
+///         .with_user_agent("my-client-name/1.0")
+///         .build(),
+/// );
+/// ```
+pub type ManagerBuilder = bolt_client::pool::ConnectorBuilder;
+
+/// A bb8 manager for Bolt connections.
+///
+/// Connections are opened and checked by the wrapped [`ConnectionManager`], which is
+/// configured through its [`Connector`], and can be created from either.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use bolt_client::AuthToken;
+/// use bb8_bolt::*;
+///
+/// let manager = BoltConnectionManager::from_connector(
+///     BoltConnectionManager::builder("db.example.com:7687")
+///         .with_auth_token(AuthToken::basic("neo4j", "password"))
+///         .with_user_agent("my-client-name/1.0")
+///         .build(),
+/// )
+/// .with_max_lifetime(Duration::from_secs(60 * 60));
+/// ```
+pub struct BoltConnectionManager(ConnectionManager);
 
 impl BoltConnectionManager {
+    /// Create a manager for the server at the given address. If a domain is provided,
+    /// TLS will be negotiated using the default [`TlsConfig`] for that domain. The address
+    /// is resolved once; use [`builder`](Self::builder) to resolve it for each connection.
     pub async fn new(
         addr: impl ToSocketAddrs,
         domain: Option<String>,
         preferred_versions: [u32; 4],
         metadata: HashMap<impl Into<String>, impl Into<Value>>,
     ) -> Result<Self, Error> {
-        Ok(Self {
-            addr: lookup_host(addr)
-                .await?
-                .next()
-                .ok_or(Error::InvalidAddress)?,
-            domain,
-            preferred_versions,
-            metadata: metadata
-                .into_iter()
-                .map(|(k, v)| (k.into(), v.into()))
-                .collect(),
-        })
+        Ok(Self::from_connector(
+            Connector::new(addr, domain, preferred_versions, metadata).await?,
+        ))
+    }
+
+    /// Create a manager that connects to a Unix domain socket at the given path.
+    #[cfg(unix)]
+    pub fn unix(
+        path: impl Into<PathBuf>,
+        preferred_versions: [u32; 4],
+        metadata: HashMap<impl Into<String>, impl Into<Value>>,
+    ) -> Self {
+        Self::from_connector(Connector::unix(path, preferred_versions, metadata))
+    }
+
+    /// Create a manager whose connections use the in-memory streams returned by
+    /// `make_stream`, typically the client end of a [`Stream::duplex`] whose other end is
+    /// served by an in-process server or stub.
+    pub fn duplex(
+        make_stream: impl Fn() -> Stream + Send + Sync + 'static,
+        preferred_versions: [u32; 4],
+        metadata: HashMap<impl Into<String>, impl Into<Value>>,
+    ) -> Self {
+        Self::from_connector(Connector::duplex(make_stream, preferred_versions, metadata))
+    }
+
+    /// Create a manager for the server identified by a connection URI, such as
+    /// `neo4j+s://db.example.com:7687`. TLS is configured according to the URI's scheme,
+    /// and its routing context is sent to servers supporting Bolt v4.1 or later.
+    pub async fn from_uri(
+        uri: &str,
+        preferred_versions: [u32; 4],
+        metadata: HashMap<impl Into<String>, impl Into<Value>>,
+    ) -> Result<Self, Error> {
+        Ok(Self::from_connector(
+            Connector::from_uri(uri, preferred_versions, metadata).await?,
+        ))
+    }
+
+    /// Start configuring a manager for the server at the given `host:port` address. Pass
+    /// the built [`Connector`] to [`from_connector`](Self::from_connector).
+    pub fn builder(addr: impl Into<String>) -> ManagerBuilder {
+        ManagerBuilder::new(addr)
+    }
+
+    /// Create a manager that opens and validates connections with the given
+    /// [`Connector`].
+    pub fn from_connector(connector: Connector) -> Self {
+        Self(ConnectionManager::new(connector))
+    }
+
+    /// Negotiate TLS with the given configuration when opening TCP connections, replacing
+    /// any configuration derived from the domain passed to [`new`](Self::new).
+    pub fn with_tls_config(self, tls_config: TlsConfig) -> Self {
+        self.map_connector(|connector| connector.with_tls_config(tls_config))
+    }
+
+    /// Use the given socket settings, such as connect timeout and keepalive, when opening
+    /// TCP connections.
+    pub fn with_tcp_config(self, tcp_config: TcpConfig) -> Self {
+        self.map_connector(|connector| connector.with_tcp_config(tcp_config))
+    }
+
+    /// Fail to open a connection if the Bolt handshake does not complete within the given
+    /// duration.
+    pub fn with_handshake_timeout(self, timeout: Duration) -> Self {
+        self.map_connector(|connector| connector.with_handshake_timeout(timeout))
+    }
+
+    /// Fail requests on a connection if a response message does not arrive within the
+    /// given duration. The connection is then considered broken.
+    pub fn with_read_timeout(self, timeout: Duration) -> Self {
+        self.map_connector(|connector| connector.with_read_timeout(timeout))
+    }
+
+    /// Validate connections by running the given query and consuming its results, instead
+    /// of the default check. By default, connections using Bolt v3 or later are validated
+    /// with a `RESET` message, and older connections by running `RETURN 1;`.
+    pub fn with_validation_query(self, query: impl Into<String>) -> Self {
+        self.map_connector(|connector| connector.with_validation_query(query))
+    }
+
+    /// Initialize each new connection with credentials from the given provider, replacing
+    /// any `scheme`, `principal`, `credentials` and `realm` given in the metadata.
+    pub fn with_auth_provider(self, auth_provider: impl AuthProvider + 'static) -> Self {
+        self.map_connector(|connector| connector.with_auth_provider(auth_provider))
+    }
+
+    /// Discard connections that have been open for longer than the given duration, instead
+    /// of reusing them.
+    pub fn with_max_lifetime(self, max_lifetime: Duration) -> Self {
+        Self(self.0.with_max_lifetime(max_lifetime))
+    }
+
+    /// Only validate connections when checked out if no message has been exchanged on them
+    /// for at least the given duration. By default, connections are validated every time.
+    pub fn with_idle_time_before_check(self, idle_time: Duration) -> Self {
+        Self(self.0.with_idle_time_before_check(idle_time))
+    }
+
+    fn map_connector(self, f: impl FnOnce(Connector) -> Connector) -> Self {
+        Self(self.0.map_connector(f))
+    }
+}
+
+impl From<ConnectionManager> for BoltConnectionManager {
+    fn from(manager: ConnectionManager) -> Self {
+        Self(manager)
     }
 }
 
-#[derive(Debug, Error)]
-pub enum Error {
-    #[error("invalid host address")]
-    InvalidAddress,
-    #[error("invalid metadata: {0}")]
-    InvalidMetadata(String),
-    #[error("client initialization failed: received {0:?}")]
-    ClientInitFailed(bolt_proto::Message),
-    #[error("invalid client version: {0:#x}")]
-    InvalidClientVersion(u32),
-    #[error(transparent)]
-    ClientError(#[from] bolt_client::error::Error),
-    #[error(transparent)]
-    ProtocolError(#[from] bolt_proto::error::Error),
-    #[error(transparent)]
-    IOError(#[from] std::io::Error),
+impl From<Connector> for BoltConnectionManager {
+    fn from(connector: Connector) -> Self {
+        Self::from_connector(connector)
+    }
+}
+
+impl Deref for BoltConnectionManager {
+    type Target = ConnectionManager;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
 }
 
 #[async_trait]
 impl ManageConnection for BoltConnectionManager {
-    type Connection = Client<Compat<BufStream<Stream>>>;
+    type Connection = Connection;
     type Error = Error;
 
     async fn connect(&self) -> Result<Self::Connection, Self::Error> {
-        let mut client = Client::new(
-            BufStream::new(Stream::connect(self.addr, self.domain.as_ref()).await?).compat(),
-            &self.preferred_versions,
-        )
-        .await?;
-        let response = match client.version() {
-            V1_0 | V2_0 => {
-                let mut metadata = self.metadata.clone();
-                let user_agent: String = metadata
-                    .remove("user_agent")
-                    .ok_or_else(|| Error::InvalidMetadata("must contain a user_agent".to_string()))
-                    .map(String::try_from)??;
-                client.init(user_agent, Metadata::from(metadata)).await?
-            }
-            V3_0 | V4_0 | V4_1 => {
-                client
-                    .hello(Some(Metadata::from(self.metadata.clone())))
-                    .await?
-            }
-            _ => return Err(Error::InvalidClientVersion(client.version())),
-        };
-
-        match response {
-            Message::Success(_) => Ok(client),
-            other => Err(Error::ClientInitFailed(other)),
-        }
+        self.0.connect().await
     }
 
     async fn is_valid(&self, conn: &mut PooledConnection<'_, Self>) -> Result<(), Self::Error> {
-        let response = conn.run("RETURN 1;".to_string(), None).await?;
-        message::Success::try_from(response)?;
-        let (response, _records) = conn.pull_all().await?;
-        message::Success::try_from(response)?;
-        Ok(())
+        self.0.check(conn).await
     }
 
-    fn has_broken(&self, _conn: &mut Self::Connection) -> bool {
-        // There's no good/fast way to check if a tokio TcpStream is still healthy.
-        // However, given that the TcpStream is shut down when the connection object is
-        // dropped, we can assume existing connections aren't broken.
-        false
+    fn has_broken(&self, conn: &mut Self::Connection) -> bool {
+        self.0.has_broken(conn)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::convert::{TryFrom, TryInto};
     use std::env;
     use std::iter::FromIterator;
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
 
     use bb8::*;
     use futures_util::future::join_all;
 
+    use bolt_client::{Metadata, ServerState};
+    use bolt_proto::{version::*, *};
+    use tokio_util::compat::*;
+
     use super::*;
 
     async fn get_connection_manager(
@@ -219,4 +324,223 @@ mod tests {
                 ))
         ));
     }
+
+    #[tokio::test]
+    async fn from_uri() {
+        let metadata = HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]);
+        let manager = BoltConnectionManager::from_uri(
+            "neo4j+s://localhost:7688?region=eu",
+            [V4_1, 0, 0, 0],
+            metadata.clone(),
+        )
+        .await
+        .unwrap();
+        let connector = manager.connector();
+        assert_eq!(connector.tls_config().unwrap().server_name(), "localhost");
+        assert_eq!(
+            connector.routing_context(),
+            Some(&HashMap::from_iter(vec![
+                (String::from("region"), String::from("eu")),
+                (String::from("address"), String::from("localhost:7688")),
+            ]))
+        );
+
+        assert!(matches!(
+            BoltConnectionManager::from_uri("http://localhost", [V4_1, 0, 0, 0], metadata).await,
+            Err(Error::ClientError(bolt_client::error::Error::InvalidUri(_)))
+        ));
+    }
+
+    #[test]
+    fn builder() {
+        let manager = BoltConnectionManager::from_connector(
+            BoltConnectionManager::builder("localhost:7688")
+                .with_default_database("movies")
+                .build(),
+        );
+        assert_eq!(manager.connector().default_database(), Some("movies"));
+
+        let manager = BoltConnectionManager::from(
+            ManagerBuilder::from_uri("neo4j+s://localhost:7688?region=eu")
+                .unwrap()
+                .build(),
+        );
+        assert_eq!(
+            manager.connector().tls_config().unwrap().server_name(),
+            "localhost"
+        );
+        assert!(manager.connector().routing_context().is_some());
+    }
+
+    // Respond to a handshake with the first preferred version, and to HELLO with SUCCESS.
+    // Any further messages are left unanswered.
+    async fn serve_hello(mut stream: tokio::io::DuplexStream) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut handshake = [0; 20];
+        stream.read_exact(&mut handshake).await.unwrap();
+        stream.write_all(&handshake[4..8]).await.unwrap();
+        stream.flush().await.unwrap();
+
+        let hello = Message::from_stream((&mut stream).compat()).await.unwrap();
+        assert!(matches!(hello, Message::Hello(_)));
+        let chunks: Vec<_> = Message::Success(message::Success::new(HashMap::new()))
+            .try_into()
+            .unwrap();
+        for chunk in chunks {
+            stream.write_all(&chunk).await.unwrap();
+        }
+        stream.flush().await.unwrap();
+        while stream.read(&mut [0; 1024]).await.unwrap_or(0) > 0 {}
+    }
+
+    #[tokio::test]
+    async fn duplex() {
+        let manager = BoltConnectionManager::duplex(
+            || {
+                let (stream, server) = Stream::duplex(1024);
+                tokio::spawn(serve_hello(server));
+                stream
+            },
+            [V4_1, 0, 0, 0],
+            HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+        );
+        for _ in 0..2 {
+            let client = manager.connect().await.unwrap();
+            assert_eq!(client.version(), V4_1);
+        }
+    }
+
+    // Respond to a handshake with the first preferred version, and to every message with
+    // SUCCESS until the client disconnects.
+    async fn serve_success(mut stream: tokio::io::DuplexStream) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut handshake = [0; 20];
+        stream.read_exact(&mut handshake).await.unwrap();
+        stream.write_all(&handshake[4..8]).await.unwrap();
+        stream.flush().await.unwrap();
+
+        while Message::from_stream((&mut stream).compat()).await.is_ok() {
+            let chunks: Vec<_> = Message::Success(message::Success::new(HashMap::new()))
+                .try_into()
+                .unwrap();
+            for chunk in chunks {
+                stream.write_all(&chunk).await.unwrap();
+            }
+            stream.flush().await.unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn reset_on_return() {
+        let connections = Arc::new(AtomicUsize::new(0));
+        let manager = {
+            let connections = Arc::clone(&connections);
+            BoltConnectionManager::duplex(
+                move || {
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    let (stream, server) = Stream::duplex(1024);
+                    tokio::spawn(serve_success(server));
+                    stream
+                },
+                [V4_1, 0, 0, 0],
+                HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+            )
+        };
+        let pool = Pool::builder().max_size(1).build(manager).await.unwrap();
+
+        let mut client = pool.get().await.unwrap();
+        client.begin(None).await.unwrap();
+        client
+            .run_with_metadata("RETURN 1;", None, None)
+            .await
+            .unwrap();
+        assert_eq!(client.state(), ServerState::TxStreaming);
+        drop(client);
+
+        let client = pool.get().await.unwrap();
+        assert_eq!(client.state(), ServerState::Ready);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    fn counting_manager(
+        connections: &Arc<AtomicUsize>,
+        serve: fn(tokio::io::DuplexStream),
+    ) -> BoltConnectionManager {
+        let connections = Arc::clone(connections);
+        BoltConnectionManager::duplex(
+            move || {
+                connections.fetch_add(1, Ordering::SeqCst);
+                let (stream, server) = Stream::duplex(1024);
+                serve(server);
+                stream
+            },
+            [V4_1, 0, 0, 0],
+            HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+        )
+    }
+
+    #[tokio::test]
+    async fn max_lifetime() {
+        let connections = Arc::new(AtomicUsize::new(0));
+        let manager = counting_manager(&connections, |server| {
+            tokio::spawn(serve_success(server));
+        })
+        .with_max_lifetime(Duration::from_millis(50));
+        let pool = Pool::builder().max_size(1).build(manager).await.unwrap();
+
+        drop(pool.get().await.unwrap());
+        drop(pool.get().await.unwrap());
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        drop(pool.get().await.unwrap());
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn idle_time_before_check() {
+        // The server stops responding after initialization, like a session silently dropped
+        // by a load balancer
+        let connections = Arc::new(AtomicUsize::new(0));
+        let manager = counting_manager(&connections, |server| {
+            tokio::spawn(serve_hello(server));
+        })
+        .with_read_timeout(Duration::from_millis(50))
+        .with_idle_time_before_check(Duration::from_millis(100));
+        let pool = Pool::builder().max_size(1).build(manager).await.unwrap();
+
+        // Recently used connections are reused without a liveness check
+        drop(pool.get().await.unwrap());
+        drop(pool.get().await.unwrap());
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+
+        // Idle connections are checked, and replaced if the check fails
+        tokio::time::sleep(Duration::from_millis(110)).await;
+        drop(pool.get().await.unwrap());
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn read_timeout() {
+        let manager = BoltConnectionManager::duplex(
+            || {
+                let (stream, server) = Stream::duplex(1024);
+                tokio::spawn(serve_hello(server));
+                stream
+            },
+            [V4_1, 0, 0, 0],
+            HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+        )
+        .with_handshake_timeout(Duration::from_millis(50))
+        .with_read_timeout(Duration::from_millis(50));
+        let mut client = manager.connect().await.unwrap();
+        assert_eq!(client.read_timeout(), Some(Duration::from_millis(50)));
+        assert!(matches!(
+            client.run_with_metadata("RETURN 1;", None, None).await,
+            Err(bolt_client::error::Error::Timeout)
+        ));
+        assert!(manager.has_broken(&mut client));
+    }
 }
@@ -0,0 +1,105 @@
+use async_std::net::{TcpStream, ToSocketAddrs};
+
+use crate::{error::*, ConnectionUri, FuturesStream, TcpConfig, TlsConfig};
+
+/// A [`FuturesStream`] over an async-std [`TcpStream`](async_std::net::TcpStream).
+#[cfg_attr(docsrs, doc(cfg(feature = "async-std-stream")))]
+pub type AsyncStdStream = FuturesStream<TcpStream>;
+
+impl AsyncStdStream {
+    /// Establish a connection with a remote socket. If a domain is provided, TLS
+    /// negotiation will be attempted using the default [`TlsConfig`] for that domain.
+    pub async fn connect(
+        addr: impl ToSocketAddrs,
+        domain: Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let tls_config = domain.map(|domain| TlsConfig::new(domain.as_ref()));
+        Self::connect_with_config(addr, tls_config.as_ref(), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with the server identified by a [`ConnectionUri`],
+    /// negotiating TLS if required by its scheme.
+    pub async fn connect_uri(uri: &ConnectionUri) -> Result<Self> {
+        let tls_config = uri.tls_config();
+        Self::connect_with_config(uri.addr(), tls_config.as_ref(), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with a remote socket and negotiate TLS using the given
+    /// [`TlsConfig`].
+    pub async fn connect_tls(addr: impl ToSocketAddrs, tls_config: &TlsConfig) -> Result<Self> {
+        Self::connect_with_config(addr, Some(tls_config), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with a remote socket using the given [`TcpConfig`]. If a
+    /// [`TlsConfig`] is provided, TLS negotiation will be attempted.
+    pub async fn connect_with_config(
+        addr: impl ToSocketAddrs,
+        tls_config: Option<&TlsConfig>,
+        tcp_config: &TcpConfig,
+    ) -> Result<Self> {
+        Self::from_tcp(TcpStream::connect(addr), tls_config, tcp_config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_std::net::TcpListener;
+    use futures_util::io::AsyncReadExt;
+
+    use super::*;
+    use crate::futures_stream::tests::*;
+
+    #[async_std::test]
+    async fn tcp() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = async_std::task::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            serve_hello(stream).await;
+        });
+
+        hello(AsyncStdStream::connect(addr, None::<String>).await.unwrap()).await;
+        handle.await;
+    }
+
+    #[async_std::test]
+    async fn secure_tcp() {
+        let acceptor = tls_acceptor();
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = async_std::task::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            serve_hello(acceptor.accept(stream).await.unwrap()).await;
+        });
+
+        let tls_config = TlsConfig::new("localhost").trust_all();
+        hello(
+            AsyncStdStream::connect_tls(addr, &tls_config)
+                .await
+                .unwrap(),
+        )
+        .await;
+        handle.await;
+    }
+
+    #[async_std::test]
+    async fn connect_timeout() {
+        // The server accepts TCP connections but never completes a TLS handshake
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = async_std::task::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            while stream.read(&mut [0; 1024]).await.unwrap_or(0) > 0 {}
+        });
+
+        let tcp_config = TcpConfig::default().with_connect_timeout(Duration::from_millis(50));
+        let tls_config = TlsConfig::new("localhost").trust_all();
+        assert!(matches!(
+            AsyncStdStream::connect_with_config(addr, Some(&tls_config), &tcp_config).await,
+            Err(Error::Timeout)
+        ));
+        handle.await;
+    }
+}
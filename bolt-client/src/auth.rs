@@ -0,0 +1,130 @@
+use std::{collections::HashMap, fmt};
+
+use bolt_proto::Value;
+
+/// Credentials sent to the server when initializing a connection, as the `scheme`,
+/// `principal`, `credentials` and `realm` entries of the `INIT` or `HELLO` metadata.
+#[derive(Clone, PartialEq)]
+pub struct AuthToken {
+    value: HashMap<String, Value>,
+}
+
+impl AuthToken {
+    /// Send no credentials, for servers with authentication disabled.
+    pub fn none() -> Self {
+        Self::custom("none", HashMap::<String, Value>::new())
+    }
+
+    /// Authenticate with a username and password.
+    pub fn basic(principal: impl Into<String>, credentials: impl Into<String>) -> Self {
+        Self::custom(
+            "basic",
+            vec![
+                ("principal", principal.into()),
+                ("credentials", credentials.into()),
+            ],
+        )
+    }
+
+    /// Authenticate with a bearer token, such as an SSO access token.
+    pub fn bearer(token: impl Into<String>) -> Self {
+        Self::custom("bearer", vec![("credentials", token.into())])
+    }
+
+    /// Authenticate with a base64-encoded Kerberos ticket.
+    pub fn kerberos(ticket: impl Into<String>) -> Self {
+        Self::custom(
+            "kerberos",
+            vec![("principal", String::new()), ("credentials", ticket.into())],
+        )
+    }
+
+    /// Authenticate using a custom scheme, supported by a server-side plugin, with the
+    /// given entries in addition to the scheme.
+    pub fn custom(
+        scheme: impl Into<String>,
+        entries: impl IntoIterator<Item = (impl Into<String>, impl Into<Value>)>,
+    ) -> Self {
+        let mut value: HashMap<String, Value> = entries
+            .into_iter()
+            .map(|(k, v)| (k.into(), v.into()))
+            .collect();
+        value.insert(String::from("scheme"), Value::from(scheme.into()));
+        Self { value }
+    }
+
+    /// Authenticate against the given realm, for servers with multiple authentication
+    /// providers.
+    pub fn with_realm(mut self, realm: impl Into<String>) -> Self {
+        self.value
+            .insert(String::from("realm"), Value::from(realm.into()));
+        self
+    }
+
+    pub fn scheme(&self) -> Option<&Value> {
+        self.value.get("scheme")
+    }
+
+    pub fn principal(&self) -> Option<&Value> {
+        self.value.get("principal")
+    }
+
+    pub fn realm(&self) -> Option<&Value> {
+        self.value.get("realm")
+    }
+}
+
+impl Default for AuthToken {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+// Credentials are left out, so that tokens can be logged safely.
+impl fmt::Debug for AuthToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut keys: Vec<&String> = self.value.keys().collect();
+        keys.sort();
+        let mut debug = f.debug_struct("AuthToken");
+        for key in keys {
+            match key.as_str() {
+                "credentials" => debug.field(key, &"<redacted>"),
+                _ => debug.field(key, &self.value[key]),
+            };
+        }
+        debug.finish()
+    }
+}
+
+impl From<AuthToken> for HashMap<String, Value> {
+    fn from(token: AuthToken) -> Self {
+        token.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic() {
+        let token = AuthToken::basic("neo4j", "secret").with_realm("native");
+        assert_eq!(token.scheme(), Some(&Value::from("basic")));
+        assert_eq!(token.principal(), Some(&Value::from("neo4j")));
+        assert_eq!(token.realm(), Some(&Value::from("native")));
+
+        let debug = format!("{:?}", token);
+        assert!(debug.contains("neo4j"));
+        assert!(!debug.contains("secret"));
+
+        let map = HashMap::from(token);
+        assert_eq!(map.get("credentials"), Some(&Value::from("secret")));
+    }
+
+    #[test]
+    fn none() {
+        let map = HashMap::from(AuthToken::default());
+        assert_eq!(map.len(), 1);
+        assert_eq!(map.get("scheme"), Some(&Value::from("none")));
+    }
+}
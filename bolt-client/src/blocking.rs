@@ -0,0 +1,389 @@
+//! A synchronous client for Bolt-compatible servers.
+//!
+//! The blocking [`Client`] wraps the asynchronous [`Client`](crate::Client), driving each
+//! request to completion on the current thread, so no async runtime is required.
+//!
+//! Timeouts are set on the socket instead of the client, since a blocking read cannot be
+//! interrupted: use [`TcpConfig`](crate::TcpConfig) with
+//! [`Stream::connect_with_config`] to limit the time spent connecting, and
+//! [`Stream::set_read_timeout`] and [`Stream::set_write_timeout`] to limit the time spent
+//! on each read and write afterwards. Reads and writes that time out fail with
+//! [`Error::Timeout`], after which the client is [`Defunct`](ServerState::Defunct).
+//!
+//! # Example
+//! ```no_run
+//! use std::convert::TryFrom;
+//! use std::iter::FromIterator;
+//!
+//! use bolt_client::{blocking::*, Metadata};
+//! use bolt_proto::{message::*, version::*};
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let stream = Stream::connect("127.0.0.1:7687", None::<String>)?;
+//!     let mut client = Client::new(stream, &[V4_1, V4_0, 0, 0])?;
+//!     let response = client.hello(Some(Metadata::from_iter(vec![
+//!         ("user_agent", "my-client-name/1.0"),
+//!         ("scheme", "basic"),
+//!         ("principal", "neo4j"),
+//!         ("credentials", "neo4j"),
+//!     ])))?;
+//!     assert!(Success::try_from(response).is_ok());
+//!
+//!     client.run_with_metadata("RETURN 1 as num;", None, None)?;
+//!     let (response, records) = client.pull(Some(Metadata::from_iter(vec![("n", -1)])))?;
+//!     assert!(Success::try_from(response).is_ok());
+//!     println!("{:?}", records[0].fields());
+//!     Ok(())
+//! }
+//! ```
+use std::{
+    future::Future,
+    io::{self, Read, Write},
+    time::Instant,
+};
+
+use bolt_proto::{message::Record, Message};
+use futures_util::io::AllowStdIo;
+
+use crate::{error::*, Metadata, Params, ServerState};
+
+mod stream;
+
+pub use stream::Stream;
+
+/// A synchronous client for Bolt servers, wrapping any [`Read`] + [`Write`] stream.
+///
+/// Each method blocks until the corresponding method of the asynchronous
+/// [`Client`](crate::Client) completes; see its documentation for details on the
+/// messages sent and the responses to expect.
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+#[derive(Debug)]
+pub struct Client<S: Read + Write> {
+    client: crate::Client<AllowStdIo<S>>,
+}
+
+impl<S: Read + Write> Client<S> {
+    /// Attempt to create a new client from a stream. A handshake will be performed with
+    /// the provided protocol versions, and, if this succeeds, a Client will be returned.
+    pub fn new(stream: S, preferred_versions: &[u32; 4]) -> Result<Self> {
+        Ok(Self {
+            client: block_on(crate::Client::new(
+                AllowStdIo::new(stream),
+                preferred_versions,
+            ))?,
+        })
+    }
+
+    /// Get the current version of this client.
+    pub fn version(&self) -> u32 {
+        self.client.version()
+    }
+
+    /// See [`Client::utc_date_times`](crate::Client::utc_date_times).
+    pub fn utc_date_times(&self) -> bool {
+        self.client.utc_date_times()
+    }
+
+    /// Get a reference to the underlying stream, for example to change the read and write
+    /// timeouts of a [`Stream`].
+    pub fn get_ref(&self) -> &S {
+        self.client.get_ref().get_ref()
+    }
+
+    /// See [`Client::default_database`](crate::Client::default_database).
+    pub fn default_database(&self) -> Option<&str> {
+        self.client.default_database()
+    }
+
+    /// See [`Client::set_default_database`](crate::Client::set_default_database).
+    pub fn set_default_database(&mut self, database: Option<String>) {
+        self.client.set_default_database(database)
+    }
+
+    /// See [`Client::created_at`](crate::Client::created_at).
+    pub fn created_at(&self) -> Instant {
+        self.client.created_at()
+    }
+
+    /// See [`Client::last_used`](crate::Client::last_used).
+    pub fn last_used(&self) -> Instant {
+        self.client.last_used()
+    }
+
+    /// See [`Client::state`](crate::Client::state).
+    pub fn state(&self) -> ServerState {
+        self.client.state()
+    }
+
+    /// See [`Client::ensure_ready`](crate::Client::ensure_ready).
+    pub fn ensure_ready(&mut self) -> Result<()> {
+        block_on(self.client.ensure_ready())
+    }
+
+    /// See [`Client::is_broken`](crate::Client::is_broken).
+    pub fn is_broken(&self) -> bool {
+        self.client.is_broken()
+    }
+
+    /// See [`Client::pipeline`](crate::Client::pipeline).
+    pub fn pipeline(&mut self, messages: Vec<Message>) -> Result<Vec<Message>> {
+        block_on(self.client.pipeline(messages))
+    }
+
+    /// See [`Client::init`](crate::Client::init).
+    pub fn init(
+        &mut self,
+        client_name: impl Into<String>,
+        auth_token: Metadata,
+    ) -> Result<Message> {
+        block_on(self.client.init(client_name, auth_token))
+    }
+
+    /// See [`Client::run`](crate::Client::run).
+    pub fn run(
+        &mut self,
+        statement: impl Into<String>,
+        parameters: Option<Params>,
+    ) -> Result<Message> {
+        block_on(self.client.run(statement, parameters))
+    }
+
+    /// See [`Client::discard_all`](crate::Client::discard_all).
+    pub fn discard_all(&mut self) -> Result<Message> {
+        block_on(self.client.discard_all())
+    }
+
+    /// See [`Client::pull_all`](crate::Client::pull_all).
+    pub fn pull_all(&mut self) -> Result<(Message, Vec<Record>)> {
+        block_on(self.client.pull_all())
+    }
+
+    /// See [`Client::ack_failure`](crate::Client::ack_failure).
+    pub fn ack_failure(&mut self) -> Result<Message> {
+        block_on(self.client.ack_failure())
+    }
+
+    /// See [`Client::reset`](crate::Client::reset).
+    pub fn reset(&mut self) -> Result<Message> {
+        block_on(self.client.reset())
+    }
+
+    /// See [`Client::hello`](crate::Client::hello).
+    pub fn hello(&mut self, metadata: Option<Metadata>) -> Result<Message> {
+        block_on(self.client.hello(metadata))
+    }
+
+    /// See [`Client::goodbye`](crate::Client::goodbye).
+    pub fn goodbye(&mut self) -> Result<()> {
+        block_on(self.client.goodbye())
+    }
+
+    /// See [`Client::run_with_metadata`](crate::Client::run_with_metadata).
+    pub fn run_with_metadata(
+        &mut self,
+        statement: impl Into<String>,
+        parameters: Option<Params>,
+        metadata: Option<Metadata>,
+    ) -> Result<Message> {
+        block_on(
+            self.client
+                .run_with_metadata(statement, parameters, metadata),
+        )
+    }
+
+    /// See [`Client::begin`](crate::Client::begin).
+    pub fn begin(&mut self, metadata: Option<Metadata>) -> Result<Message> {
+        block_on(self.client.begin(metadata))
+    }
+
+    /// See [`Client::commit`](crate::Client::commit).
+    pub fn commit(&mut self) -> Result<Message> {
+        block_on(self.client.commit())
+    }
+
+    /// See [`Client::rollback`](crate::Client::rollback).
+    pub fn rollback(&mut self) -> Result<Message> {
+        block_on(self.client.rollback())
+    }
+
+    /// See [`Client::discard`](crate::Client::discard).
+    pub fn discard(&mut self, metadata: Option<Metadata>) -> Result<Message> {
+        block_on(self.client.discard(metadata))
+    }
+
+    /// See [`Client::pull`](crate::Client::pull).
+    pub fn pull(&mut self, metadata: Option<Metadata>) -> Result<(Message, Vec<Record>)> {
+        block_on(self.client.pull(metadata))
+    }
+
+    /// See [`Client::logon`](crate::Client::logon).
+    pub fn logon(&mut self, auth: Metadata) -> Result<Message> {
+        block_on(self.client.logon(auth))
+    }
+
+    /// See [`Client::logoff`](crate::Client::logoff).
+    pub fn logoff(&mut self) -> Result<Message> {
+        block_on(self.client.logoff())
+    }
+
+    /// See [`Client::telemetry`](crate::Client::telemetry).
+    pub fn telemetry(&mut self, api: i64) -> Result<Message> {
+        block_on(self.client.telemetry(api))
+    }
+}
+
+// Drive a request of the asynchronous client to completion. Sockets fail reads and writes
+// that exceed their timeout with WouldBlock or TimedOut, depending on the platform, which
+// leaves the client defunct like any other IO error.
+fn block_on<T>(future: impl Future<Output = Result<T>>) -> Result<T> {
+    futures_executor::block_on(future).map_err(|error| match error {
+        Error::IOError(error) | Error::ProtocolError(bolt_proto::error::Error::IOError(error))
+            if is_timeout(&error) =>
+        {
+            Error::Timeout
+        }
+        other => other,
+    })
+}
+
+fn is_timeout(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        convert::{TryFrom, TryInto},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use bolt_proto::{message::*, version::*, Value};
+    use bytes::Bytes;
+
+    use super::*;
+
+    fn receive(stream: &mut impl Read) -> Message {
+        futures_executor::block_on(Message::from_stream(AllowStdIo::new(stream))).unwrap()
+    }
+
+    fn send(stream: &mut impl Write, message: Message) {
+        let chunks: Vec<Bytes> = message.try_into().unwrap();
+        for chunk in chunks {
+            stream.write_all(&chunk).unwrap();
+        }
+        stream.flush().unwrap();
+    }
+
+    // Respond to a handshake with Bolt v4.1, then to HELLO, RUN and PULL with canned
+    // responses.
+    fn serve(mut stream: impl Read + Write) {
+        let mut handshake = [0; 20];
+        stream.read_exact(&mut handshake).unwrap();
+        stream.write_all(&V4_1.to_be_bytes()).unwrap();
+        stream.flush().unwrap();
+
+        let success = || Message::Success(Success::new(HashMap::new()));
+        assert!(matches!(receive(&mut stream), Message::Hello(_)));
+        send(&mut stream, success());
+        assert!(matches!(receive(&mut stream), Message::RunWithMetadata(_)));
+        send(&mut stream, success());
+        assert!(matches!(receive(&mut stream), Message::Pull(_)));
+        send(
+            &mut stream,
+            Message::Record(Record::new(vec![Value::from(1)])),
+        );
+        send(&mut stream, success());
+    }
+
+    #[test]
+    fn hello_run_pull() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = thread::spawn(move || serve(listener.accept().unwrap().0));
+
+        let stream = Stream::connect(addr, None::<String>).unwrap();
+        let mut client = Client::new(stream, &[V4_1, V4_0, 0, 0]).unwrap();
+        assert_eq!(client.version(), V4_1);
+        assert!(Success::try_from(client.hello(None).unwrap()).is_ok());
+        assert!(
+            Success::try_from(client.run_with_metadata("RETURN 1;", None, None).unwrap()).is_ok()
+        );
+        let (response, records) = client.pull(None).unwrap();
+        assert!(Success::try_from(response).is_ok());
+        assert_eq!(records[0].fields(), &[Value::from(1)]);
+        assert!(matches!(
+            client.run("RETURN 1;", None),
+            Err(Error::UnsupportedOperation(V4_1))
+        ));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn read_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = thread::spawn(move || {
+            // Never respond to the handshake
+            let (mut stream, _) = listener.accept().unwrap();
+            while stream.read(&mut [0; 1024]).unwrap_or(0) > 0 {}
+
+            // Respond to the handshake and HELLO, but not to RUN
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut handshake = [0; 20];
+            stream.read_exact(&mut handshake).unwrap();
+            stream.write_all(&V4_1.to_be_bytes()).unwrap();
+            assert!(matches!(receive(&mut stream), Message::Hello(_)));
+            send(&mut stream, Message::Success(Success::new(HashMap::new())));
+            while stream.read(&mut [0; 1024]).unwrap_or(0) > 0 {}
+        });
+
+        let stream = Stream::connect(addr, None::<String>).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_millis(50)))
+            .unwrap();
+        assert!(matches!(
+            Client::new(stream, &[V4_1, 0, 0, 0]),
+            Err(Error::Timeout)
+        ));
+
+        let stream = Stream::connect(addr, None::<String>).unwrap();
+        let mut client = Client::new(stream, &[V4_1, 0, 0, 0]).unwrap();
+        client.hello(None).unwrap();
+        client
+            .get_ref()
+            .set_read_timeout(Some(Duration::from_millis(50)))
+            .unwrap();
+        assert!(matches!(
+            client.run_with_metadata("RETURN 1;", None, None),
+            Err(Error::Timeout)
+        ));
+        assert!(client.is_broken());
+        drop(client);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn handshake_failed() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut handshake = [0; 20];
+            stream.read_exact(&mut handshake).unwrap();
+            stream.write_all(&[0, 0, 0, 0]).unwrap();
+        });
+
+        let stream = Stream::connect(addr, None::<String>).unwrap();
+        assert!(matches!(
+            Client::new(stream, &[V4_1, 0, 0, 0]),
+            Err(Error::HandshakeFailed(_))
+        ));
+        handle.join().unwrap();
+    }
+}
@@ -0,0 +1,281 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    io::{self, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::{Duration, Instant},
+};
+
+use rustls::{ClientSession, Session, StreamOwned};
+use socket2::SockRef;
+
+use crate::{error::*, ConnectionUri, TcpConfig, TlsConfig};
+
+/// A convenient wrapper around a blocking [`TcpStream`](std::net::TcpStream), optionally
+/// secured with TLS.
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub enum Stream {
+    Tcp(TcpStream),
+    SecureTcp(Box<StreamOwned<ClientSession, TcpStream>>),
+}
+
+impl Stream {
+    /// Establish a connection with a remote socket. If a domain is provided, TLS
+    /// negotiation will be attempted using the default [`TlsConfig`] for that domain.
+    pub fn connect(addr: impl ToSocketAddrs, domain: Option<impl AsRef<str>>) -> Result<Self> {
+        let tls_config = domain.map(|domain| TlsConfig::new(domain.as_ref()));
+        Self::connect_with_config(addr, tls_config.as_ref(), &TcpConfig::default())
+    }
+
+    /// Establish a connection with the server identified by a [`ConnectionUri`],
+    /// negotiating TLS if required by its scheme.
+    pub fn connect_uri(uri: &ConnectionUri) -> Result<Self> {
+        let tls_config = uri.tls_config();
+        Self::connect_with_config(uri.addr(), tls_config.as_ref(), &TcpConfig::default())
+    }
+
+    /// Establish a connection with a remote socket and negotiate TLS using the given
+    /// [`TlsConfig`]. The TLS handshake is completed before returning.
+    pub fn connect_tls(addr: impl ToSocketAddrs, tls_config: &TlsConfig) -> Result<Self> {
+        Self::connect_with_config(addr, Some(tls_config), &TcpConfig::default())
+    }
+
+    /// Establish a connection with a remote socket using the given [`TcpConfig`]. If a
+    /// [`TlsConfig`] is provided, the TLS handshake is completed before returning.
+    pub fn connect_with_config(
+        addr: impl ToSocketAddrs,
+        tls_config: Option<&TlsConfig>,
+        tcp_config: &TcpConfig,
+    ) -> Result<Self> {
+        let deadline = tcp_config
+            .connect_timeout()
+            .map(|timeout| Instant::now() + timeout);
+        let stream = connect_tcp(addr, tcp_config.connect_timeout()).map_err(timeout_error)?;
+        tcp_config.configure(SockRef::from(&stream))?;
+        let tls_config = match tls_config {
+            Some(tls_config) => tls_config,
+            None => return Ok(Stream::Tcp(stream)),
+        };
+
+        let session = ClientSession::new(&tls_config.client_config(), tls_config.dns_name_ref()?);
+        let mut stream = StreamOwned::new(session, stream);
+        while stream.sess.is_handshaking() {
+            // Bound each step of the handshake by what remains of the connect timeout
+            if let Some(deadline) = deadline {
+                let remaining = deadline
+                    .checked_duration_since(Instant::now())
+                    .filter(|remaining| *remaining > Duration::from_secs(0))
+                    .ok_or(Error::Timeout)?;
+                stream.sock.set_read_timeout(Some(remaining))?;
+                stream.sock.set_write_timeout(Some(remaining))?;
+            }
+            stream
+                .sess
+                .complete_io(&mut stream.sock)
+                .map_err(timeout_error)?;
+        }
+        if deadline.is_some() {
+            stream.sock.set_read_timeout(None)?;
+            stream.sock.set_write_timeout(None)?;
+        }
+        Ok(Stream::SecureTcp(Box::new(stream)))
+    }
+
+    /// Set the maximum duration to wait for each read from the underlying socket. Reads
+    /// that time out fail with an [`io::Error`] of kind
+    /// [`WouldBlock`](io::ErrorKind::WouldBlock) or [`TimedOut`](io::ErrorKind::TimedOut),
+    /// depending on the platform, which a blocking [`Client`](super::Client) reports as
+    /// [`Error::Timeout`]. A value of `None` (the default) waits indefinitely.
+    ///
+    /// Set this before passing the stream to [`Client::new`](super::Client::new) to limit
+    /// the time spent on the handshake, or use [`Client::get_ref`](super::Client::get_ref)
+    /// to change it later.
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        Ok(self.tcp_stream().set_read_timeout(timeout)?)
+    }
+
+    /// Set the maximum duration to wait for each write to the underlying socket, in the same
+    /// way as [`set_read_timeout`](Stream::set_read_timeout).
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<()> {
+        Ok(self.tcp_stream().set_write_timeout(timeout)?)
+    }
+
+    pub fn read_timeout(&self) -> Result<Option<Duration>> {
+        Ok(self.tcp_stream().read_timeout()?)
+    }
+
+    pub fn write_timeout(&self) -> Result<Option<Duration>> {
+        Ok(self.tcp_stream().write_timeout()?)
+    }
+
+    fn tcp_stream(&self) -> &TcpStream {
+        match self {
+            Stream::Tcp(tcp_stream) => tcp_stream,
+            Stream::SecureTcp(tls_stream) => &tls_stream.sock,
+        }
+    }
+}
+
+fn connect_tcp(addr: impl ToSocketAddrs, timeout: Option<Duration>) -> io::Result<TcpStream> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return TcpStream::connect(addr),
+    };
+    // Like TcpStream::connect, try each address in turn and return the last error
+    let mut last_error = None;
+    for addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(error) => last_error = Some(error),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "could not resolve to any addresses",
+        )
+    }))
+}
+
+// Timed out socket operations fail with WouldBlock on some platforms
+fn timeout_error(error: io::Error) -> Error {
+    match error.kind() {
+        io::ErrorKind::TimedOut | io::ErrorKind::WouldBlock => Error::Timeout,
+        _ => error.into(),
+    }
+}
+
+impl Debug for Stream {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Stream::Tcp(tcp_stream) => f.debug_tuple("Tcp").field(tcp_stream).finish(),
+            Stream::SecureTcp(tls_stream) => {
+                f.debug_tuple("SecureTcp").field(&tls_stream.sock).finish()
+            }
+        }
+    }
+}
+
+impl Read for Stream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(tcp_stream) => tcp_stream.read(buf),
+            Stream::SecureTcp(tls_stream) => tls_stream.read(buf),
+        }
+    }
+}
+
+impl Write for Stream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Stream::Tcp(tcp_stream) => tcp_stream.write(buf),
+            Stream::SecureTcp(tls_stream) => tls_stream.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Stream::Tcp(tcp_stream) => tcp_stream.flush(),
+            Stream::SecureTcp(tls_stream) => tls_stream.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{net::TcpListener, sync::Arc, thread};
+
+    use rustls::{Certificate, NoClientAuth, PrivateKey, ServerConfig, ServerSession};
+
+    use super::*;
+
+    // Start a TLS server with a self-signed certificate that accepts a single connection
+    // and echoes back 4 bytes.
+    fn echo_server() -> (String, thread::JoinHandle<()>) {
+        let certificate = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let mut config = ServerConfig::new(NoClientAuth::new());
+        config
+            .set_single_cert(
+                vec![Certificate(certificate.serialize_der().unwrap())],
+                PrivateKey(certificate.serialize_private_key_der()),
+            )
+            .unwrap();
+        let config = Arc::new(config);
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut stream = StreamOwned::new(ServerSession::new(&config), stream);
+            let mut buf = [0; 4];
+            if stream.read_exact(&mut buf).is_ok() {
+                let _ = stream.write_all(&buf);
+                let _ = stream.flush();
+            }
+        });
+        (addr, handle)
+    }
+
+    #[test]
+    fn secure_tcp() {
+        let (addr, handle) = echo_server();
+        let mut stream =
+            Stream::connect_tls(&addr, &TlsConfig::new("localhost").trust_all()).unwrap();
+        stream.write_all(b"ping").unwrap();
+        stream.flush().unwrap();
+        let mut buf = [0; 4];
+        stream.read_exact(&mut buf).unwrap();
+        assert_eq!(&buf, b"ping");
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn tcp_config() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let tcp_config = TcpConfig::default()
+            .with_keepalive(Duration::from_secs(60))
+            .with_nodelay(true);
+
+        let stream = Stream::connect_with_config(addr, None, &tcp_config).unwrap();
+        match &stream {
+            Stream::Tcp(tcp_stream) => {
+                assert!(tcp_stream.nodelay().unwrap());
+                assert!(SockRef::from(tcp_stream).keepalive().unwrap());
+            }
+            other => panic!("expected TCP stream, got {:?}", other),
+        }
+        stream
+            .set_write_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        assert_eq!(
+            stream.write_timeout().unwrap(),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(stream.read_timeout().unwrap(), None);
+    }
+
+    #[test]
+    fn connect_timeout() {
+        // The server accepts TCP connections but never completes a TLS handshake
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            while stream.read(&mut [0; 1024]).unwrap_or(0) > 0 {}
+        });
+
+        let tcp_config = TcpConfig::default().with_connect_timeout(Duration::from_millis(50));
+        let tls_config = TlsConfig::new("localhost").trust_all();
+        assert!(matches!(
+            Stream::connect_with_config(addr, Some(&tls_config), &tcp_config),
+            Err(Error::Timeout)
+        ));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn untrusted_certificate() {
+        let (addr, handle) = echo_server();
+        assert!(Stream::connect_tls(&addr, &TlsConfig::new("localhost")).is_err());
+        handle.join().unwrap();
+    }
+}
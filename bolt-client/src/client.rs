@@ -7,20 +7,36 @@
 // http://creativecommons.org/licenses/by-sa/3.0/ or send a letter to Creative Commons,
 // PO Box 1866, Mountain View, CA 94042, USA.
 
-use std::convert::TryInto;
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::{TryFrom, TryInto},
+    future::Future,
+    time::{Duration, Instant},
+};
 
 use bytes::*;
-use futures_util::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+use futures_timer::Delay;
+use futures_util::{
+    future::{self, Either},
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    pin_mut,
+};
 
-use bolt_proto::Message;
+use bolt_proto::{version::*, Message, Value};
 
-use crate::error::*;
+use crate::{error::*, Metadata};
 
+mod state;
 mod v1;
 mod v2;
 mod v3;
 mod v4;
 mod v4_1;
+mod v5_1;
+mod v5_4;
+
+pub use state::ServerState;
+use state::{Request, StateMachine};
 
 const PREAMBLE: [u8; 4] = [0x60, 0x60, 0xB0, 0x17];
 
@@ -29,13 +45,38 @@ const PREAMBLE: [u8; 4] = [0x60, 0x60, 0xB0, 0x17];
 pub struct Client<S: AsyncRead + AsyncWrite + Unpin> {
     stream: S,
     version: u32,
+    read_timeout: Option<Duration>,
+    default_database: Option<String>,
+    utc_date_times: bool,
+    state: StateMachine,
+    pending: VecDeque<Request>,
+    created_at: Instant,
+    last_used: Instant,
+    // The credentials a pool connector authenticated the connection with, if they were
+    // supplied by an auth provider
+    #[cfg(feature = "pool")]
+    auth_token: Option<crate::AuthToken>,
 }
 
 impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// Attempt to create a new client from an asynchronous stream. A handshake will be
     /// performed with the provided protocol versions, and, if this succeeds, a Client will be
     /// returned.
-    pub async fn new(mut stream: S, preferred_versions: &[u32; 4]) -> Result<Self> {
+    pub async fn new(stream: S, preferred_versions: &[u32; 4]) -> Result<Self> {
+        Self::handshake(stream, preferred_versions).await
+    }
+
+    /// Like [`new`](Client::new), but fail with [`Error::Timeout`] if the handshake does
+    /// not complete within the given duration.
+    pub async fn new_with_timeout(
+        stream: S,
+        preferred_versions: &[u32; 4],
+        timeout: Duration,
+    ) -> Result<Self> {
+        with_timeout(Some(timeout), Self::handshake(stream, preferred_versions)).await
+    }
+
+    async fn handshake(mut stream: S, preferred_versions: &[u32; 4]) -> Result<Self> {
         let mut preferred_versions_bytes = BytesMut::with_capacity(16);
         preferred_versions
             .iter()
@@ -47,8 +88,21 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
         let mut u32_bytes = [0, 0, 0, 0];
         stream.read_exact(&mut u32_bytes).await?;
         let version = u32::from_be_bytes(u32_bytes);
-        if preferred_versions.contains(&version) && version > 0 {
-            Ok(Self { stream, version })
+        if version > 0 && preferred_versions.iter().any(|&v| covers(v, version)) {
+            let now = Instant::now();
+            Ok(Self {
+                stream,
+                version,
+                read_timeout: None,
+                default_database: None,
+                utc_date_times: at_least(version, V5_0),
+                state: StateMachine::new(),
+                pending: VecDeque::new(),
+                created_at: now,
+                last_used: now,
+                #[cfg(feature = "pool")]
+                auth_token: None,
+            })
         } else {
             Err(Error::HandshakeFailed(*preferred_versions))
         }
@@ -59,12 +113,158 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
         self.version
     }
 
+    #[cfg(feature = "blocking")]
+    pub(crate) fn get_ref(&self) -> &S {
+        &self.stream
+    }
+
+    /// Check whether date-times with a UTC offset or time zone ID are sent using the
+    /// encoding based on UTC seconds, rather than the legacy encoding based on local
+    /// seconds. This is the case for clients using Bolt v5.0 or later, or Bolt v4.3/v4.4
+    /// once the server has accepted the "utc" patch requested in `HELLO` with
+    /// `{"patch_bolt": ["utc"]}`.
+    ///
+    /// Date-times are converted to the appropriate encoding before each request is sent.
+    pub fn utc_date_times(&self) -> bool {
+        self.utc_date_times
+    }
+
+    /// Get the maximum duration to wait for each message from the server.
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Set the maximum duration to wait for each message from the server. If a message
+    /// does not arrive in time, the request fails with [`Error::Timeout`] and the client
+    /// becomes [`Defunct`](ServerState::Defunct). A value of `None` (the default) waits indefinitely.
+    pub fn set_read_timeout(&mut self, timeout: Option<Duration>) {
+        self.read_timeout = timeout;
+    }
+
+    /// Get the database that `BEGIN` and `RUN` requests are sent to when their metadata
+    /// does not name one.
+    pub fn default_database(&self) -> Option<&str> {
+        self.default_database.as_deref()
+    }
+
+    /// Send `BEGIN` and `RUN` requests whose metadata does not contain a `db` entry to the
+    /// given database instead of the server's default database. This only applies to
+    /// clients using Bolt v4 or later, which support multiple databases.
+    pub fn set_default_database(&mut self, database: Option<String>) {
+        self.default_database = database;
+    }
+
+    // Add the default database to metadata for BEGIN and RUN, unless it names a database.
+    fn with_default_database(&self, metadata: Option<Metadata>) -> Metadata {
+        let mut metadata = metadata.unwrap_or_default();
+        if let (Some(database), true) = (&self.default_database, at_least(self.version, V4_0)) {
+            metadata
+                .value
+                .entry(String::from("db"))
+                .or_insert_with(|| Value::from(database.clone()));
+        }
+        metadata
+    }
+
+    /// Get the time at which the handshake with the server completed.
+    pub fn created_at(&self) -> Instant {
+        self.created_at
+    }
+
+    /// Get the time at which a message was last sent to or received from the server.
+    pub fn last_used(&self) -> Instant {
+        self.last_used
+    }
+
+    #[cfg(feature = "pool")]
+    pub(crate) fn auth_token(&self) -> Option<&crate::AuthToken> {
+        self.auth_token.as_ref()
+    }
+
+    #[cfg(feature = "pool")]
+    pub(crate) fn set_auth_token(&mut self, auth_token: Option<crate::AuthToken>) {
+        self.auth_token = auth_token;
+    }
+
+    /// Get the state of the server, as tracked from the requests sent by this client and
+    /// the summary messages received so far. Requests that are not valid in the current
+    /// state fail with [`Error::InvalidState`] without being sent to the server.
+    ///
+    /// While pipelined requests are awaiting their responses, the state reflects only the
+    /// responses received so far.
+    pub fn state(&self) -> ServerState {
+        self.state.state()
+    }
+
+    /// Check whether the connection is known to be unusable, because initialization
+    /// failed, the client said `GOODBYE`, or reading or writing a message failed, timed
+    /// out, or was cancelled, leaving the stream in an unknown state.
+    pub fn is_broken(&self) -> bool {
+        self.state() == ServerState::Defunct
+    }
+
+    /// Return the server to the [`Ready`](ServerState::Ready) state, if it is not ready or
+    /// responses to earlier requests are still pending, e.g. because a request future was
+    /// dropped after its message was sent. A `RESET` message is sent, terminating any
+    /// open result stream or transaction and clearing any failure, and all pending
+    /// responses are read and discarded.
+    ///
+    /// If the server does not respond to the `RESET` with `SUCCESS`, its response is
+    /// returned in [`Error::ResetFailed`] and the client becomes
+    /// [`Defunct`](ServerState::Defunct).
+    pub async fn ensure_ready(&mut self) -> Result<()> {
+        if self.state() == ServerState::Ready && self.pending.is_empty() {
+            return Ok(());
+        }
+
+        self.send_message(Message::Reset).await?;
+        let mut response = self.read_message().await?;
+        while !self.pending.is_empty() {
+            response = self.read_message().await?;
+        }
+        match response {
+            Message::Success(_) if self.state() == ServerState::Ready => Ok(()),
+            other => {
+                self.state.set_defunct();
+                Err(Error::ResetFailed(other))
+            }
+        }
+    }
+
     pub(crate) async fn read_message(&mut self) -> Result<Message> {
-        let message = Message::from_stream(&mut self.stream).await?;
+        self.state.start_io();
+        let stream = &mut self.stream;
+        let read = async { Ok(Message::from_stream(stream).await?) };
+        let message = with_timeout(self.read_timeout, read)
+            .await
+            .inspect_err(|_| self.state.set_defunct())?;
+        self.state.finish_io();
+        self.last_used = Instant::now();
 
         #[cfg(test)]
         println!("<<< {:?}\n", message);
 
+        match &message {
+            Message::Record(_) => {}
+            Message::Success(success) => {
+                if let Some(request) = self.pending.pop_front() {
+                    if request == Request::Init && accepts_utc_patch(success.metadata()) {
+                        self.utc_date_times = true;
+                    }
+                    self.state
+                        .success(request, self.version, success.metadata());
+                }
+            }
+            Message::Failure(_) => {
+                if let Some(request) = self.pending.pop_front() {
+                    self.state.failure(request);
+                }
+            }
+            _ => {
+                self.pending.pop_front();
+            }
+        }
+
         Ok(message)
     }
 
@@ -72,11 +272,55 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
         #[cfg(test)]
         println!(">>> {:?}", message);
 
+        let request = Request::from_message(&message);
+        self.check_request(request)?;
+        let message = message.convert_date_times(self.utc_date_times)?;
         let chunks: Vec<Bytes> = message.try_into()?;
+        self.write_chunks(chunks).await?;
+        self.flush().await?;
+        self.track_request(request);
+        Ok(())
+    }
+
+    // Fail if the message is a request that is not valid in the current server state.
+    // Requests pipelined after others are checked by the server instead, since their
+    // validity depends on responses that haven't been received yet.
+    fn check_request(&self, request: Option<Request>) -> Result<()> {
+        if let Some(request) = request {
+            if self.pending.is_empty() || self.state() == ServerState::Defunct {
+                self.state.validate(request, self.version)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn track_request(&mut self, request: Option<Request>) {
+        match request {
+            // The server closes the connection without responding to GOODBYE
+            Some(Request::Goodbye) => self.state.set_defunct(),
+            Some(request) => self.pending.push_back(request),
+            None => {}
+        }
+    }
+
+    async fn write_chunks(&mut self, chunks: Vec<Bytes>) -> Result<()> {
+        self.state.start_io();
         for chunk in chunks {
-            self.stream.write_all(&chunk).await?;
+            if let Err(error) = self.stream.write_all(&chunk).await {
+                self.state.set_defunct();
+                return Err(error.into());
+            }
         }
-        self.stream.flush().await?;
+        Ok(())
+    }
+
+    async fn flush(&mut self) -> Result<()> {
+        self.stream.flush().await.map_err(|error| {
+            self.state.set_defunct();
+            Error::from(error)
+        })?;
+        self.state.finish_io();
+        self.last_used = Instant::now();
         Ok(())
     }
 
@@ -110,16 +354,25 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
         // This Vec is too small if we're expecting some RECORD messages, so there's no "good" size
         let mut responses = Vec::with_capacity(messages.len());
 
+        // Convert every message before writing any of them, so that a message that can't be
+        // sent doesn't leave the ones before it written without their responses being read
+        let mut requests = Vec::with_capacity(messages.len());
         for message in messages {
             #[cfg(test)]
             println!(">>> {:?}", message);
 
+            let request = Request::from_message(&message);
+            let message = message.convert_date_times(self.utc_date_times)?;
             let chunks: Vec<Bytes> = message.try_into()?;
-            for chunk in chunks {
-                self.stream.write_all(&chunk).await?;
-            }
+            requests.push((request, chunks));
+        }
+
+        for (request, chunks) in requests {
+            self.check_request(request)?;
+            self.write_chunks(chunks).await?;
+            self.track_request(request);
         }
-        self.stream.flush().await?;
+        self.flush().await?;
 
         for _ in 0..responses.capacity() {
             let mut response = self.read_message().await?;
@@ -132,3 +385,47 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
         Ok(responses)
     }
 }
+
+// Check whether a version is the same as or later than the given minimum. Versions can't
+// be compared directly, since the minor version is held in the higher byte.
+pub(crate) fn at_least(version: u32, minimum: u32) -> bool {
+    (version & 0xFF, version >> 8 & 0xFF) >= (minimum & 0xFF, minimum >> 8 & 0xFF)
+}
+
+// Check whether the server agreed to send and receive date-times in UTC, in response to a
+// HELLO with {"patch_bolt": ["utc"]}. Only supported by servers using Bolt v4.3 or v4.4.
+fn accepts_utc_patch(metadata: &HashMap<String, Value>) -> bool {
+    metadata
+        .get("patch_bolt")
+        .and_then(|patches| Vec::<String>::try_from(patches.clone()).ok())
+        .is_some_and(|patches| patches.iter().any(|patch| patch == "utc"))
+}
+
+// Check whether a preferred version sent in the handshake, which may be a range of versions,
+// covers the version chosen by the server.
+fn covers(preferred: u32, version: u32) -> bool {
+    let (major, minor, back) = (
+        preferred & 0xFF,
+        (preferred >> 8) & 0xFF,
+        (preferred >> 16) & 0xFF,
+    );
+    version >> 16 == 0
+        && version & 0xFF == major
+        && (minor.saturating_sub(back)..=minor).contains(&((version >> 8) & 0xFF))
+}
+
+pub(crate) async fn with_timeout<T>(
+    timeout: Option<Duration>,
+    future: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    match timeout {
+        Some(timeout) => {
+            pin_mut!(future);
+            match future::select(future, Delay::new(timeout)).await {
+                Either::Left((result, _)) => result,
+                Either::Right(_) => Err(Error::Timeout),
+            }
+        }
+        None => future.await,
+    }
+}
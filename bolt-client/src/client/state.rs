@@ -0,0 +1,576 @@
+use std::collections::HashMap;
+
+use bolt_proto::{version::*, Message, Value};
+
+use super::at_least;
+use crate::error::*;
+
+/// The state of the server a [`Client`](crate::Client) is connected to, as tracked by
+/// the client from the requests it sends and the summary messages it receives.
+///
+/// The states follow the server state machine described in the Bolt protocol
+/// specification. Bolt v1-2 clients never enter the transactional states, since explicit
+/// transactions are only available from Bolt v3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerState {
+    /// The handshake has completed, but the connection has not been initialized with
+    /// `INIT` or `HELLO` yet.
+    Connected,
+    /// The connection has been initialized with `HELLO`, but is not authenticated. Only
+    /// used from Bolt v5.1, where credentials are sent with `LOGON` instead of `HELLO`,
+    /// and `LOGOFF` returns to this state.
+    Authentication,
+    /// The connection is ready to accept a new query or transaction.
+    Ready,
+    /// An auto-commit query has produced a result stream that has not been fully
+    /// consumed with `PULL`/`PULL_ALL` or `DISCARD`/`DISCARD_ALL` yet.
+    Streaming,
+    /// An explicit transaction is open, and all of its result streams are consumed.
+    TxReady,
+    /// An explicit transaction is open, and at least one of its result streams has not
+    /// been fully consumed yet.
+    TxStreaming,
+    /// A request has failed. The server will respond with `IGNORED` to all requests until
+    /// the failure is acknowledged with `RESET` or `ACK_FAILURE`.
+    Failed,
+    /// The connection is unusable, because initialization failed, the client said
+    /// `GOODBYE`, or reading or writing a message failed, timed out, or was cancelled.
+    Defunct,
+}
+
+// The kind of a request awaiting its summary message from the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Request {
+    Init,
+    Run,
+    DiscardAll,
+    PullAll,
+    AckFailure,
+    Reset,
+    Goodbye,
+    Begin,
+    Commit,
+    Rollback,
+    Discard,
+    Pull,
+    Logon,
+    Logoff,
+    Telemetry,
+}
+
+impl Request {
+    pub(crate) fn from_message(message: &Message) -> Option<Self> {
+        match message {
+            Message::Init(_) | Message::Hello(_) => Some(Request::Init),
+            Message::Run(_) | Message::RunWithMetadata(_) => Some(Request::Run),
+            Message::DiscardAll => Some(Request::DiscardAll),
+            Message::PullAll => Some(Request::PullAll),
+            Message::AckFailure => Some(Request::AckFailure),
+            Message::Reset => Some(Request::Reset),
+            Message::Goodbye => Some(Request::Goodbye),
+            Message::Begin(_) => Some(Request::Begin),
+            Message::Commit => Some(Request::Commit),
+            Message::Rollback => Some(Request::Rollback),
+            Message::Discard(_) => Some(Request::Discard),
+            Message::Pull(_) => Some(Request::Pull),
+            Message::Logon(_) => Some(Request::Logon),
+            Message::Logoff => Some(Request::Logoff),
+            Message::Telemetry(_) => Some(Request::Telemetry),
+            Message::Record(_) | Message::Success(_) | Message::Failure(_) | Message::Ignored => {
+                None
+            }
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Request::Init => "INIT/HELLO",
+            Request::Run => "RUN",
+            Request::DiscardAll => "DISCARD_ALL",
+            Request::PullAll => "PULL_ALL",
+            Request::AckFailure => "ACK_FAILURE",
+            Request::Reset => "RESET",
+            Request::Goodbye => "GOODBYE",
+            Request::Begin => "BEGIN",
+            Request::Commit => "COMMIT",
+            Request::Rollback => "ROLLBACK",
+            Request::Discard => "DISCARD",
+            Request::Pull => "PULL",
+            Request::Logon => "LOGON",
+            Request::Logoff => "LOGOFF",
+            Request::Telemetry => "TELEMETRY",
+        }
+    }
+}
+
+/// Tracks the [`ServerState`] of a connection, along with the number of open result
+/// streams in an explicit transaction.
+#[derive(Debug, Clone)]
+pub(crate) struct StateMachine {
+    state: ServerState,
+    open_tx_streams: usize,
+    io_in_progress: bool,
+}
+
+impl StateMachine {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: ServerState::Connected,
+            open_tx_streams: 0,
+            io_in_progress: false,
+        }
+    }
+
+    pub(crate) fn state(&self) -> ServerState {
+        if self.io_in_progress {
+            ServerState::Defunct
+        } else {
+            self.state
+        }
+    }
+
+    /// Mark the start of a read or write. If it is never marked as finished, because the
+    /// future performing it was dropped, the stream may hold a partial message and the
+    /// connection is considered defunct.
+    pub(crate) fn start_io(&mut self) {
+        self.io_in_progress = true;
+    }
+
+    pub(crate) fn finish_io(&mut self) {
+        self.io_in_progress = false;
+    }
+
+    pub(crate) fn set_defunct(&mut self) {
+        self.state = ServerState::Defunct;
+    }
+
+    /// Check whether the request may be sent to a server in the current state.
+    pub(crate) fn validate(&self, request: Request, version: u32) -> Result<()> {
+        use Request::*;
+        use ServerState::*;
+
+        let allowed = match (self.state(), request) {
+            (Defunct, _) => false,
+            (Connected, request) => matches!(request, Init | Goodbye),
+            (Authentication, request) => matches!(request, Logon | Reset | Goodbye),
+            (_, Reset) | (_, AckFailure) | (_, Goodbye) => true,
+            // The server ignores everything else until the failure is acknowledged
+            (Failed, _) => true,
+            (_, Init) | (_, Logon) => false,
+            (Ready, request) => matches!(request, Run | Begin | Logoff | Telemetry),
+            (Streaming, request) => matches!(request, DiscardAll | PullAll | Discard | Pull),
+            (TxReady, request) => matches!(request, Run | Commit | Rollback),
+            (TxStreaming, Run) => at_least(version, V4_0),
+            (TxStreaming, request) => matches!(request, DiscardAll | PullAll | Discard | Pull),
+        };
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(Error::InvalidState(self.state(), request.name()))
+        }
+    }
+
+    /// Update the state after the server responded to the request with `SUCCESS`.
+    pub(crate) fn success(
+        &mut self,
+        request: Request,
+        version: u32,
+        metadata: &HashMap<String, Value>,
+    ) {
+        use Request::*;
+        use ServerState::*;
+
+        let has_more = metadata.get("has_more") == Some(&Value::from(true));
+        self.state = match (self.state, request) {
+            (_, Init) if at_least(version, V5_1) => Authentication,
+            (Authentication, Reset) | (_, Logoff) => Authentication,
+            (_, Init) | (_, Logon) | (_, Reset) | (_, AckFailure) | (_, Commit) | (_, Rollback) => {
+                self.open_tx_streams = 0;
+                Ready
+            }
+            (_, Goodbye) => Defunct,
+            (_, Begin) => TxReady,
+            (Ready, Run) | (Streaming, Run) => Streaming,
+            (TxReady, Run) | (TxStreaming, Run) => {
+                self.open_tx_streams += 1;
+                TxStreaming
+            }
+            (_, Pull) | (_, Discard) if has_more => self.state,
+            (TxStreaming, PullAll) | (TxStreaming, DiscardAll) => {
+                self.open_tx_streams = 0;
+                TxReady
+            }
+            (TxStreaming, Pull) | (TxStreaming, Discard) => {
+                self.open_tx_streams = self.open_tx_streams.saturating_sub(1);
+                if self.open_tx_streams == 0 {
+                    TxReady
+                } else {
+                    TxStreaming
+                }
+            }
+            (Streaming, _) => Ready,
+            (state, _) => state,
+        };
+    }
+
+    /// Update the state after the server responded to the request with `FAILURE`.
+    pub(crate) fn failure(&mut self, request: Request) {
+        self.state = match request {
+            // The server closes the connection when initialization, authentication or a
+            // reset fails
+            Request::Init | Request::Logon | Request::Reset | Request::Goodbye => {
+                ServerState::Defunct
+            }
+            _ => ServerState::Failed,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::FromIterator;
+
+    use super::*;
+
+    fn success(has_more: bool) -> HashMap<String, Value> {
+        HashMap::from_iter(vec![(String::from("has_more"), Value::from(has_more))])
+    }
+
+    fn ready(version: u32) -> StateMachine {
+        let mut machine = StateMachine::new();
+        machine.validate(Request::Init, version).unwrap();
+        machine.success(Request::Init, version, &HashMap::new());
+        machine
+    }
+
+    #[test]
+    fn connected() {
+        let machine = StateMachine::new();
+        assert_eq!(machine.state(), ServerState::Connected);
+        assert!(machine.validate(Request::Init, V4_1).is_ok());
+        assert!(machine.validate(Request::Goodbye, V4_1).is_ok());
+        assert!(matches!(
+            machine.validate(Request::Run, V4_1),
+            Err(Error::InvalidState(ServerState::Connected, "RUN"))
+        ));
+    }
+
+    #[test]
+    fn init_failure() {
+        let mut machine = StateMachine::new();
+        machine.failure(Request::Init);
+        assert_eq!(machine.state(), ServerState::Defunct);
+        assert!(machine.validate(Request::Reset, V4_1).is_err());
+    }
+
+    #[test]
+    fn auto_commit() {
+        let mut machine = ready(V4_1);
+        assert_eq!(machine.state(), ServerState::Ready);
+        for request in &[
+            Request::Init,
+            Request::Pull,
+            Request::Commit,
+            Request::Rollback,
+        ] {
+            assert!(machine.validate(*request, V4_1).is_err());
+        }
+
+        machine.success(Request::Run, V4_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Streaming);
+        assert!(machine.validate(Request::Run, V4_1).is_err());
+        assert!(machine.validate(Request::Begin, V4_1).is_err());
+        machine.success(Request::Pull, V4_1, &success(true));
+        assert_eq!(machine.state(), ServerState::Streaming);
+        machine.success(Request::Pull, V4_1, &success(false));
+        assert_eq!(machine.state(), ServerState::Ready);
+
+        machine.success(Request::Run, V4_1, &HashMap::new());
+        machine.success(Request::DiscardAll, V4_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Ready);
+    }
+
+    #[test]
+    fn explicit_transaction() {
+        let mut machine = ready(V4_1);
+        machine.success(Request::Begin, V4_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::TxReady);
+        assert!(machine.validate(Request::Begin, V4_1).is_err());
+        assert!(machine.validate(Request::Pull, V4_1).is_err());
+
+        // Bolt v4+ allows several open result streams in a transaction
+        machine.success(Request::Run, V4_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::TxStreaming);
+        assert!(machine.validate(Request::Run, V4_1).is_ok());
+        assert!(machine.validate(Request::Run, V3_0).is_err());
+        assert!(machine.validate(Request::Commit, V4_1).is_err());
+        machine.success(Request::Run, V4_1, &HashMap::new());
+        machine.success(Request::Pull, V4_1, &success(false));
+        assert_eq!(machine.state(), ServerState::TxStreaming);
+        machine.success(Request::Discard, V4_1, &success(false));
+        assert_eq!(machine.state(), ServerState::TxReady);
+
+        assert!(machine.validate(Request::Commit, V4_1).is_ok());
+        machine.success(Request::Commit, V4_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Ready);
+    }
+
+    #[test]
+    fn failure_and_reset() {
+        let mut machine = ready(V4_1);
+        machine.success(Request::Begin, V4_1, &HashMap::new());
+        machine.failure(Request::Run);
+        assert_eq!(machine.state(), ServerState::Failed);
+        // Requests are ignored by the server rather than rejected locally
+        assert!(machine.validate(Request::Pull, V4_1).is_ok());
+        machine.success(Request::Reset, V4_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Ready);
+
+        machine.failure(Request::Reset);
+        assert_eq!(machine.state(), ServerState::Defunct);
+    }
+
+    #[test]
+    fn authentication() {
+        let mut machine = StateMachine::new();
+        machine.success(Request::Init, V5_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Authentication);
+        for request in &[
+            Request::Run,
+            Request::Begin,
+            Request::Logoff,
+            Request::Telemetry,
+        ] {
+            assert!(machine.validate(*request, V5_1).is_err());
+        }
+        assert!(machine.validate(Request::Logon, V5_1).is_ok());
+        machine.success(Request::Logon, V5_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Ready);
+        assert!(machine.validate(Request::Logon, V5_1).is_err());
+
+        machine.success(Request::Telemetry, V5_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Ready);
+        machine.success(Request::Logoff, V5_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Authentication);
+        machine.success(Request::Reset, V5_1, &HashMap::new());
+        assert_eq!(machine.state(), ServerState::Authentication);
+
+        machine.failure(Request::Logon);
+        assert_eq!(machine.state(), ServerState::Defunct);
+    }
+
+    // Requests sent by a client over a stream update its state from the responses
+    #[cfg(feature = "tokio-stream")]
+    mod client {
+        use std::convert::{TryFrom, TryInto};
+
+        use bolt_proto::message::*;
+        use bytes::Bytes;
+        use futures_util::FutureExt;
+        use tokio::io::{AsyncReadExt, AsyncWriteExt, DuplexStream};
+        use tokio_util::compat::*;
+
+        use super::*;
+        use crate::{stream::Stream, Client, Metadata};
+
+        async fn respond(server: &mut DuplexStream, messages: Vec<Message>) {
+            Message::from_stream(server.compat()).await.unwrap();
+            for message in messages {
+                let chunks: Vec<Bytes> = message.try_into().unwrap();
+                for chunk in chunks {
+                    server.write_all(&chunk).await.unwrap();
+                }
+            }
+            server.flush().await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn logon_and_logoff() {
+            let (stream, mut server) = Stream::duplex(1024);
+            let handle = tokio::spawn(async move {
+                let mut handshake = [0; 20];
+                server.read_exact(&mut handshake).await.unwrap();
+                server.write_all(&V5_4.to_be_bytes()).await.unwrap();
+
+                let ok = || Message::Success(Success::new(HashMap::new()));
+                respond(&mut server, vec![ok()]).await; // HELLO
+                respond(&mut server, vec![ok()]).await; // LOGON
+                respond(&mut server, vec![ok()]).await; // TELEMETRY
+                respond(&mut server, vec![ok()]).await; // LOGOFF
+                let failure = Failure::new(HashMap::from_iter(vec![(
+                    String::from("code"),
+                    Value::from("Neo.ClientError.Security.Unauthorized"),
+                )]));
+                respond(&mut server, vec![Message::Failure(failure)]).await; // LOGON
+            });
+
+            let mut client = Client::new(stream.compat(), &[V5_4, 0, 0, 0])
+                .await
+                .unwrap();
+            let metadata = Metadata::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")])
+                .with_notifications_minimum_severity("WARNING")
+                .with_notifications_disabled_categories(vec!["HINT"]);
+            assert_eq!(
+                metadata.value.get("notifications_disabled_categories"),
+                Some(&Value::from(vec!["HINT"]))
+            );
+            client.hello(Some(metadata)).await.unwrap();
+            assert_eq!(client.state(), ServerState::Authentication);
+            assert!(matches!(
+                client.logoff().await,
+                Err(Error::InvalidState(ServerState::Authentication, "LOGOFF"))
+            ));
+
+            let auth = Metadata::from_iter(vec![("scheme", "none")]);
+            client.logon(auth.clone()).await.unwrap();
+            assert_eq!(client.state(), ServerState::Ready);
+            client
+                .telemetry(Telemetry::AUTO_COMMIT_TRANSACTION)
+                .await
+                .unwrap();
+            assert_eq!(client.state(), ServerState::Ready);
+            client.logoff().await.unwrap();
+            assert_eq!(client.state(), ServerState::Authentication);
+
+            let response = client.logon(auth).await.unwrap();
+            assert!(Failure::try_from(response).is_ok());
+            assert_eq!(client.state(), ServerState::Defunct);
+            handle.await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn client_state() {
+            let (stream, mut server) = Stream::duplex(1024);
+            let handle = tokio::spawn(async move {
+                let mut handshake = [0; 20];
+                server.read_exact(&mut handshake).await.unwrap();
+                server.write_all(&V4_1.to_be_bytes()).await.unwrap();
+
+                let ok = || Message::Success(Success::new(HashMap::new()));
+                let record = || Message::Record(Record::new(vec![Value::from(1)]));
+                respond(&mut server, vec![ok()]).await; // HELLO
+                respond(&mut server, vec![ok()]).await; // RUN
+                respond(&mut server, vec![record(), ok()]).await; // PULL
+                respond(&mut server, vec![ok()]).await; // BEGIN
+                respond(&mut server, vec![ok()]).await; // RUN
+                let has_more = Success::new(success(true));
+                respond(&mut server, vec![record(), Message::Success(has_more)]).await; // PULL
+                respond(&mut server, vec![record(), ok()]).await; // PULL
+                respond(&mut server, vec![ok()]).await; // COMMIT
+                let failure = Failure::new(HashMap::from_iter(vec![(
+                    String::from("code"),
+                    Value::from("Neo.ClientError.Statement.SyntaxError"),
+                )]));
+                respond(&mut server, vec![Message::Failure(failure)]).await; // RUN
+                respond(&mut server, vec![Message::Ignored]).await; // PULL
+                respond(&mut server, vec![ok()]).await; // RESET
+                respond(&mut server, vec![]).await; // GOODBYE
+            });
+
+            let mut client = Client::new(stream.compat(), &[V4_1, 0, 0, 0])
+                .await
+                .unwrap();
+            assert_eq!(client.state(), ServerState::Connected);
+            client.hello(None).await.unwrap();
+            assert_eq!(client.state(), ServerState::Ready);
+            assert!(matches!(
+                client.commit().await,
+                Err(Error::InvalidState(ServerState::Ready, "COMMIT"))
+            ));
+
+            client
+                .run_with_metadata("RETURN 1;", None, None)
+                .await
+                .unwrap();
+            assert_eq!(client.state(), ServerState::Streaming);
+            client.pull(None).await.unwrap();
+            assert_eq!(client.state(), ServerState::Ready);
+
+            client.begin(None).await.unwrap();
+            assert_eq!(client.state(), ServerState::TxReady);
+            client
+                .run_with_metadata("RETURN 1;", None, None)
+                .await
+                .unwrap();
+            assert_eq!(client.state(), ServerState::TxStreaming);
+            client.pull(None).await.unwrap();
+            assert_eq!(client.state(), ServerState::TxStreaming);
+            client.pull(None).await.unwrap();
+            assert_eq!(client.state(), ServerState::TxReady);
+            client.commit().await.unwrap();
+            assert_eq!(client.state(), ServerState::Ready);
+
+            let response = client
+                .run_with_metadata("RETURN", None, None)
+                .await
+                .unwrap();
+            assert!(Failure::try_from(response).is_ok());
+            assert_eq!(client.state(), ServerState::Failed);
+            let (response, _) = client.pull(None).await.unwrap();
+            assert!(matches!(response, Message::Ignored));
+            client.reset().await.unwrap();
+            assert_eq!(client.state(), ServerState::Ready);
+
+            client.goodbye().await.unwrap();
+            assert!(client.is_broken());
+            assert!(matches!(
+                client.run_with_metadata("RETURN 1;", None, None).await,
+                Err(Error::InvalidState(ServerState::Defunct, "RUN"))
+            ));
+            handle.await.unwrap();
+        }
+
+        #[tokio::test]
+        async fn ensure_ready() {
+            let (stream, mut server) = Stream::duplex(1024);
+            let handle = tokio::spawn(async move {
+                let mut handshake = [0; 20];
+                server.read_exact(&mut handshake).await.unwrap();
+                server.write_all(&V4_1.to_be_bytes()).await.unwrap();
+
+                let ok = || Message::Success(Success::new(HashMap::new()));
+                let record = || Message::Record(Record::new(vec![Value::from(1)]));
+                respond(&mut server, vec![ok()]).await; // HELLO
+                respond(&mut server, vec![ok()]).await; // RUN
+                respond(&mut server, vec![record(), ok()]).await; // PULL
+                respond(&mut server, vec![ok()]).await; // RESET
+                respond(&mut server, vec![]).await; // RUN
+                while server.read(&mut [0; 1024]).await.unwrap_or(0) > 0 {}
+            });
+
+            let mut client = Client::new(stream.compat(), &[V4_1, 0, 0, 0])
+                .await
+                .unwrap();
+            client.hello(None).await.unwrap();
+            client.ensure_ready().await.unwrap();
+
+            // Leave a result stream open, with the response to PULL still pending
+            client
+                .run_with_metadata("RETURN 1;", None, None)
+                .await
+                .unwrap();
+            client
+                .send_message(Message::Pull(Pull::new(HashMap::new())))
+                .await
+                .unwrap();
+            assert_eq!(client.state(), ServerState::Streaming);
+            client.ensure_ready().await.unwrap();
+            assert_eq!(client.state(), ServerState::Ready);
+
+            // Cancelling a request while awaiting its response leaves the stream in an unknown
+            // state
+            assert!(client
+                .run_with_metadata("RETURN 1;", None, None)
+                .now_or_never()
+                .is_none());
+            assert_eq!(client.state(), ServerState::Defunct);
+            assert!(matches!(
+                client.ensure_ready().await,
+                Err(Error::InvalidState(ServerState::Defunct, "RESET"))
+            ));
+            drop(client);
+            handle.await.unwrap();
+        }
+    }
+}
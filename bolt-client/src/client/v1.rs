@@ -183,14 +183,14 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// - `SUCCESS {…}` if the session was successfully reset
     /// - `FAILURE {"code": …​, "message": …​}` if a reset is not currently
     ///   possible
-    #[bolt_version(1, 2, 3, 4, 4.1)]
+    #[bolt_version(1, 2, 3, 4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn reset(&mut self) -> Result<Message> {
         self.send_message(Message::Reset).await?;
         self.read_message().await
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "tokio-stream"))]
 pub(crate) mod tests {
     use std::{convert::TryFrom, env, iter::FromIterator};
 
@@ -198,7 +198,7 @@ pub(crate) mod tests {
     use tokio::io::BufStream;
     use tokio_util::compat::*;
 
-    use crate::{skip_if_handshake_failed, stream, Metadata};
+    use crate::{skip_if_handshake_failed, stream, Metadata, ServerState};
 
     use super::*;
 
@@ -307,11 +307,12 @@ pub(crate) mod tests {
 
         // See https://github.com/neo4j/neo4j/pull/8050.
         // The current behavior is to simply close the connection on a failed INIT.
-        // Messages now fail to send since connection was closed
+        // The client is now defunct and refuses to send further messages.
+        assert_eq!(client.state(), ServerState::Defunct);
         let response = initialize_client(&mut client, true).await;
         assert!(matches!(
             response,
-            Err(Error::ProtocolError(bolt_proto::error::Error::IOError(_)))
+            Err(Error::InvalidState(ServerState::Defunct, _))
         ))
     }
 
@@ -443,8 +444,10 @@ pub(crate) mod tests {
         let client = get_initialized_client(V1_0).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        let response = client.discard_all().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
+        assert!(matches!(
+            client.discard_all().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
     }
 
     #[tokio::test]
@@ -467,9 +470,10 @@ pub(crate) mod tests {
         assert!(Success::try_from(response).is_ok());
         let response = client.discard_all().await.unwrap();
         assert!(Success::try_from(response).is_ok());
-        let (response, records) = client.pull_all().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
-        assert!(records.is_empty());
+        assert!(matches!(
+            client.pull_all().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
     }
 
     #[tokio::test]
@@ -514,7 +518,10 @@ pub(crate) mod tests {
         let client = get_initialized_client(V1_0).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        client.send_message(Message::Commit).await.unwrap();
+        client
+            .send_message(Message::Begin(Begin::new(Default::default())))
+            .await
+            .unwrap();
         assert!(matches!(
             client.read_message().await,
             Err(Error::ProtocolError(_))
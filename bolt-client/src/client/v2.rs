@@ -1,4 +1,4 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "tokio-stream"))]
 mod tests {
     use std::convert::TryFrom;
 
@@ -9,6 +9,7 @@ mod tests {
     use crate::client::v1::tests::*;
     use crate::error::*;
     use crate::skip_if_handshake_failed;
+    use crate::ServerState;
 
     #[tokio::test]
     async fn init() {
@@ -29,11 +30,12 @@ mod tests {
 
         // See https://github.com/neo4j/neo4j/pull/8050.
         // The current behavior is to simply close the connection on a failed INIT.
-        // Messages now fail to send since connection was closed
+        // The client is now defunct and refuses to send further messages.
+        assert_eq!(client.state(), ServerState::Defunct);
         let response = initialize_client(&mut client, true).await;
         assert!(matches!(
             response,
-            Err(Error::ProtocolError(bolt_proto::error::Error::IOError(_)))
+            Err(Error::InvalidState(ServerState::Defunct, _))
         ))
     }
 
@@ -125,8 +127,8 @@ mod tests {
         assert_eq!(
             records[0].fields(),
             &[Value::from(NaiveDateTime::new(
-                NaiveDate::from_ymd(2010, 3, 5),
-                NaiveTime::from_hms_nano(12, 30, 1, 500),
+                NaiveDate::from_ymd_opt(2010, 3, 5).unwrap(),
+                NaiveTime::from_hms_nano_opt(12, 30, 1, 500).unwrap(),
             ))]
         );
 
@@ -179,8 +181,11 @@ mod tests {
         assert_eq!(
             c.properties().get("starting"),
             Some(&Value::from(
-                FixedOffset::east(-8 * 3600).from_utc_datetime(
-                    &NaiveDate::from_ymd(2019, 12, 19).and_hms_milli(16, 8, 4, 322)
+                FixedOffset::east_opt(-8 * 3600).unwrap().from_utc_datetime(
+                    &NaiveDate::from_ymd_opt(2019, 12, 19)
+                        .unwrap()
+                        .and_hms_milli_opt(16, 8, 4, 322)
+                        .unwrap()
                 )
             ))
         );
@@ -199,8 +204,10 @@ mod tests {
         let client = get_initialized_client(V2_0).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        let response = client.discard_all().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
+        assert!(matches!(
+            client.discard_all().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
     }
 
     #[tokio::test]
@@ -223,9 +230,10 @@ mod tests {
         assert!(Success::try_from(response).is_ok());
         let response = client.discard_all().await.unwrap();
         assert!(Success::try_from(response).is_ok());
-        let (response, records) = client.pull_all().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
-        assert!(records.is_empty());
+        assert!(matches!(
+            client.pull_all().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
     }
 
     #[tokio::test]
@@ -18,7 +18,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// - `FAILURE {"code": …​, "message": …​}` if the request was malformed, or
     ///   if initialization cannot be performed at this time, or if the authorization
     ///   failed.
-    #[bolt_version(3, 4, 4.1)]
+    #[bolt_version(3, 4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn hello(&mut self, metadata: Option<Metadata>) -> Result<Message> {
         let hello_msg = Hello::new(metadata.unwrap_or_default().value);
         self.send_message(Message::Hello(hello_msg)).await?;
@@ -30,7 +30,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// # Description
     /// The `GOODBYE` message is a Bolt v3+ client message used to end the session. The
     /// server will end the connection upon receipt of this message.
-    #[bolt_version(3, 4, 4.1)]
+    #[bolt_version(3, 4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn goodbye(&mut self) -> Result<()> {
         self.send_message(Message::Goodbye).await?;
         Ok(())
@@ -46,7 +46,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// - `SUCCESS {…​}` if the statement has been accepted for execution
     /// - `FAILURE {"code": …​, "message": …​}` if the request was malformed or
     ///   if a statement may not be executed at this time
-    #[bolt_version(3, 4, 4.1)]
+    #[bolt_version(3, 4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn run_with_metadata(
         &mut self,
         statement: impl Into<String>,
@@ -56,7 +56,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
         let run_msg = RunWithMetadata::new(
             statement.into(),
             parameters.unwrap_or_default().value,
-            metadata.unwrap_or_default().value,
+            self.with_default_database(metadata).value,
         );
         self.send_message(Message::RunWithMetadata(run_msg)).await?;
         self.read_message().await
@@ -72,9 +72,9 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// - `SUCCESS {…}` if transaction has started successfully
     /// - `FAILURE {"code": …​, "message": …​}` if the request was malformed, or
     ///   if transaction could not be started
-    #[bolt_version(3, 4, 4.1)]
+    #[bolt_version(3, 4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn begin(&mut self, metadata: Option<Metadata>) -> Result<Message> {
-        let begin_msg = Begin::new(metadata.unwrap_or_default().value);
+        let begin_msg = Begin::new(self.with_default_database(metadata).value);
         self.send_message(Message::Begin(begin_msg)).await?;
         self.read_message().await
     }
@@ -90,7 +90,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// - `SUCCESS {…}` if transaction has been committed successfully
     /// - `FAILURE {"code": …​, "message": …​}` if the request was malformed, or
     ///   if transaction could not be committed
-    #[bolt_version(3, 4, 4.1)]
+    #[bolt_version(3, 4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn commit(&mut self) -> Result<Message> {
         self.send_message(Message::Commit).await?;
         self.read_message().await
@@ -107,14 +107,14 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// - `SUCCESS {…}` if transaction has been rolled back successfully
     /// - `FAILURE {"code": …​, "message": …​}` if the request was malformed, or
     ///   if transaction could not be rolled back
-    #[bolt_version(3, 4, 4.1)]
+    #[bolt_version(3, 4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn rollback(&mut self) -> Result<Message> {
         self.send_message(Message::Rollback).await?;
         self.read_message().await
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "tokio-stream"))]
 mod tests {
     use std::convert::TryFrom;
 
@@ -122,6 +122,7 @@ mod tests {
 
     use crate::client::v1::tests::*;
     use crate::skip_if_handshake_failed;
+    use crate::ServerState;
 
     use super::*;
 
@@ -260,8 +261,11 @@ mod tests {
         let client = get_initialized_client(V3_0).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        let response = client.commit().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
+        assert!(matches!(
+            client.commit().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
+        assert_eq!(client.state(), ServerState::Ready);
     }
 
     #[tokio::test]
@@ -313,7 +317,10 @@ mod tests {
         let client = get_initialized_client(V3_0).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        let response = client.rollback().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
+        assert!(matches!(
+            client.rollback().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
+        assert_eq!(client.state(), ServerState::Ready);
     }
 }
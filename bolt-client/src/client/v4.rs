@@ -17,7 +17,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// - `SUCCESS {…}` if the result stream has been successfully discarded
     /// - `FAILURE {"code": …​, "message": …​}` if no result stream is currently
     ///   available
-    #[bolt_version(4, 4.1)]
+    #[bolt_version(4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn discard(&mut self, metadata: Option<Metadata>) -> Result<Message> {
         let discard_msg = Discard::new(metadata.unwrap_or_default().value);
         self.send_message(Message::Discard(discard_msg)).await?;
@@ -34,7 +34,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     /// - `SUCCESS {…​}` if the result stream has been successfully transferred
     /// - `FAILURE {"code": …​, "message": …​}` if no result stream is currently
     ///   available or if retrieval fails
-    #[bolt_version(4, 4.1)]
+    #[bolt_version(4, 4.1, 4.2, 4.3, 4.4, 5.0, 5.1, 5.2, 5.3, 5.4)]
     pub async fn pull(&mut self, metadata: Option<Metadata>) -> Result<(Message, Vec<Record>)> {
         let pull_msg = Pull::new(metadata.unwrap_or_default().value);
         self.send_message(Message::Pull(pull_msg)).await?;
@@ -48,7 +48,7 @@ impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "tokio-stream"))]
 mod tests {
     use std::collections::HashMap;
     use std::convert::TryFrom;
@@ -58,6 +58,7 @@ mod tests {
 
     use crate::client::v1::tests::*;
     use crate::skip_if_handshake_failed;
+    use crate::ServerState;
 
     use super::*;
 
@@ -282,8 +283,11 @@ mod tests {
         let client = get_initialized_client(V4_0).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        let response = client.commit().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
+        assert!(matches!(
+            client.commit().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
+        assert_eq!(client.state(), ServerState::Ready);
     }
 
     #[tokio::test]
@@ -338,7 +342,10 @@ mod tests {
         let client = get_initialized_client(V4_0).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        let response = client.rollback().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
+        assert!(matches!(
+            client.rollback().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
+        assert_eq!(client.state(), ServerState::Ready);
     }
 }
@@ -1,4 +1,4 @@
-#[cfg(test)]
+#[cfg(all(test, feature = "tokio-stream"))]
 mod tests {
     use std::collections::HashMap;
     use std::convert::TryFrom;
@@ -7,8 +7,9 @@ mod tests {
     use bolt_proto::{message::*, value::*, version::*, Message};
 
     use crate::client::v1::tests::*;
+    use crate::error::*;
     use crate::skip_if_handshake_failed;
-    use crate::Metadata;
+    use crate::{Metadata, ServerState};
 
     #[tokio::test]
     async fn hello() {
@@ -231,8 +232,11 @@ mod tests {
         let client = get_initialized_client(V4_1).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        let response = client.commit().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
+        assert!(matches!(
+            client.commit().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
+        assert_eq!(client.state(), ServerState::Ready);
     }
 
     #[tokio::test]
@@ -287,7 +291,10 @@ mod tests {
         let client = get_initialized_client(V4_1).await;
         skip_if_handshake_failed!(client);
         let mut client = client.unwrap();
-        let response = client.rollback().await.unwrap();
-        assert!(Failure::try_from(response).is_ok());
+        assert!(matches!(
+            client.rollback().await,
+            Err(Error::InvalidState(ServerState::Ready, _))
+        ));
+        assert_eq!(client.state(), ServerState::Ready);
     }
 }
@@ -0,0 +1,45 @@
+use bolt_client_macros::*;
+use bolt_proto::message::*;
+use bolt_proto::Message;
+use futures_util::io::{AsyncRead, AsyncWrite};
+
+use crate::error::*;
+use crate::{Client, Metadata};
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
+    /// Send a `LOGON` message to the server.
+    ///
+    /// # Description
+    /// This Bolt v5.1+ message authenticates the connection. From Bolt v5.1, `HELLO` no
+    /// longer carries an auth token: after a successful `HELLO`, the client must send
+    /// `LOGON` with the `scheme`, `principal`, `credentials` and other authentication
+    /// entries before running any queries.
+    ///
+    /// # Response
+    /// - `SUCCESS {…}` if authentication has completed successfully
+    /// - `FAILURE {"code": …​, "message": …​}` if the request was malformed, or
+    ///   if the authorization failed. The server then closes the connection.
+    #[bolt_version(5.1, 5.2, 5.3, 5.4)]
+    pub async fn logon(&mut self, auth: Metadata) -> Result<Message> {
+        let logon_msg = Logon::new(auth.value);
+        self.send_message(Message::Logon(logon_msg)).await?;
+        self.read_message().await
+    }
+
+    /// Send a `LOGOFF` message to the server.
+    ///
+    /// # Description
+    /// This Bolt v5.1+ message removes the authentication from an idle connection, which
+    /// can then be authenticated again with [`logon`](Client::logon), for example with
+    /// refreshed credentials, without opening a new connection.
+    ///
+    /// # Response
+    /// - `SUCCESS {}` if the connection is no longer authenticated
+    /// - `FAILURE {"code": …​, "message": …​}` if the request was malformed, or
+    ///   if the connection is not idle
+    #[bolt_version(5.1, 5.2, 5.3, 5.4)]
+    pub async fn logoff(&mut self) -> Result<Message> {
+        self.send_message(Message::Logoff).await?;
+        self.read_message().await
+    }
+}
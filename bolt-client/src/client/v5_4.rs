@@ -0,0 +1,27 @@
+use bolt_client_macros::*;
+use bolt_proto::message::*;
+use bolt_proto::Message;
+use futures_util::io::{AsyncRead, AsyncWrite};
+
+use crate::error::*;
+use crate::Client;
+
+impl<S: AsyncRead + AsyncWrite + Unpin> Client<S> {
+    /// Send a `TELEMETRY` message to the server.
+    ///
+    /// # Description
+    /// This Bolt v5.4+ message tells the server which driver API, such as
+    /// [`Telemetry::UNMANAGED_TRANSACTION`], the following work is submitted through.
+    /// Servers advertise whether they accept telemetry with the `telemetry.enabled` hint
+    /// in the `HELLO` response; otherwise, the message should not be sent.
+    ///
+    /// # Response
+    /// - `SUCCESS {}` if the telemetry has been accepted
+    /// - `FAILURE {"code": …​, "message": …​}` if the request was malformed
+    #[bolt_version(5.4)]
+    pub async fn telemetry(&mut self, api: i64) -> Result<Message> {
+        let telemetry_msg = Telemetry::new(api);
+        self.send_message(Message::Telemetry(telemetry_msg)).await?;
+        self.read_message().await
+    }
+}
@@ -1,19 +1,74 @@
 use bolt_proto::version::*;
 use thiserror::Error;
 
+use crate::ServerState;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, Error)]
 pub enum Error {
-    #[cfg(feature = "tokio-stream")]
+    #[cfg(any(
+        feature = "tokio-stream",
+        feature = "async-std-stream",
+        feature = "smol-stream",
+        feature = "blocking"
+    ))]
     #[error("invalid DNS name: {0}")]
     InvalidDNSName(String),
+    #[cfg(any(
+        feature = "tokio-stream",
+        feature = "async-std-stream",
+        feature = "smol-stream",
+        feature = "blocking"
+    ))]
+    #[error("invalid certificate: {0}")]
+    InvalidCertificate(String),
+    #[cfg(any(
+        feature = "tokio-stream",
+        feature = "async-std-stream",
+        feature = "smol-stream",
+        feature = "blocking"
+    ))]
+    #[error("invalid private key")]
+    InvalidPrivateKey,
+    #[error("operation timed out")]
+    Timeout,
+    #[cfg(feature = "pool")]
+    #[error("invalid host address")]
+    InvalidAddress,
+    #[cfg(feature = "pool")]
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(String),
+    #[cfg(feature = "pool")]
+    #[error("client initialization failed: received {0:?}")]
+    InitFailed(bolt_proto::Message),
+    #[cfg(feature = "pool")]
+    #[error("connection is not ready: server is in the {0:?} state")]
+    ConnectionNotReady(ServerState),
+    #[cfg(feature = "pool")]
+    #[error("connection validation failed: received {0:?}")]
+    ValidationFailed(bolt_proto::Message),
+    #[cfg(feature = "pool")]
+    #[error("failed to get credentials: {0}")]
+    CredentialsUnavailable(Box<dyn std::error::Error + Send + Sync>),
+    #[error("invalid connection URI: {0}")]
+    InvalidUri(String),
     #[error(transparent)]
     IOError(#[from] std::io::Error),
     #[error("handshake with server failed for versions [{}]", format_versions(.0))]
     HandshakeFailed([u32; 4]),
     #[error("unsupported operation for client with version = {}", format_version(*.0))]
     UnsupportedOperation(u32),
+    #[error("cannot send {1} while the server is in the {0:?} state")]
+    InvalidState(ServerState, &'static str),
+    #[error("failed to reset connection: received {0:?}")]
+    ResetFailed(bolt_proto::Message),
+    #[error("query failed: received {0:?}")]
+    QueryFailed(bolt_proto::Message),
+    #[error("expected exactly one record, received {0}")]
+    UnexpectedRecordCount(usize),
+    #[error("no field named {0:?} in record")]
+    FieldNotFound(String),
     #[error(transparent)]
     ProtocolError(#[from] bolt_proto::error::Error),
 }
@@ -25,6 +80,24 @@ fn format_version(version: u32) -> String {
         V3_0 => String::from("3.0"),
         V4_0 => String::from("4.0"),
         V4_1 => String::from("4.1"),
+        V4_2 => String::from("4.2"),
+        V4_3 => String::from("4.3"),
+        V4_4 => String::from("4.4"),
+        V5_0 => String::from("5.0"),
+        V5_1 => String::from("5.1"),
+        V5_2 => String::from("5.2"),
+        V5_3 => String::from("5.3"),
+        V5_4 => String::from("5.4"),
+        _ if version >> 16 & 0xFF > 0 && version >> 24 == 0 => {
+            let (major, minor, back) = (version & 0xFF, version >> 8 & 0xFF, version >> 16 & 0xFF);
+            format!(
+                "{}.{}-{}.{}",
+                major,
+                minor.saturating_sub(back),
+                major,
+                minor
+            )
+        }
         _ => format!("{:#x}", version),
     }
 }
@@ -0,0 +1,160 @@
+use std::{
+    fmt::Debug,
+    future::Future,
+    io,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_rustls::{client::TlsStream, TlsConnector};
+use futures_util::io::{AsyncRead, AsyncWrite};
+use pin_project::pin_project;
+use socket2::SockRef;
+
+use crate::{client::with_timeout, error::*, TcpConfig, TlsConfig};
+
+/// A convenient wrapper around a TCP stream from a runtime built on the `futures` I/O traits,
+/// or a [`TlsStream`](futures_rustls::client::TlsStream) over one. Unlike the tokio-based
+/// `Stream`, this can be passed to [`Client::new`](crate::Client::new) directly.
+///
+/// Use the [`AsyncStdStream`](crate::AsyncStdStream) or [`SmolStream`](crate::SmolStream)
+/// aliases to open connections with a particular runtime.
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(feature = "async-std-stream", feature = "smol-stream")))
+)]
+#[pin_project(project = FuturesStreamProj)]
+#[derive(Debug)]
+pub enum FuturesStream<S> {
+    Tcp(#[pin] S),
+    SecureTcp(#[pin] Box<TlsStream<S>>),
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> FuturesStream<S>
+where
+    for<'s> SockRef<'s>: From<&'s S>,
+{
+    // Shared by the runtime-specific constructors, which only differ in how they open the
+    // TCP connection
+    pub(crate) async fn from_tcp(
+        connect: impl Future<Output = io::Result<S>>,
+        tls_config: Option<&TlsConfig>,
+        tcp_config: &TcpConfig,
+    ) -> Result<Self> {
+        let tls = match tls_config {
+            Some(tls_config) => Some((
+                TlsConnector::from(tls_config.client_config()),
+                tls_config.dns_name_ref()?,
+            )),
+            None => None,
+        };
+        let connect = async {
+            let stream = connect.await?;
+            tcp_config.configure(SockRef::from(&stream))?;
+            match tls {
+                Some((connector, dns_name_ref)) => Ok(FuturesStream::SecureTcp(Box::new(
+                    connector.connect(dns_name_ref, stream).await?,
+                ))),
+                None => Ok(FuturesStream::Tcp(stream)),
+            }
+        };
+        with_timeout(tcp_config.connect_timeout(), connect).await
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncRead for FuturesStream<S> {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.project() {
+            FuturesStreamProj::Tcp(tcp_stream) => AsyncRead::poll_read(tcp_stream, cx, buf),
+            FuturesStreamProj::SecureTcp(tls_stream) => AsyncRead::poll_read(tls_stream, cx, buf),
+        }
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> AsyncWrite for FuturesStream<S> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        match self.project() {
+            FuturesStreamProj::Tcp(tcp_stream) => AsyncWrite::poll_write(tcp_stream, cx, buf),
+            FuturesStreamProj::SecureTcp(tls_stream) => AsyncWrite::poll_write(tls_stream, cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.project() {
+            FuturesStreamProj::Tcp(tcp_stream) => AsyncWrite::poll_flush(tcp_stream, cx),
+            FuturesStreamProj::SecureTcp(tls_stream) => AsyncWrite::poll_flush(tls_stream, cx),
+        }
+    }
+
+    fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        match self.project() {
+            FuturesStreamProj::Tcp(tcp_stream) => AsyncWrite::poll_close(tcp_stream, cx),
+            FuturesStreamProj::SecureTcp(tls_stream) => AsyncWrite::poll_close(tls_stream, cx),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        collections::HashMap,
+        convert::{TryFrom, TryInto},
+        sync::Arc,
+    };
+
+    use bolt_proto::{message::Success, version::*, Message};
+    use bytes::Bytes;
+    use futures_rustls::{
+        rustls::{self, NoClientAuth, PrivateKey, ServerConfig},
+        TlsAcceptor,
+    };
+    use futures_util::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+    use crate::Client;
+
+    // Accept TLS connections for "localhost" with a self-signed certificate.
+    pub(crate) fn tls_acceptor() -> TlsAcceptor {
+        let certificate = rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap();
+        let mut config = ServerConfig::new(NoClientAuth::new());
+        config
+            .set_single_cert(
+                vec![rustls::Certificate(certificate.serialize_der().unwrap())],
+                PrivateKey(certificate.serialize_private_key_der()),
+            )
+            .unwrap();
+        TlsAcceptor::from(Arc::new(config))
+    }
+
+    // Respond to a handshake with Bolt v4.1, and to the following message with SUCCESS.
+    pub(crate) async fn serve_hello(mut stream: impl AsyncRead + AsyncWrite + Unpin) {
+        let mut handshake = [0; 20];
+        stream.read_exact(&mut handshake).await.unwrap();
+        stream.write_all(&V4_1.to_be_bytes()).await.unwrap();
+        stream.flush().await.unwrap();
+
+        let message = Message::from_stream(&mut stream).await.unwrap();
+        assert!(matches!(message, Message::Hello(_)));
+        let chunks: Vec<Bytes> = Message::Success(Success::new(HashMap::new()))
+            .try_into()
+            .unwrap();
+        for chunk in chunks {
+            stream.write_all(&chunk).await.unwrap();
+        }
+        stream.flush().await.unwrap();
+    }
+
+    pub(crate) async fn hello<S: AsyncRead + AsyncWrite + Unpin>(stream: FuturesStream<S>) {
+        let mut client = Client::new(stream, &[V4_1, 0, 0, 0]).await.unwrap();
+        assert_eq!(client.version(), V4_1);
+        assert!(Success::try_from(client.hello(None).await.unwrap()).is_ok());
+    }
+}
@@ -219,23 +219,104 @@
 //! See the documentation of the [`Client`] struct for information on transaction
 //! management, error handling, and more.
 #[doc(inline)]
-pub use self::client::Client;
+pub use self::client::{Client, ServerState};
 
+mod auth;
 mod client;
 mod define_value_map;
 pub mod error;
+pub mod result;
+pub mod uri;
 
 pub use bolt_proto;
 
+#[cfg(feature = "async-std-stream")]
+mod async_std_stream;
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
+#[cfg(any(feature = "async-std-stream", feature = "smol-stream"))]
+mod futures_stream;
+#[cfg(feature = "pool")]
+#[cfg_attr(docsrs, doc(cfg(feature = "pool")))]
+pub mod pool;
+#[cfg(feature = "smol-stream")]
+mod smol_stream;
 #[cfg(feature = "tokio-stream")]
 mod stream;
+#[cfg(any(
+    feature = "tokio-stream",
+    feature = "async-std-stream",
+    feature = "smol-stream",
+    feature = "blocking"
+))]
+mod tcp_config;
+#[cfg(any(
+    feature = "tokio-stream",
+    feature = "async-std-stream",
+    feature = "smol-stream",
+    feature = "blocking"
+))]
+mod tls;
 
+#[cfg(feature = "async-std-stream")]
+pub use async_std_stream::AsyncStdStream;
+pub use auth::AuthToken;
+#[cfg(any(feature = "async-std-stream", feature = "smol-stream"))]
+pub use futures_stream::FuturesStream;
+#[cfg(feature = "smol-stream")]
+pub use smol_stream::SmolStream;
 #[cfg(feature = "tokio-stream")]
 pub use stream::Stream;
+#[cfg(any(
+    feature = "tokio-stream",
+    feature = "async-std-stream",
+    feature = "smol-stream",
+    feature = "blocking"
+))]
+pub use tcp_config::TcpConfig;
+#[cfg(any(
+    feature = "tokio-stream",
+    feature = "async-std-stream",
+    feature = "smol-stream",
+    feature = "blocking"
+))]
+pub use tls::TlsConfig;
+pub use uri::ConnectionUri;
 
 define_value_map!(Metadata);
 define_value_map!(Params);
 
+impl Metadata {
+    /// Only receive notifications of at least the given severity, such as `"WARNING"`, or
+    /// none at all with `"OFF"`. Notification filters are understood by servers
+    /// supporting Bolt v5.2 or later, in `HELLO` metadata for the whole connection, or in
+    /// `BEGIN` and `RUN` metadata for a single transaction.
+    pub fn with_notifications_minimum_severity(mut self, severity: impl Into<String>) -> Self {
+        self.value.insert(
+            String::from("notifications_minimum_severity"),
+            bolt_proto::Value::from(severity.into()),
+        );
+        self
+    }
+
+    /// Do not receive notifications in the given categories, such as `"HINT"` or
+    /// `"DEPRECATION"`. See
+    /// [`with_notifications_minimum_severity`](Self::with_notifications_minimum_severity)
+    /// for the messages this applies to.
+    pub fn with_notifications_disabled_categories(
+        mut self,
+        categories: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        let categories: Vec<String> = categories.into_iter().map(Into::into).collect();
+        self.value.insert(
+            String::from("notifications_disabled_categories"),
+            bolt_proto::Value::from(categories),
+        );
+        self
+    }
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! skip_if_handshake_failed {
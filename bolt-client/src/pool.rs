@@ -0,0 +1,542 @@
+//! A connection pool for Bolt servers.
+//!
+//! A [`Pool`] hands out initialized connections opened by a [`Connector`], keeping a
+//! separate set of connections for each server address it is asked to connect to, as
+//! needed when routing requests within a cluster. Before a connection is reused, any
+//! unconsumed result stream, open transaction, or failure left behind by its previous
+//! user is cleared with `RESET`, and connections using Bolt v5.1 or later are
+//! authenticated again if the connector's [`AuthProvider`] has changed its credentials.
+//! Connections are retired after a maximum lifetime, and can be checked for liveness
+//! before being reused after sitting idle.
+//!
+//! # Example
+//! ```no_run
+//! use std::collections::HashMap;
+//! use std::iter::FromIterator;
+//! use std::time::Duration;
+//!
+//! use bolt_client::pool::*;
+//! use bolt_proto::version::*;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     let connector = Connector::from_uri(
+//!         "neo4j://localhost:7687",
+//!         [V4_1, V4_0, 0, 0],
+//!         HashMap::from_iter(vec![
+//!             ("user_agent", "my-client-name/1.0"),
+//!             ("scheme", "basic"),
+//!             ("principal", "neo4j"),
+//!             ("credentials", "neo4j"),
+//!         ]),
+//!     )
+//!     .await?;
+//!     let pool = Pool::new(
+//!         connector,
+//!         PoolConfig::default().with_idle_time_before_check(Duration::from_secs(60)),
+//!     );
+//!
+//!     let mut connection = pool.get().await?;
+//!     connection.run_with_metadata("RETURN 1 as num;", None, None).await?;
+//!     Ok(())
+//! }
+//! ```
+use std::{
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::error::*;
+
+mod auth_provider;
+mod connector;
+mod manager;
+
+pub use auth_provider::AuthProvider;
+pub use connector::{Connection, Connector, ConnectorBuilder};
+pub use manager::{ConnectionManager, ManagerError};
+
+/// Settings for a [`Pool`].
+///
+/// By default, a pool holds at most 100 connections per server address, retires
+/// connections after an hour, waits at most a minute to acquire a connection, and does
+/// not check the liveness of idle connections.
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    max_size: usize,
+    max_lifetime: Option<Duration>,
+    idle_time_before_check: Option<Duration>,
+    acquisition_timeout: Option<Duration>,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 100,
+            max_lifetime: Some(Duration::from_secs(60 * 60)),
+            idle_time_before_check: None,
+            acquisition_timeout: Some(Duration::from_secs(60)),
+        }
+    }
+}
+
+impl PoolConfig {
+    /// Limit the number of connections, idle or in use, to each server address.
+    pub fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    /// Close connections that have been open for longer than the given duration, rather
+    /// than reusing them. A value of `None` keeps connections open indefinitely.
+    pub fn with_max_lifetime(mut self, max_lifetime: Option<Duration>) -> Self {
+        self.max_lifetime = max_lifetime;
+        self
+    }
+
+    /// Validate connections with [`Connector::validate`] before reusing them if no message
+    /// has been exchanged on them for at least the given duration, discarding those that
+    /// fail.
+    pub fn with_idle_time_before_check(mut self, idle_time: Duration) -> Self {
+        self.idle_time_before_check = Some(idle_time);
+        self
+    }
+
+    /// Fail with [`Error::Timeout`] if a connection can't be acquired within the given
+    /// duration, including the time spent waiting for a connection to be returned and
+    /// opening a new one. A value of `None` waits indefinitely.
+    pub fn with_acquisition_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.acquisition_timeout = timeout;
+        self
+    }
+
+    pub fn max_size(&self) -> usize {
+        self.max_size
+    }
+
+    pub fn max_lifetime(&self) -> Option<Duration> {
+        self.max_lifetime
+    }
+
+    pub fn idle_time_before_check(&self) -> Option<Duration> {
+        self.idle_time_before_check
+    }
+
+    pub fn acquisition_timeout(&self) -> Option<Duration> {
+        self.acquisition_timeout
+    }
+}
+
+/// A pool of connections to one or more Bolt servers. Clones share the same connections.
+#[derive(Clone)]
+pub struct Pool {
+    inner: Arc<PoolInner>,
+}
+
+struct PoolInner {
+    connector: Connector,
+    config: PoolConfig,
+    // Connections to the connector's own address are keyed by `None`
+    address_pools: Mutex<HashMap<Option<String>, Arc<AddressPool>>>,
+}
+
+struct AddressPool {
+    idle: Mutex<Vec<Connection>>,
+    permits: Arc<Semaphore>,
+    max_lifetime: Option<Duration>,
+}
+
+impl AddressPool {
+    fn is_expired(&self, connection: &Connection) -> bool {
+        matches!(self.max_lifetime, Some(max_lifetime) if connection.created_at().elapsed() >= max_lifetime)
+    }
+}
+
+impl Pool {
+    pub fn new(connector: Connector, config: PoolConfig) -> Self {
+        Self {
+            inner: Arc::new(PoolInner {
+                connector,
+                config,
+                address_pools: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+
+    pub fn connector(&self) -> &Connector {
+        &self.inner.connector
+    }
+
+    pub fn config(&self) -> &PoolConfig {
+        &self.inner.config
+    }
+
+    /// Get a connection to the connector's server, reusing an idle connection if possible.
+    pub async fn get(&self) -> Result<PooledConnection> {
+        self.acquire(None).await
+    }
+
+    /// Get a connection to the server at the given `host:port` address, such as another
+    /// member of the cluster taken from a routing table. Each address has its own set of
+    /// connections, limited to [`PoolConfig::max_size`].
+    pub async fn get_for(&self, addr: &str) -> Result<PooledConnection> {
+        self.acquire(Some(addr)).await
+    }
+
+    /// Get the number of idle connections to each address, where `None` stands for the
+    /// connector's server.
+    pub fn idle_connections(&self) -> HashMap<Option<String>, usize> {
+        self.inner
+            .address_pools
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(addr, pool)| (addr.clone(), pool.idle.lock().unwrap().len()))
+            .collect()
+    }
+
+    fn address_pool(&self, addr: Option<&str>) -> Arc<AddressPool> {
+        let mut address_pools = self.inner.address_pools.lock().unwrap();
+        let address_pool = address_pools
+            .entry(addr.map(String::from))
+            .or_insert_with(|| {
+                Arc::new(AddressPool {
+                    idle: Mutex::new(Vec::new()),
+                    permits: Arc::new(Semaphore::new(self.inner.config.max_size)),
+                    max_lifetime: self.inner.config.max_lifetime,
+                })
+            });
+        Arc::clone(address_pool)
+    }
+
+    async fn acquire(&self, addr: Option<&str>) -> Result<PooledConnection> {
+        let acquire = self.acquire_from(self.address_pool(addr), addr);
+        match self.inner.config.acquisition_timeout {
+            Some(timeout) => tokio::time::timeout(timeout, acquire)
+                .await
+                .map_err(|_| Error::Timeout)?,
+            None => acquire.await,
+        }
+    }
+
+    async fn acquire_from(
+        &self,
+        address_pool: Arc<AddressPool>,
+        addr: Option<&str>,
+    ) -> Result<PooledConnection> {
+        let permit = Arc::clone(&address_pool.permits)
+            .acquire_owned()
+            .await
+            .expect("pool semaphore is never closed");
+
+        loop {
+            let idle = address_pool.idle.lock().unwrap().pop();
+            let mut connection = match idle {
+                Some(connection) => connection,
+                None => break,
+            };
+            if address_pool.is_expired(&connection)
+                || connection.ensure_ready().await.is_err()
+                || self
+                    .inner
+                    .connector
+                    .reauthenticate(&mut connection)
+                    .await
+                    .is_err()
+            {
+                continue;
+            }
+            if let Some(idle_time) = self.inner.config.idle_time_before_check {
+                if connection.last_used().elapsed() >= idle_time
+                    && self
+                        .inner
+                        .connector
+                        .validate(&mut connection)
+                        .await
+                        .is_err()
+                {
+                    continue;
+                }
+            }
+            return Ok(PooledConnection::new(connection, address_pool, permit));
+        }
+
+        let connection = match addr {
+            Some(addr) => self.inner.connector.connect_to(addr).await?,
+            None => self.inner.connector.connect().await?,
+        };
+        Ok(PooledConnection::new(connection, address_pool, permit))
+    }
+}
+
+/// A connection borrowed from a [`Pool`], which dereferences to a [`Connection`]. The
+/// connection is returned to the pool when this is dropped, unless it is broken or has
+/// exceeded its maximum lifetime.
+pub struct PooledConnection {
+    connection: Option<Connection>,
+    address_pool: Arc<AddressPool>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl PooledConnection {
+    fn new(
+        connection: Connection,
+        address_pool: Arc<AddressPool>,
+        permit: OwnedSemaphorePermit,
+    ) -> Self {
+        Self {
+            connection: Some(connection),
+            address_pool,
+            _permit: permit,
+        }
+    }
+}
+
+impl Deref for PooledConnection {
+    type Target = Connection;
+
+    fn deref(&self) -> &Self::Target {
+        self.connection.as_ref().unwrap()
+    }
+}
+
+impl DerefMut for PooledConnection {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.connection.as_mut().unwrap()
+    }
+}
+
+impl Drop for PooledConnection {
+    fn drop(&mut self) {
+        if let Some(connection) = self.connection.take() {
+            if !connection.is_broken() && !self.address_pool.is_expired(&connection) {
+                self.address_pool.idle.lock().unwrap().push(connection);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::{
+        convert::TryInto,
+        iter::FromIterator,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use async_trait::async_trait;
+    use bolt_proto::{message::Success, version::*, Message};
+    use tokio::{
+        io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+        net::TcpListener,
+    };
+    use tokio_util::compat::*;
+
+    use super::*;
+    use crate::{AuthToken, ServerState, Stream};
+
+    // Respond to a handshake with the first preferred version, and to the first
+    // `responses` messages with SUCCESS, then wait for the client to disconnect.
+    pub(crate) async fn serve_success(
+        mut stream: impl AsyncRead + AsyncWrite + Unpin,
+        responses: usize,
+    ) {
+        let mut handshake = [0; 20];
+        stream.read_exact(&mut handshake).await.unwrap();
+        stream.write_all(&handshake[4..8]).await.unwrap();
+        stream.flush().await.unwrap();
+
+        for _ in 0..responses {
+            if Message::from_stream((&mut stream).compat()).await.is_err() {
+                return;
+            }
+            let chunks: Vec<_> = Message::Success(Success::new(HashMap::new()))
+                .try_into()
+                .unwrap();
+            for chunk in chunks {
+                stream.write_all(&chunk).await.unwrap();
+            }
+            stream.flush().await.unwrap();
+        }
+        while stream.read(&mut [0; 1024]).await.unwrap_or(0) > 0 {}
+    }
+
+    // Supplies a bearer token that can be changed while connections are open
+    pub(crate) struct SharedToken(pub(crate) Arc<Mutex<String>>);
+
+    #[async_trait]
+    impl AuthProvider for SharedToken {
+        async fn token(&self) -> Result<AuthToken> {
+            Ok(AuthToken::bearer(self.0.lock().unwrap().clone()))
+        }
+    }
+
+    // Create a connector to in-memory servers answering the given number of messages,
+    // counting the connections opened.
+    pub(crate) fn connector(responses: usize) -> (Connector, Arc<AtomicUsize>) {
+        let connections = Arc::new(AtomicUsize::new(0));
+        let connector = {
+            let connections = Arc::clone(&connections);
+            Connector::duplex(
+                move || {
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    let (stream, server) = Stream::duplex(1024);
+                    tokio::spawn(serve_success(server, responses));
+                    stream
+                },
+                [V4_1, 0, 0, 0],
+                HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+            )
+        };
+        (connector, connections)
+    }
+
+    #[tokio::test]
+    async fn reuse_and_reset() {
+        let (connector, connections) = connector(usize::MAX);
+        let pool = Pool::new(connector, PoolConfig::default());
+
+        let mut connection = pool.get().await.unwrap();
+        connection.begin(None).await.unwrap();
+        connection
+            .run_with_metadata("RETURN 1;", None, None)
+            .await
+            .unwrap();
+        assert_eq!(connection.state(), ServerState::TxStreaming);
+        drop(connection);
+        assert_eq!(pool.idle_connections()[&None], 1);
+
+        let connection = pool.get().await.unwrap();
+        assert_eq!(connection.state(), ServerState::Ready);
+        assert_eq!(pool.idle_connections()[&None], 0);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn max_lifetime() {
+        let (connector, connections) = connector(usize::MAX);
+        let pool = Pool::new(
+            connector,
+            PoolConfig::default().with_max_lifetime(Some(Duration::from_millis(50))),
+        );
+
+        drop(pool.get().await.unwrap());
+        drop(pool.get().await.unwrap());
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        drop(pool.get().await.unwrap());
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn idle_liveness_check() {
+        // Servers stop answering after HELLO, so liveness checks time out
+        let (connector, connections) = connector(1);
+        let connector = connector.with_read_timeout(Duration::from_millis(50));
+        let pool = Pool::new(
+            connector,
+            PoolConfig::default().with_idle_time_before_check(Duration::from_millis(50)),
+        );
+
+        drop(pool.get().await.unwrap());
+        drop(pool.get().await.unwrap());
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let connection = pool.get().await.unwrap();
+        assert_eq!(connection.state(), ServerState::Ready);
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn broken_connections_are_discarded() {
+        let (connector, connections) = connector(1);
+        let connector = connector.with_read_timeout(Duration::from_millis(50));
+        let pool = Pool::new(connector, PoolConfig::default());
+
+        let mut connection = pool.get().await.unwrap();
+        assert!(connection
+            .run_with_metadata("RETURN 1;", None, None)
+            .await
+            .is_err());
+        assert!(connection.is_broken());
+        drop(connection);
+        assert_eq!(pool.idle_connections()[&None], 0);
+        drop(pool.get().await.unwrap());
+        assert_eq!(connections.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn reauthenticate_on_checkout() {
+        let token = Arc::new(Mutex::new(String::from("token-0")));
+        let connections = Arc::new(AtomicUsize::new(0));
+        let connector = {
+            let connections = Arc::clone(&connections);
+            Connector::duplex(
+                move || {
+                    connections.fetch_add(1, Ordering::SeqCst);
+                    let (stream, server) = Stream::duplex(1024);
+                    tokio::spawn(serve_success(server, usize::MAX));
+                    stream
+                },
+                [V5_1, 0, 0, 0],
+                HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+            )
+            .with_auth_provider(SharedToken(Arc::clone(&token)))
+        };
+        let pool = Pool::new(connector, PoolConfig::default());
+
+        drop(pool.get().await.unwrap());
+        *token.lock().unwrap() = String::from("token-1");
+        let connection = pool.get().await.unwrap();
+        assert_eq!(connection.auth_token(), Some(&AuthToken::bearer("token-1")));
+        assert_eq!(connection.state(), ServerState::Ready);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn acquisition_timeout() {
+        let (connector, _) = connector(usize::MAX);
+        let pool = Pool::new(
+            connector,
+            PoolConfig::default()
+                .with_max_size(1)
+                .with_acquisition_timeout(Some(Duration::from_millis(50))),
+        );
+
+        let connection = pool.get().await.unwrap();
+        assert!(matches!(pool.get().await, Err(Error::Timeout)));
+        drop(connection);
+        assert!(pool.get().await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn per_address_pools() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            serve_success(stream, usize::MAX).await;
+        });
+        let (connector, connections) = connector(usize::MAX);
+        let pool = Pool::new(connector, PoolConfig::default().with_max_size(1));
+
+        let default = pool.get().await.unwrap();
+        let other = pool.get_for(&addr).await.unwrap();
+        assert_eq!(other.version(), V4_1);
+        drop(default);
+        drop(other);
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            pool.idle_connections(),
+            HashMap::from_iter(vec![(None, 1), (Some(addr.clone()), 1)])
+        );
+
+        drop(pool.get_for(&addr).await.unwrap());
+        drop(pool);
+        handle.await.unwrap();
+    }
+}
@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+
+use crate::{error::*, AuthToken};
+
+/// Supplies the credentials used to initialize each new connection, for servers whose
+/// passwords or tokens change while a [`Connector`](super::Connector) is in use.
+///
+/// Providers that cannot obtain credentials, for example because an identity provider is
+/// unreachable, should fail with [`Error::CredentialsUnavailable`]. The connection is
+/// then not opened.
+///
+/// # Example
+/// ```
+/// use std::sync::RwLock;
+///
+/// use async_trait::async_trait;
+/// use bolt_client::{error::Result, pool::AuthProvider, AuthToken};
+///
+/// struct RotatingPassword {
+///     password: RwLock<String>,
+/// }
+///
+/// #[async_trait]
+/// impl AuthProvider for RotatingPassword {
+///     async fn token(&self) -> Result<AuthToken> {
+///         Ok(AuthToken::basic("neo4j", self.password.read().unwrap().clone()))
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait AuthProvider: Send + Sync {
+    /// Get the credentials to send when initializing a new connection.
+    async fn token(&self) -> Result<AuthToken>;
+}
+
+/// Fixed credentials, used for every connection.
+#[async_trait]
+impl AuthProvider for AuthToken {
+    async fn token(&self) -> Result<AuthToken> {
+        Ok(self.clone())
+    }
+}
@@ -0,0 +1,780 @@
+#[cfg(unix)]
+use std::path::PathBuf;
+use std::{
+    collections::HashMap, convert::TryFrom, iter::FromIterator, net::SocketAddr, sync::Arc,
+    time::Duration,
+};
+
+use bolt_proto::{version::*, Message, Value};
+use tokio::{
+    io::BufStream,
+    net::{lookup_host, ToSocketAddrs},
+};
+use tokio_util::compat::*;
+
+use super::AuthProvider;
+use crate::{
+    client::at_least, error::*, AuthToken, Client, ConnectionUri, Metadata, ServerState, Stream,
+    TcpConfig, TlsConfig,
+};
+
+mod builder;
+
+pub use builder::ConnectorBuilder;
+
+/// A client connected to a server by a buffered [`Stream`], as created by a [`Connector`].
+pub type Connection = Client<Compat<BufStream<Stream>>>;
+
+/// Opens and initializes connections to a Bolt server, and checks that existing
+/// connections are still usable.
+///
+/// This holds the connection logic shared by the [`Pool`](super::Pool) and by pool
+/// adapters such as `bb8-bolt` and `deadpool-bolt`.
+pub struct Connector {
+    address: Address,
+    tls_config: Option<TlsConfig>,
+    tcp_config: TcpConfig,
+    handshake_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    routing_context: Option<HashMap<String, String>>,
+    validation_query: Option<String>,
+    default_database: Option<String>,
+    auth_provider: Option<Arc<dyn AuthProvider>>,
+    preferred_versions: [u32; 4],
+    metadata: HashMap<String, Value>,
+}
+
+// The metadata entries that make up an auth token
+const AUTH_KEYS: [&str; 5] = ["scheme", "principal", "credentials", "realm", "parameters"];
+
+enum Address {
+    Tcp(SocketAddr),
+    // A host name and port, resolved each time a connection is opened
+    Host(String),
+    #[cfg(unix)]
+    Unix(PathBuf),
+    Duplex(Box<dyn Fn() -> Stream + Send + Sync>),
+}
+
+impl Connector {
+    /// Start configuring a connector for the server at the given `host:port` address,
+    /// which is resolved again each time a connection is opened.
+    pub fn builder(addr: impl Into<String>) -> ConnectorBuilder {
+        ConnectorBuilder::new(addr)
+    }
+
+    /// Create a connector for the server at the given address. If a domain is provided,
+    /// TLS will be negotiated using the default [`TlsConfig`] for that domain.
+    ///
+    /// The address is resolved once, and connections are always opened to the first
+    /// resolved socket address. Use [`builder`](Self::builder) to resolve the address
+    /// each time a connection is opened instead.
+    pub async fn new(
+        addr: impl ToSocketAddrs,
+        domain: Option<String>,
+        preferred_versions: [u32; 4],
+        metadata: HashMap<impl Into<String>, impl Into<Value>>,
+    ) -> Result<Self> {
+        let addr = lookup_host(addr)
+            .await?
+            .next()
+            .ok_or(Error::InvalidAddress)?;
+        let mut connector = Self::with_address(Address::Tcp(addr), preferred_versions, metadata);
+        connector.tls_config = domain.map(TlsConfig::new);
+        Ok(connector)
+    }
+
+    /// Create a connector for a Unix domain socket at the given path.
+    #[cfg(unix)]
+    pub fn unix(
+        path: impl Into<PathBuf>,
+        preferred_versions: [u32; 4],
+        metadata: HashMap<impl Into<String>, impl Into<Value>>,
+    ) -> Self {
+        Self::with_address(Address::Unix(path.into()), preferred_versions, metadata)
+    }
+
+    /// Create a connector whose connections use the in-memory streams returned by
+    /// `make_stream`, typically the client end of a [`Stream::duplex`] whose other end is
+    /// served by an in-process server or stub.
+    pub fn duplex(
+        make_stream: impl Fn() -> Stream + Send + Sync + 'static,
+        preferred_versions: [u32; 4],
+        metadata: HashMap<impl Into<String>, impl Into<Value>>,
+    ) -> Self {
+        Self::with_address(
+            Address::Duplex(Box::new(make_stream)),
+            preferred_versions,
+            metadata,
+        )
+    }
+
+    fn with_address(
+        address: Address,
+        preferred_versions: [u32; 4],
+        metadata: HashMap<impl Into<String>, impl Into<Value>>,
+    ) -> Self {
+        Self {
+            address,
+            tls_config: None,
+            tcp_config: TcpConfig::default(),
+            handshake_timeout: None,
+            read_timeout: None,
+            routing_context: None,
+            validation_query: None,
+            default_database: None,
+            auth_provider: None,
+            preferred_versions,
+            metadata: metadata
+                .into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        }
+    }
+
+    /// Create a connector for the server identified by a connection URI, such as
+    /// `neo4j+s://db.example.com:7687`. TLS is configured according to the URI's scheme,
+    /// and its routing context is sent to servers supporting Bolt v4.1 or later. The
+    /// host is resolved each time a connection is opened.
+    pub async fn from_uri(
+        uri: &str,
+        preferred_versions: [u32; 4],
+        metadata: HashMap<impl Into<String>, impl Into<Value>>,
+    ) -> Result<Self> {
+        let uri: ConnectionUri = uri.parse()?;
+        let mut connector =
+            Self::with_address(Address::Host(uri.addr()), preferred_versions, metadata);
+        connector.tls_config = uri.tls_config();
+        connector.routing_context = uri.routing_context();
+        Ok(connector)
+    }
+
+    /// Negotiate TLS with the given configuration when opening TCP connections, replacing
+    /// any configuration derived from the domain passed to [`new`](Self::new).
+    pub fn with_tls_config(mut self, tls_config: TlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Use the given socket settings, such as connect timeout and keepalive, when opening
+    /// TCP connections.
+    pub fn with_tcp_config(mut self, tcp_config: TcpConfig) -> Self {
+        self.tcp_config = tcp_config;
+        self
+    }
+
+    /// Fail to open a connection if the Bolt handshake does not complete within the given
+    /// duration.
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = Some(timeout);
+        self
+    }
+
+    /// Fail requests on a connection if a response message does not arrive within the
+    /// given duration. The connection is then considered broken.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Validate connections by running the given query and consuming its results, instead
+    /// of the default check. By default, connections using Bolt v3 or later are validated
+    /// with a `RESET` message, and older connections by running `RETURN 1;`.
+    pub fn with_validation_query(mut self, query: impl Into<String>) -> Self {
+        self.validation_query = Some(query.into());
+        self
+    }
+
+    /// Initialize each new connection with credentials from the given provider, replacing
+    /// any `scheme`, `principal`, `credentials`, `realm` and `parameters` given in the
+    /// metadata. Connections using Bolt v5.1 or later can be authenticated again with
+    /// [`reauthenticate`](Self::reauthenticate) when the provider's credentials change.
+    pub fn with_auth_provider(mut self, auth_provider: impl AuthProvider + 'static) -> Self {
+        self.auth_provider = Some(Arc::new(auth_provider));
+        self
+    }
+
+    pub fn tls_config(&self) -> Option<&TlsConfig> {
+        self.tls_config.as_ref()
+    }
+
+    pub fn routing_context(&self) -> Option<&HashMap<String, String>> {
+        self.routing_context.as_ref()
+    }
+
+    pub fn default_database(&self) -> Option<&str> {
+        self.default_database.as_deref()
+    }
+
+    /// Open a new connection and initialize it with `INIT` or `HELLO`, depending on the
+    /// negotiated protocol version. From Bolt v5.1, the credentials are sent in a separate
+    /// `LOGON` message after `HELLO`.
+    pub async fn connect(&self) -> Result<Connection> {
+        let stream = match &self.address {
+            Address::Tcp(addr) => {
+                Stream::connect_with_config(addr, self.tls_config.as_ref(), &self.tcp_config)
+                    .await?
+            }
+            // Every address the host resolves to is tried in turn, so that connections
+            // fail over to other servers behind the same name
+            Address::Host(addr) => {
+                Stream::connect_with_config(
+                    addr.as_str(),
+                    self.tls_config.as_ref(),
+                    &self.tcp_config,
+                )
+                .await?
+            }
+            #[cfg(unix)]
+            Address::Unix(path) => Stream::connect_unix(path).await?,
+            Address::Duplex(make_stream) => make_stream(),
+        };
+        self.initialize(stream).await
+    }
+
+    /// Like [`connect`](Self::connect), but connect to another server, such as a member of
+    /// the same cluster, given as a `host:port` address. If TLS is used, the host is used
+    /// as the server name.
+    pub async fn connect_to(&self, addr: &str) -> Result<Connection> {
+        let uri: ConnectionUri = format!("bolt://{}", addr).parse()?;
+        let tls_config = self
+            .tls_config
+            .as_ref()
+            .map(|tls_config| tls_config.for_server_name(uri.host()));
+        let stream =
+            Stream::connect_with_config(uri.addr(), tls_config.as_ref(), &self.tcp_config).await?;
+        self.initialize(stream).await
+    }
+
+    async fn initialize(&self, stream: Stream) -> Result<Connection> {
+        let stream = BufStream::new(stream).compat();
+        let mut client = match self.handshake_timeout {
+            Some(timeout) => {
+                Client::new_with_timeout(stream, &self.preferred_versions, timeout).await?
+            }
+            None => Client::new(stream, &self.preferred_versions).await?,
+        };
+        client.set_read_timeout(self.read_timeout);
+        client.set_default_database(self.default_database.clone());
+
+        let mut metadata = self.metadata.clone();
+        let auth_token = match &self.auth_provider {
+            Some(auth_provider) => Some(auth_provider.token().await?),
+            None => None,
+        };
+        // From Bolt v5.1, credentials are sent separately with LOGON
+        let mut auth: HashMap<String, Value> = AUTH_KEYS
+            .iter()
+            .filter_map(|&key| metadata.remove_entry(key))
+            .collect();
+        if let Some(auth_token) = &auth_token {
+            auth = HashMap::from(auth_token.clone());
+        }
+        let version = client.version();
+        if !at_least(version, V5_1) {
+            metadata.extend(auth.drain());
+        }
+
+        let response = match version {
+            V1_0 | V2_0 => {
+                let user_agent: String = metadata
+                    .remove("user_agent")
+                    .ok_or_else(|| Error::InvalidMetadata("must contain a user_agent".to_string()))
+                    .map(String::try_from)??;
+                client.init(user_agent, Metadata::from(metadata)).await?
+            }
+            V3_0 | V4_0 | V4_1 | V4_2 | V4_3 | V4_4 | V5_0 | V5_1 | V5_2 | V5_3 | V5_4 => {
+                if let (true, Some(routing_context)) =
+                    (at_least(version, V4_1), &self.routing_context)
+                {
+                    metadata.insert(
+                        String::from("routing"),
+                        Value::from(routing_context.clone()),
+                    );
+                }
+                // Ask for date-times in UTC, which are sent by default in v5.0 and later
+                if let V4_3 | V4_4 = version {
+                    metadata.insert(String::from("patch_bolt"), Value::from(vec!["utc"]));
+                }
+                match client.hello(Some(Metadata::from(metadata))).await? {
+                    Message::Success(_) if at_least(version, V5_1) => {
+                        // Servers with authentication disabled still expect a scheme
+                        if auth.is_empty() {
+                            auth = HashMap::from(AuthToken::none());
+                        }
+                        client.logon(Metadata::from(auth)).await?
+                    }
+                    response => response,
+                }
+            }
+            version => return Err(Error::UnsupportedOperation(version)),
+        };
+
+        match response {
+            Message::Success(_) => {
+                client.set_auth_token(auth_token);
+                Ok(client)
+            }
+            other => Err(Error::InitFailed(other)),
+        }
+    }
+
+    /// Authenticate the connection again with `LOGOFF` and `LOGON` if the
+    /// [`AuthProvider`] now supplies different credentials than the ones the connection
+    /// was authenticated with. This only applies to connections using Bolt v5.1 or later,
+    /// which must be idle; older connections keep their credentials until they are closed.
+    pub async fn reauthenticate(&self, client: &mut Connection) -> Result<()> {
+        let auth_provider = match &self.auth_provider {
+            Some(auth_provider) if at_least(client.version(), V5_1) => auth_provider,
+            _ => return Ok(()),
+        };
+        let auth_token = auth_provider.token().await?;
+        if client.auth_token() == Some(&auth_token) {
+            return Ok(());
+        }
+
+        // The connection can't be used with its old credentials once LOGOFF is sent
+        client.set_auth_token(None);
+        match client.logoff().await? {
+            Message::Success(_) => {}
+            other => return Err(Error::InitFailed(other)),
+        }
+        match client
+            .logon(Metadata::from(HashMap::from(auth_token.clone())))
+            .await?
+        {
+            Message::Success(_) => {
+                client.set_auth_token(Some(auth_token));
+                Ok(())
+            }
+            other => Err(Error::InitFailed(other)),
+        }
+    }
+
+    /// Check that the connection is idle and that the server still responds to requests.
+    /// Connections left inside a transaction, with an unconsumed result stream, or in a
+    /// failed state are rejected with [`Error::ConnectionNotReady`] without contacting the
+    /// server.
+    pub async fn validate(&self, client: &mut Connection) -> Result<()> {
+        if client.state() != ServerState::Ready {
+            return Err(Error::ConnectionNotReady(client.state()));
+        }
+
+        let version = client.version();
+        let response = match &self.validation_query {
+            None if at_least(version, V3_0) => client.reset().await?,
+            query => {
+                let query = query.as_deref().unwrap_or("RETURN 1;");
+                let response = match version {
+                    V1_0 | V2_0 => client.run(query, None).await?,
+                    _ => client.run_with_metadata(query, None, None).await?,
+                };
+                if !matches!(response, Message::Success(_)) {
+                    return Err(Error::ValidationFailed(response));
+                }
+                let (response, _records) = match version {
+                    V1_0 | V2_0 | V3_0 => client.pull_all().await?,
+                    _ => {
+                        client
+                            .pull(Some(Metadata::from_iter(vec![("n", -1)])))
+                            .await?
+                    }
+                };
+                response
+            }
+        };
+
+        match response {
+            Message::Success(_) => Ok(()),
+            other => Err(Error::ValidationFailed(other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        convert::TryInto,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    use async_trait::async_trait;
+    use bolt_proto::message::{Record, RunWithMetadata, Success};
+    use bolt_proto::value::Node;
+    use chrono::TimeZone;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    use super::*;
+    use crate::{
+        pool::tests::{serve_success, SharedToken},
+        AuthToken, Params,
+    };
+
+    #[tokio::test]
+    async fn from_uri() {
+        let metadata = HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]);
+        let connector = Connector::from_uri(
+            "neo4j+s://localhost:7688?region=eu",
+            [V4_1, 0, 0, 0],
+            metadata.clone(),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(&connector.address, Address::Host(addr) if addr == "localhost:7688"));
+        assert_eq!(connector.tls_config().unwrap().server_name(), "localhost");
+        assert_eq!(
+            connector.routing_context(),
+            Some(&HashMap::from_iter(vec![
+                (String::from("region"), String::from("eu")),
+                (String::from("address"), String::from("localhost:7688")),
+            ]))
+        );
+
+        let connector = Connector::from_uri("bolt://localhost", [V4_1, 0, 0, 0], metadata.clone())
+            .await
+            .unwrap();
+        assert!(matches!(&connector.address, Address::Host(addr) if addr == "localhost:7687"));
+        assert!(connector.tls_config().is_none());
+        assert!(connector.routing_context().is_none());
+
+        assert!(matches!(
+            Connector::from_uri("http://localhost", [V4_1, 0, 0, 0], metadata).await,
+            Err(Error::InvalidUri(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn validate() {
+        for &version in &[V1_0, V3_0, V4_1] {
+            let connector = Connector::duplex(
+                || {
+                    let (stream, server) = Stream::duplex(1024);
+                    tokio::spawn(serve_success(server, usize::MAX));
+                    stream
+                },
+                [version, 0, 0, 0],
+                HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+            );
+            let mut client = connector.connect().await.unwrap();
+            assert!(connector.validate(&mut client).await.is_ok());
+            let connector = connector.with_validation_query("RETURN 1;");
+            assert!(connector.validate(&mut client).await.is_ok());
+
+            if at_least(version, V3_0) {
+                client.begin(None).await.unwrap();
+                assert!(matches!(
+                    connector.validate(&mut client).await,
+                    Err(Error::ConnectionNotReady(ServerState::TxReady))
+                ));
+            } else {
+                client.run("RETURN 1;", None).await.unwrap();
+                assert!(matches!(
+                    connector.validate(&mut client).await,
+                    Err(Error::ConnectionNotReady(ServerState::Streaming))
+                ));
+            }
+        }
+    }
+
+    // Create a connector to in-memory servers that accept the "utc" patch if requested,
+    // respond to every message with SUCCESS, and send each received message to `sender`.
+    fn utc_patch_connector(version: u32, sender: std::sync::mpsc::Sender<Message>) -> Connector {
+        Connector::duplex(
+            move || {
+                let (stream, mut server) = Stream::duplex(1024);
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    let mut handshake = [0; 20];
+                    server.read_exact(&mut handshake).await.unwrap();
+                    server.write_all(&handshake[4..8]).await.unwrap();
+                    while let Ok(message) = Message::from_stream((&mut server).compat()).await {
+                        // Accept the patch if requested
+                        let metadata = match &message {
+                            Message::Hello(hello) => hello
+                                .metadata()
+                                .get("patch_bolt")
+                                .map(|patches| (String::from("patch_bolt"), patches.clone()))
+                                .into_iter()
+                                .collect(),
+                            _ => HashMap::new(),
+                        };
+                        sender.send(message).unwrap();
+                        let chunks: Vec<_> =
+                            Message::Success(Success::new(metadata)).try_into().unwrap();
+                        for chunk in chunks {
+                            server.write_all(&chunk).await.unwrap();
+                        }
+                    }
+                });
+                stream
+            },
+            [version, 0, 0, 0],
+            HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+        )
+    }
+
+    #[tokio::test]
+    async fn utc_patch() {
+        for &version in &[V4_1, V4_4, V5_0] {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let connector = utc_patch_connector(version, sender);
+            let mut client = connector.connect().await.unwrap();
+            match receiver.recv().unwrap() {
+                Message::Hello(hello) => {
+                    assert_eq!(hello.metadata().contains_key("patch_bolt"), version == V4_4)
+                }
+                other => panic!("unexpected request: {:?}", other),
+            }
+            assert_eq!(client.utc_date_times(), version != V4_1);
+
+            let date_time = chrono::FixedOffset::east_opt(3600)
+                .unwrap()
+                .with_ymd_and_hms(2021, 3, 28, 2, 30, 0)
+                .unwrap();
+            let parameters = HashMap::from_iter(vec![("at", Value::from(date_time))]);
+            client
+                .run_with_metadata("RETURN $at;", Some(Params::from(parameters)), None)
+                .await
+                .unwrap();
+            match receiver.recv().unwrap() {
+                Message::RunWithMetadata(run) => {
+                    let at = run.parameters()["at"].clone();
+                    assert_eq!(matches!(at, Value::DateTimeOffsetUtc(_)), version != V4_1);
+                    assert_eq!(
+                        chrono::DateTime::<chrono::FixedOffset>::try_from(at).unwrap(),
+                        date_time
+                    );
+                }
+                other => panic!("unexpected request: {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn utc_patch_pipeline() {
+        for &version in &[V4_1, V4_4] {
+            let (sender, receiver) = std::sync::mpsc::channel();
+            let connector = utc_patch_connector(version, sender);
+            let mut client = connector.connect().await.unwrap();
+            assert!(matches!(receiver.recv().unwrap(), Message::Hello(_)));
+
+            let date_time = chrono::FixedOffset::east_opt(3600)
+                .unwrap()
+                .with_ymd_and_hms(2021, 3, 28, 2, 30, 0)
+                .unwrap();
+            let run = RunWithMetadata::new(
+                String::from("RETURN $at;"),
+                HashMap::from_iter(vec![(String::from("at"), Value::from(date_time))]),
+                HashMap::new(),
+            );
+            let responses = client
+                .pipeline(vec![Message::RunWithMetadata(run)])
+                .await
+                .unwrap();
+            assert!(matches!(responses[..], [Message::Success(_)]));
+            match receiver.recv().unwrap() {
+                Message::RunWithMetadata(run) => {
+                    let at = run.parameters()["at"].clone();
+                    assert_eq!(matches!(at, Value::DateTimeOffsetUtc(_)), version == V4_4);
+                    assert_eq!(
+                        chrono::DateTime::<chrono::FixedOffset>::try_from(at).unwrap(),
+                        date_time
+                    );
+                }
+                other => panic!("unexpected request: {:?}", other),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn element_ids() {
+        let node = Node::new(
+            1,
+            vec![String::from("Person")],
+            HashMap::<String, Value>::new(),
+        )
+        .with_element_id("4:db:1");
+        let record_node = node.clone();
+        let connector = Connector::duplex(
+            move || {
+                let (stream, mut server) = Stream::duplex(1024);
+                let node = record_node.clone();
+                tokio::spawn(async move {
+                    let mut handshake = [0; 20];
+                    server.read_exact(&mut handshake).await.unwrap();
+                    server.write_all(&handshake[4..8]).await.unwrap();
+                    while let Ok(message) = Message::from_stream((&mut server).compat()).await {
+                        let mut responses = vec![];
+                        if let Message::Pull(_) = message {
+                            responses.push(Message::Record(Record::new(vec![Value::from(
+                                node.clone(),
+                            )])));
+                        }
+                        responses.push(Message::Success(Success::new(HashMap::new())));
+                        for response in responses {
+                            let chunks: Vec<_> = response.try_into().unwrap();
+                            for chunk in chunks {
+                                server.write_all(&chunk).await.unwrap();
+                            }
+                        }
+                    }
+                });
+                stream
+            },
+            [V5_0, 0, 0, 0],
+            HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+        );
+
+        let mut client = connector.connect().await.unwrap();
+        client
+            .run_with_metadata("MATCH (n) RETURN n;", None, None)
+            .await
+            .unwrap();
+        let (response, records) = client.pull(None).await.unwrap();
+        assert!(matches!(response, Message::Success(_)));
+        match &records[0].fields()[0] {
+            Value::Node(received) => {
+                assert_eq!(received, &node);
+                assert_eq!(received.element_id(), Some("4:db:1"));
+            }
+            other => panic!("unexpected value: {:?}", other),
+        }
+    }
+
+    struct RotatingToken(AtomicUsize);
+
+    #[async_trait]
+    impl AuthProvider for RotatingToken {
+        async fn token(&self) -> Result<AuthToken> {
+            match self.0.fetch_add(1, Ordering::SeqCst) {
+                2 => Err(Error::CredentialsUnavailable(
+                    "identity provider is down".into(),
+                )),
+                n => Ok(AuthToken::bearer(format!("token-{}", n))),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn auth_provider() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let connector = Connector::duplex(
+            move || {
+                let (stream, mut server) = Stream::duplex(1024);
+                let sender = sender.clone();
+                tokio::spawn(async move {
+                    let mut handshake = [0; 20];
+                    server.read_exact(&mut handshake).await.unwrap();
+                    server.write_all(&V4_1.to_be_bytes()).await.unwrap();
+                    if let Ok(Message::Hello(hello)) =
+                        Message::from_stream((&mut server).compat()).await
+                    {
+                        sender.send(hello.metadata().clone()).unwrap();
+                    }
+                    let chunks: Vec<_> = Message::Success(Success::new(HashMap::new()))
+                        .try_into()
+                        .unwrap();
+                    for chunk in chunks {
+                        server.write_all(&chunk).await.unwrap();
+                    }
+                    while server.read(&mut [0; 1024]).await.unwrap_or(0) > 0 {}
+                });
+                stream
+            },
+            [V4_1, 0, 0, 0],
+            HashMap::from_iter(vec![
+                ("user_agent", "bolt-client/X.Y.Z"),
+                ("scheme", "basic"),
+                ("principal", "neo4j"),
+            ]),
+        )
+        .with_auth_provider(RotatingToken(AtomicUsize::new(0)));
+
+        for n in 0..2 {
+            connector.connect().await.unwrap();
+            let metadata = receiver.recv().unwrap();
+            assert_eq!(metadata.get("scheme"), Some(&Value::from("bearer")));
+            assert_eq!(
+                metadata.get("credentials"),
+                Some(&Value::from(format!("token-{}", n)))
+            );
+            assert_eq!(metadata.get("principal"), None);
+            assert_eq!(
+                metadata.get("user_agent"),
+                Some(&Value::from("bolt-client/X.Y.Z"))
+            );
+        }
+        assert!(matches!(
+            connector.connect().await,
+            Err(Error::CredentialsUnavailable(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn logon() {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        utc_patch_connector(V5_1, sender).connect().await.unwrap();
+        assert!(matches!(receiver.recv().unwrap(), Message::Hello(_)));
+        match receiver.recv().unwrap() {
+            Message::Logon(logon) => {
+                assert_eq!(logon.metadata().get("scheme"), Some(&Value::from("none")))
+            }
+            other => panic!("unexpected request: {:?}", other),
+        }
+
+        let token = Arc::new(std::sync::Mutex::new(String::from("token-0")));
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let connector =
+            utc_patch_connector(V5_1, sender).with_auth_provider(SharedToken(Arc::clone(&token)));
+        let mut client = connector.connect().await.unwrap();
+        match receiver.recv().unwrap() {
+            Message::Hello(hello) => {
+                assert!(!hello.metadata().contains_key("scheme"));
+                assert!(!hello.metadata().contains_key("credentials"));
+                assert_eq!(
+                    hello.metadata().get("user_agent"),
+                    Some(&Value::from("bolt-client/X.Y.Z"))
+                );
+            }
+            other => panic!("unexpected request: {:?}", other),
+        }
+        match receiver.recv().unwrap() {
+            Message::Logon(logon) => assert_eq!(
+                logon.metadata().get("credentials"),
+                Some(&Value::from("token-0"))
+            ),
+            other => panic!("unexpected request: {:?}", other),
+        }
+
+        // Nothing is sent while the credentials are unchanged
+        connector.reauthenticate(&mut client).await.unwrap();
+        *token.lock().unwrap() = String::from("token-1");
+        connector.reauthenticate(&mut client).await.unwrap();
+        assert!(matches!(receiver.recv().unwrap(), Message::Logoff));
+        match receiver.recv().unwrap() {
+            Message::Logon(logon) => assert_eq!(
+                logon.metadata().get("credentials"),
+                Some(&Value::from("token-1"))
+            ),
+            other => panic!("unexpected request: {:?}", other),
+        }
+        assert_eq!(client.state(), ServerState::Ready);
+    }
+
+    #[tokio::test]
+    async fn init_failed() {
+        let connector = Connector::duplex(
+            || {
+                let (stream, server) = Stream::duplex(1024);
+                tokio::spawn(serve_success(server, 0));
+                stream
+            },
+            [V1_0, 0, 0, 0],
+            HashMap::<String, Value>::new(),
+        );
+        assert!(matches!(
+            connector.connect().await,
+            Err(Error::InvalidMetadata(_))
+        ));
+    }
+}
@@ -0,0 +1,262 @@
+use std::{collections::HashMap, iter::FromIterator, sync::Arc, time::Duration};
+
+use bolt_proto::{version::*, Value};
+
+use super::{Address, AuthProvider, Connector};
+use crate::{error::*, AuthToken, ConnectionUri, TcpConfig, TlsConfig};
+
+/// Configures a [`Connector`] for a server identified by a host name and port.
+///
+/// Unlike [`Connector::new`], which resolves its address once, connectors built this way
+/// resolve the host each time a connection is opened, trying each resolved address in
+/// turn. Servers behind several DNS records, or moved to a new address, are thus picked
+/// up without recreating the connector.
+///
+/// By default, connections send no credentials, identify themselves with a
+/// `bolt-client/<version>` user agent, and offer Bolt versions 4.1, 4.0, 3.0 and 2.0.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use bolt_client::{pool::Connector, AuthToken};
+///
+/// let connector = Connector::builder("db.example.com:7687")
+///     .with_auth_token(AuthToken::basic("neo4j", "password"))
+///     .with_user_agent("my-client-name/1.0")
+///     .with_default_database("movies")
+///     .with_handshake_timeout(Duration::from_secs(5))
+///     .build();
+/// assert_eq!(connector.default_database(), Some("movies"));
+/// ```
+#[derive(Clone)]
+pub struct ConnectorBuilder {
+    addr: String,
+    auth_provider: Arc<dyn AuthProvider>,
+    user_agent: String,
+    preferred_versions: [u32; 4],
+    tls_config: Option<TlsConfig>,
+    tcp_config: TcpConfig,
+    handshake_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    routing_context: Option<HashMap<String, String>>,
+    validation_query: Option<String>,
+    default_database: Option<String>,
+}
+
+impl ConnectorBuilder {
+    /// Start configuring a connector for the server at the given `host:port` address.
+    pub fn new(addr: impl Into<String>) -> Self {
+        Self {
+            addr: addr.into(),
+            auth_provider: Arc::new(AuthToken::none()),
+            user_agent: format!("bolt-client/{}", env!("CARGO_PKG_VERSION")),
+            preferred_versions: [V4_1, V4_0, V3_0, V2_0],
+            tls_config: None,
+            tcp_config: TcpConfig::default(),
+            handshake_timeout: None,
+            read_timeout: None,
+            routing_context: None,
+            validation_query: None,
+            default_database: None,
+        }
+    }
+
+    /// Start configuring a connector for the server identified by a connection URI, such
+    /// as `neo4j+s://db.example.com:7687`. TLS is configured according to the URI's scheme,
+    /// and its routing context is sent to servers supporting Bolt v4.1 or later.
+    pub fn from_uri(uri: &str) -> Result<Self> {
+        let uri: ConnectionUri = uri.parse()?;
+        let mut builder = Self::new(uri.addr());
+        builder.tls_config = uri.tls_config();
+        builder.routing_context = uri.routing_context();
+        Ok(builder)
+    }
+
+    /// Authenticate new connections with the given credentials.
+    pub fn with_auth_token(mut self, auth_token: AuthToken) -> Self {
+        self.auth_provider = Arc::new(auth_token);
+        self
+    }
+
+    /// Authenticate each new connection with the credentials currently supplied by the
+    /// given provider.
+    pub fn with_auth_provider(mut self, auth_provider: impl AuthProvider + 'static) -> Self {
+        self.auth_provider = Arc::new(auth_provider);
+        self
+    }
+
+    /// Identify new connections to the server with the given user agent, such as
+    /// `my-client-name/1.0`.
+    pub fn with_user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Offer the given protocol versions in the handshake, in order of preference. Each
+    /// entry may be a single version, a range of versions created with
+    /// [`version::range`](bolt_proto::version::range), or 0 if unused.
+    pub fn with_preferred_versions(mut self, preferred_versions: [u32; 4]) -> Self {
+        self.preferred_versions = preferred_versions;
+        self
+    }
+
+    /// Send transactions and queries that do not name a database to the given database,
+    /// on connections using Bolt v4 or later.
+    pub fn with_default_database(mut self, database: impl Into<String>) -> Self {
+        self.default_database = Some(database.into());
+        self
+    }
+
+    /// Negotiate TLS with the given configuration when opening connections.
+    pub fn with_tls_config(mut self, tls_config: TlsConfig) -> Self {
+        self.tls_config = Some(tls_config);
+        self
+    }
+
+    /// Use the given socket settings, such as connect timeout and keepalive, when opening
+    /// connections.
+    pub fn with_tcp_config(mut self, tcp_config: TcpConfig) -> Self {
+        self.tcp_config = tcp_config;
+        self
+    }
+
+    /// Fail to open a connection if the Bolt handshake does not complete within the given
+    /// duration.
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = Some(timeout);
+        self
+    }
+
+    /// Fail requests on a connection if a response message does not arrive within the
+    /// given duration. The connection is then considered broken.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Send the given routing context to servers supporting Bolt v4.1 or later, replacing
+    /// any context taken from a connection URI.
+    pub fn with_routing_context(mut self, routing_context: HashMap<String, String>) -> Self {
+        self.routing_context = Some(routing_context);
+        self
+    }
+
+    /// Validate connections by running the given query and consuming its results, instead
+    /// of the default check.
+    pub fn with_validation_query(mut self, query: impl Into<String>) -> Self {
+        self.validation_query = Some(query.into());
+        self
+    }
+
+    pub fn build(self) -> Connector {
+        let metadata = HashMap::from_iter(vec![(
+            String::from("user_agent"),
+            Value::from(self.user_agent),
+        )]);
+        let mut connector =
+            Connector::with_address(Address::Host(self.addr), self.preferred_versions, metadata);
+        connector.tls_config = self.tls_config;
+        connector.tcp_config = self.tcp_config;
+        connector.handshake_timeout = self.handshake_timeout;
+        connector.read_timeout = self.read_timeout;
+        connector.routing_context = self.routing_context;
+        connector.validation_query = self.validation_query;
+        connector.default_database = self.default_database;
+        connector.auth_provider = Some(self.auth_provider);
+        connector
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryInto;
+
+    use bolt_proto::{message::*, Message};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+    use tokio_util::compat::*;
+
+    use super::*;
+
+    // Accept a single connection, choosing version 4.0 in the handshake and answering
+    // each request with SUCCESS, and return the handshake and requests received.
+    async fn serve_once(listener: TcpListener, requests: usize) -> ([u8; 20], Vec<Message>) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut handshake = [0; 20];
+        stream.read_exact(&mut handshake).await.unwrap();
+        stream.write_all(&V4_0.to_be_bytes()).await.unwrap();
+        stream.flush().await.unwrap();
+
+        let mut received = Vec::with_capacity(requests);
+        for _ in 0..requests {
+            received.push(Message::from_stream((&mut stream).compat()).await.unwrap());
+            let chunks: Vec<_> = Message::Success(Success::new(HashMap::new()))
+                .try_into()
+                .unwrap();
+            for chunk in chunks {
+                stream.write_all(&chunk).await.unwrap();
+            }
+            stream.flush().await.unwrap();
+        }
+        (handshake, received)
+    }
+
+    #[test]
+    fn from_uri() {
+        let connector = ConnectorBuilder::from_uri("neo4j+s://localhost:7688?region=eu")
+            .unwrap()
+            .build();
+        assert!(matches!(&connector.address, Address::Host(addr) if addr == "localhost:7688"));
+        assert_eq!(connector.tls_config().unwrap().server_name(), "localhost");
+        assert!(connector.routing_context().is_some());
+
+        assert!(matches!(
+            ConnectorBuilder::from_uri("http://localhost"),
+            Err(Error::InvalidUri(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn build() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(serve_once(listener, 2));
+
+        let connector = Connector::builder(format!("localhost:{}", port))
+            .with_auth_token(AuthToken::basic("neo4j", "secret"))
+            .with_user_agent("my-client-name/1.0")
+            .with_preferred_versions([range(V4_1, 1), 0, 0, 0])
+            .with_default_database("movies")
+            .build();
+        let mut connection = connector.connect().await.unwrap();
+        assert_eq!(connection.version(), V4_0);
+        connection
+            .run_with_metadata("RETURN 1;", None, None)
+            .await
+            .unwrap();
+
+        let (handshake, requests) = server.await.unwrap();
+        assert_eq!(&handshake[4..8], &[0x00, 0x01, 0x01, 0x04]);
+        match &requests[..] {
+            [Message::Hello(hello), Message::RunWithMetadata(run)] => {
+                assert_eq!(
+                    hello.metadata(),
+                    &HashMap::from_iter(vec![
+                        (
+                            String::from("user_agent"),
+                            Value::from("my-client-name/1.0")
+                        ),
+                        (String::from("scheme"), Value::from("basic")),
+                        (String::from("principal"), Value::from("neo4j")),
+                        (String::from("credentials"), Value::from("secret")),
+                    ])
+                );
+                assert_eq!(run.metadata().get("db"), Some(&Value::from("movies")));
+            }
+            other => panic!("unexpected requests: {:?}", other),
+        }
+    }
+}
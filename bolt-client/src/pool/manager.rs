@@ -0,0 +1,251 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+use super::{Connection, Connector};
+use crate::{error::Error as ClientError, ServerState};
+
+/// The connection handling shared by adapters for other connection pools, such as
+/// `bb8-bolt` and `deadpool-bolt`: a [`Connector`], along with the maximum lifetime and the
+/// idle time before a liveness check that a [`PoolConfig`](super::PoolConfig) sets for a
+/// [`Pool`](super::Pool).
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+///
+/// use bolt_client::{pool::*, AuthToken};
+///
+/// let manager = ConnectionManager::from(
+///     Connector::builder("db.example.com:7687")
+///         .with_auth_token(AuthToken::basic("neo4j", "password"))
+///         .with_user_agent("my-client-name/1.0")
+///         .build(),
+/// )
+/// .with_max_lifetime(Duration::from_secs(60 * 60));
+/// ```
+pub struct ConnectionManager {
+    connector: Connector,
+    max_lifetime: Option<Duration>,
+    idle_time_before_check: Option<Duration>,
+}
+
+impl ConnectionManager {
+    /// Create a manager that opens and validates connections with the given
+    /// [`Connector`]. Connections are never retired for their age, and are validated every
+    /// time they are checked out.
+    pub fn new(connector: Connector) -> Self {
+        Self {
+            connector,
+            max_lifetime: None,
+            idle_time_before_check: None,
+        }
+    }
+
+    /// Discard connections that have been open for longer than the given duration, instead
+    /// of reusing them.
+    pub fn with_max_lifetime(mut self, max_lifetime: Duration) -> Self {
+        self.max_lifetime = Some(max_lifetime);
+        self
+    }
+
+    /// Only validate connections when checked out if no message has been exchanged on them
+    /// for at least the given duration. By default, connections are validated every time.
+    pub fn with_idle_time_before_check(mut self, idle_time: Duration) -> Self {
+        self.idle_time_before_check = Some(idle_time);
+        self
+    }
+
+    /// Reconfigure the [`Connector`], typically with one of its `with_*` methods, keeping the
+    /// manager's other settings.
+    pub fn map_connector(mut self, f: impl FnOnce(Connector) -> Connector) -> Self {
+        self.connector = f(self.connector);
+        self
+    }
+
+    pub fn connector(&self) -> &Connector {
+        &self.connector
+    }
+
+    pub fn max_lifetime(&self) -> Option<Duration> {
+        self.max_lifetime
+    }
+
+    pub fn idle_time_before_check(&self) -> Option<Duration> {
+        self.idle_time_before_check
+    }
+
+    /// Open and initialize a new connection with the [`Connector`].
+    pub async fn connect(&self) -> Result<Connection, ManagerError> {
+        Ok(self.connector.connect().await?)
+    }
+
+    /// Whether the connection has been open for longer than the maximum lifetime.
+    pub fn is_expired(&self, conn: &Connection) -> bool {
+        matches!(self.max_lifetime, Some(max_lifetime) if conn.created_at().elapsed() >= max_lifetime)
+    }
+
+    /// Whether the connection should be discarded rather than returned to the pool.
+    pub fn has_broken(&self, conn: &Connection) -> bool {
+        conn.is_broken() || self.is_expired(conn)
+    }
+
+    /// Prepare a connection to be checked out: reject it if it has expired, reset it if it
+    /// was returned in the middle of a result stream, a transaction, or a failure,
+    /// authenticate it again with [`Connector::reauthenticate`] if the credentials have
+    /// changed, and validate it with [`Connector::validate`] unless it was used recently.
+    pub async fn check(&self, conn: &mut Connection) -> Result<(), ManagerError> {
+        if self.is_expired(conn) {
+            return Err(ManagerError::ConnectionExpired);
+        }
+        conn.ensure_ready().await?;
+        self.connector.reauthenticate(conn).await?;
+        match self.idle_time_before_check {
+            Some(idle_time) if conn.last_used().elapsed() < idle_time => Ok(()),
+            _ => Ok(self.connector.validate(conn).await?),
+        }
+    }
+}
+
+impl From<Connector> for ConnectionManager {
+    fn from(connector: Connector) -> Self {
+        Self::new(connector)
+    }
+}
+
+/// The errors reported by a [`ConnectionManager`], in which the failures specific to
+/// opening and checking out connections are separated from other client errors.
+#[derive(Debug, Error)]
+pub enum ManagerError {
+    #[error("invalid host address")]
+    InvalidAddress,
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(String),
+    #[error("client initialization failed: received {0:?}")]
+    ClientInitFailed(bolt_proto::Message),
+    #[error("invalid client version: {0:#x}")]
+    InvalidClientVersion(u32),
+    #[error("connection is not ready: server is in the {0:?} state")]
+    ConnectionNotReady(ServerState),
+    #[error("connection validation failed: received {0:?}")]
+    ValidationFailed(bolt_proto::Message),
+    #[error("connection has exceeded its maximum lifetime")]
+    ConnectionExpired,
+    #[error(transparent)]
+    ClientError(ClientError),
+    #[error(transparent)]
+    ProtocolError(#[from] bolt_proto::error::Error),
+    #[error(transparent)]
+    IOError(#[from] std::io::Error),
+}
+
+impl From<ClientError> for ManagerError {
+    fn from(error: ClientError) -> Self {
+        match error {
+            ClientError::InvalidAddress => ManagerError::InvalidAddress,
+            ClientError::InvalidMetadata(message) => ManagerError::InvalidMetadata(message),
+            ClientError::InitFailed(response) => ManagerError::ClientInitFailed(response),
+            ClientError::UnsupportedOperation(version) => {
+                ManagerError::InvalidClientVersion(version)
+            }
+            ClientError::ConnectionNotReady(state) => ManagerError::ConnectionNotReady(state),
+            ClientError::ValidationFailed(response) => ManagerError::ValidationFailed(response),
+            ClientError::ProtocolError(error) => ManagerError::ProtocolError(error),
+            ClientError::IOError(error) => ManagerError::IOError(error),
+            other => ManagerError::ClientError(other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        iter::FromIterator,
+        sync::{Arc, Mutex},
+    };
+
+    use bolt_proto::version::*;
+
+    use super::*;
+    use crate::{
+        pool::tests::{connector, serve_success, SharedToken},
+        AuthToken, Stream,
+    };
+
+    #[tokio::test]
+    async fn max_lifetime() {
+        let manager = ConnectionManager::from(connector(usize::MAX).0)
+            .with_max_lifetime(Duration::from_millis(50));
+
+        let mut conn = manager.connect().await.unwrap();
+        assert!(manager.check(&mut conn).await.is_ok());
+        assert!(!manager.has_broken(&conn));
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(manager.is_expired(&conn));
+        assert!(manager.has_broken(&conn));
+        assert!(matches!(
+            manager.check(&mut conn).await,
+            Err(ManagerError::ConnectionExpired)
+        ));
+    }
+
+    #[tokio::test]
+    async fn idle_time_before_check() {
+        // The server stops responding after initialization
+        let manager =
+            ConnectionManager::from(connector(1).0.with_read_timeout(Duration::from_millis(50)))
+                .with_idle_time_before_check(Duration::from_millis(100));
+
+        // Recently used connections are not validated
+        let mut conn = manager.connect().await.unwrap();
+        assert!(manager.check(&mut conn).await.is_ok());
+
+        tokio::time::sleep(Duration::from_millis(110)).await;
+        assert!(matches!(
+            manager.check(&mut conn).await,
+            Err(ManagerError::ClientError(ClientError::Timeout))
+        ));
+        assert!(manager.has_broken(&conn));
+    }
+
+    #[tokio::test]
+    async fn reauthenticate() {
+        let token = Arc::new(Mutex::new(String::from("token-0")));
+        let manager = ConnectionManager::from(
+            Connector::duplex(
+                || {
+                    let (stream, server) = Stream::duplex(1024);
+                    tokio::spawn(serve_success(server, usize::MAX));
+                    stream
+                },
+                [V5_1, 0, 0, 0],
+                HashMap::from_iter(vec![("user_agent", "bolt-client/X.Y.Z")]),
+            )
+            .with_auth_provider(SharedToken(Arc::clone(&token))),
+        );
+
+        let mut conn = manager.connect().await.unwrap();
+        assert_eq!(conn.auth_token(), Some(&AuthToken::bearer("token-0")));
+        *token.lock().unwrap() = String::from("token-1");
+        assert!(manager.check(&mut conn).await.is_ok());
+        assert_eq!(conn.auth_token(), Some(&AuthToken::bearer("token-1")));
+    }
+
+    #[test]
+    fn error_mapping() {
+        assert!(matches!(
+            ManagerError::from(ClientError::UnsupportedOperation(V1_0)),
+            ManagerError::InvalidClientVersion(V1_0)
+        ));
+        assert!(matches!(
+            ManagerError::from(ClientError::InvalidAddress),
+            ManagerError::InvalidAddress
+        ));
+        assert!(matches!(
+            ManagerError::from(ClientError::Timeout),
+            ManagerError::ClientError(ClientError::Timeout)
+        ));
+    }
+}
@@ -0,0 +1,295 @@
+//! Helpers for consuming the records produced by a query.
+//!
+//! A [`QueryResult`] is built from the responses to a `RUN` request and the `PULL` or
+//! `PULL_ALL` that followed it, and pairs each record with the field names announced in
+//! the `RUN` response, so that fields can be looked up by name.
+//!
+//! # Example
+//! ```
+//! use std::{collections::HashMap, iter::FromIterator};
+//!
+//! use bolt_client::result::QueryResult;
+//! use bolt_proto::{message::*, Message, Value};
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! // The responses to `client.run_with_metadata(...)` and `client.pull(...)`
+//! let run_response = Message::Success(Success::new(HashMap::from_iter(vec![(
+//!     String::from("fields"),
+//!     Value::from(vec!["name", "age"]),
+//! )])));
+//! let records = vec![Record::new(vec![Value::from("Alice"), Value::from(42)])];
+//! let pull_response = (Message::Success(Success::new(HashMap::new())), records);
+//!
+//! let result = QueryResult::new(run_response, pull_response)?;
+//! assert_eq!(result.keys(), &["name", "age"]);
+//! let record = result.single()?;
+//! assert_eq!(record.get::<String>("name")?, "Alice");
+//! assert_eq!(record.get::<i64>("age")?, 42);
+//! # Ok(())
+//! # }
+//! ```
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::TryFrom,
+    iter::FromIterator,
+    sync::Arc,
+};
+
+use bolt_proto::{graph::Graph, message::Success, Message, Value};
+
+use crate::error::*;
+
+/// The records produced by a query, along with their field names and the metadata
+/// returned once all records were received.
+///
+/// Records are consumed in order by iterating over the result.
+#[derive(Debug, Clone)]
+pub struct QueryResult {
+    keys: Arc<[String]>,
+    records: VecDeque<Record>,
+    summary: HashMap<String, Value>,
+}
+
+impl QueryResult {
+    /// Create a result from the response to a `RUN` request, and the response and records
+    /// returned by the following `PULL` or `PULL_ALL`. Fails with [`Error::QueryFailed`]
+    /// if either response is not `SUCCESS`.
+    pub fn new(
+        run_response: Message,
+        pull_response: (Message, Vec<bolt_proto::message::Record>),
+    ) -> Result<Self> {
+        let run_success = Success::try_from(run_response.clone())
+            .map_err(|_| Error::QueryFailed(run_response))?;
+        let (pull_response, records) = pull_response;
+        let pull_success = Success::try_from(pull_response.clone())
+            .map_err(|_| Error::QueryFailed(pull_response))?;
+
+        let keys: Arc<[String]> = match run_success.metadata().get("fields") {
+            Some(fields) => Vec::<String>::try_from(fields.clone())?.into(),
+            None => Arc::new([]),
+        };
+        let records = records
+            .into_iter()
+            .map(|record| Record {
+                keys: Arc::clone(&keys),
+                fields: record.into_fields(),
+            })
+            .collect();
+        Ok(Self {
+            keys,
+            records,
+            summary: pull_success.metadata().clone(),
+        })
+    }
+
+    /// Get the names of the fields in each record.
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// Get the metadata sent by the server after the last record, such as query
+    /// statistics and bookmarks.
+    pub fn summary(&self) -> &HashMap<String, Value> {
+        &self.summary
+    }
+
+    /// Get the number of records not consumed yet.
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Get the next record without consuming it.
+    pub fn peek(&self) -> Option<&Record> {
+        self.records.front()
+    }
+
+    /// Consume the result, which must contain exactly one remaining record. Fails with
+    /// [`Error::UnexpectedRecordCount`] otherwise.
+    pub fn single(mut self) -> Result<Record> {
+        match self.records.len() {
+            1 => Ok(self.records.pop_front().unwrap()),
+            count => Err(Error::UnexpectedRecordCount(count)),
+        }
+    }
+
+    /// Consume the result, converting the field with the given name in each remaining
+    /// record.
+    pub fn column<T>(self, key: &str) -> Result<Vec<T>>
+    where
+        T: TryFrom<Value, Error = bolt_proto::error::Error>,
+    {
+        self.records.iter().map(|record| record.get(key)).collect()
+    }
+}
+
+impl Iterator for QueryResult {
+    type Item = Record;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.records.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.records.len(), Some(self.records.len()))
+    }
+}
+
+impl ExactSizeIterator for QueryResult {}
+
+/// A record in a [`QueryResult`], whose fields can be looked up by name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    keys: Arc<[String]>,
+    fields: Vec<Value>,
+}
+
+impl Record {
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    pub fn fields(&self) -> &[Value] {
+        &self.fields
+    }
+
+    pub fn into_fields(self) -> Vec<Value> {
+        self.fields
+    }
+
+    /// Get the value of the field with the given name, if there is one.
+    pub fn value(&self, key: &str) -> Option<&Value> {
+        self.keys
+            .iter()
+            .position(|k| k == key)
+            .and_then(|index| self.fields.get(index))
+    }
+
+    /// Get the field with the given name, converted to the requested type. Fails with
+    /// [`Error::FieldNotFound`] if there is no such field, or with
+    /// [`Error::ProtocolError`] if the field cannot be converted.
+    pub fn get<T>(&self, key: &str) -> Result<T>
+    where
+        T: TryFrom<Value, Error = bolt_proto::error::Error>,
+    {
+        let value = self
+            .value(key)
+            .ok_or_else(|| Error::FieldNotFound(key.to_string()))?;
+        Ok(T::try_from(value.clone())?)
+    }
+}
+
+/// Collect the nodes and relationships in every field of every record into a [`Graph`].
+impl Extend<Record> for Graph {
+    fn extend<T: IntoIterator<Item = Record>>(&mut self, iter: T) {
+        for record in iter {
+            self.extend(record.fields());
+        }
+    }
+}
+
+impl FromIterator<Record> for Graph {
+    fn from_iter<T: IntoIterator<Item = Record>>(iter: T) -> Self {
+        let mut graph = Graph::new();
+        graph.extend(iter);
+        graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bolt_proto::{message::Failure, value::Node};
+
+    use super::*;
+
+    fn result(count: i64) -> QueryResult {
+        let run_response = Message::Success(Success::new(HashMap::from_iter(vec![(
+            String::from("fields"),
+            Value::from(vec!["n", "node"]),
+        )])));
+        let records = (0..count)
+            .map(|n| {
+                let node = Node::new(
+                    n,
+                    vec![String::from("Test")],
+                    HashMap::<String, Value>::new(),
+                );
+                bolt_proto::message::Record::new(vec![Value::from(n), Value::from(node)])
+            })
+            .collect();
+        let pull_response = Message::Success(Success::new(HashMap::from_iter(vec![(
+            String::from("bookmark"),
+            Value::from("bm"),
+        )])));
+        QueryResult::new(run_response, (pull_response, records)).unwrap()
+    }
+
+    #[test]
+    fn keys_and_summary() {
+        let result = result(2);
+        assert_eq!(result.keys(), &["n", "node"]);
+        assert_eq!(result.summary().get("bookmark"), Some(&Value::from("bm")));
+        assert_eq!(result.len(), 2);
+    }
+
+    #[test]
+    fn get() {
+        let record = result(1).single().unwrap();
+        assert_eq!(record.get::<i64>("n").unwrap(), 0);
+        assert_eq!(record.get::<Node>("node").unwrap().labels(), &["Test"]);
+        assert!(matches!(
+            record.get::<i64>("missing"),
+            Err(Error::FieldNotFound(key)) if key == "missing"
+        ));
+        assert!(matches!(
+            record.get::<String>("n"),
+            Err(Error::ProtocolError(_))
+        ));
+    }
+
+    #[test]
+    fn single() {
+        assert!(matches!(
+            result(0).single(),
+            Err(Error::UnexpectedRecordCount(0))
+        ));
+        assert!(matches!(
+            result(2).single(),
+            Err(Error::UnexpectedRecordCount(2))
+        ));
+    }
+
+    #[test]
+    fn peek_and_iterate() {
+        let mut result = result(3);
+        assert_eq!(result.peek().unwrap().get::<i64>("n").unwrap(), 0);
+        assert_eq!(result.next().unwrap().get::<i64>("n").unwrap(), 0);
+        assert_eq!(result.peek().unwrap().get::<i64>("n").unwrap(), 1);
+        assert_eq!(result.column::<i64>("n").unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn failed_query() {
+        let failure = Message::Failure(Failure::new(HashMap::new()));
+        let success = Message::Success(Success::new(HashMap::new()));
+        assert!(matches!(
+            QueryResult::new(failure.clone(), (success.clone(), vec![])),
+            Err(Error::QueryFailed(Message::Failure(_)))
+        ));
+        assert!(matches!(
+            QueryResult::new(success, (Message::Ignored, vec![])),
+            Err(Error::QueryFailed(Message::Ignored))
+        ));
+    }
+
+    #[test]
+    fn graph() {
+        // Every record has a distinct node
+        let graph: Graph = result(3).collect();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.nodes_with_label("Test").count(), 3);
+    }
+}
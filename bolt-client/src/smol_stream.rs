@@ -0,0 +1,110 @@
+use async_net::{AsyncToSocketAddrs, TcpStream};
+
+use crate::{error::*, ConnectionUri, FuturesStream, TcpConfig, TlsConfig};
+
+/// A [`FuturesStream`] over a smol [`TcpStream`](async_net::TcpStream).
+#[cfg_attr(docsrs, doc(cfg(feature = "smol-stream")))]
+pub type SmolStream = FuturesStream<TcpStream>;
+
+impl SmolStream {
+    /// Establish a connection with a remote socket. If a domain is provided, TLS
+    /// negotiation will be attempted using the default [`TlsConfig`] for that domain.
+    pub async fn connect(
+        addr: impl AsyncToSocketAddrs,
+        domain: Option<impl AsRef<str>>,
+    ) -> Result<Self> {
+        let tls_config = domain.map(|domain| TlsConfig::new(domain.as_ref()));
+        Self::connect_with_config(addr, tls_config.as_ref(), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with the server identified by a [`ConnectionUri`],
+    /// negotiating TLS if required by its scheme.
+    pub async fn connect_uri(uri: &ConnectionUri) -> Result<Self> {
+        let tls_config = uri.tls_config();
+        Self::connect_with_config(uri.addr(), tls_config.as_ref(), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with a remote socket and negotiate TLS using the given
+    /// [`TlsConfig`].
+    pub async fn connect_tls(
+        addr: impl AsyncToSocketAddrs,
+        tls_config: &TlsConfig,
+    ) -> Result<Self> {
+        Self::connect_with_config(addr, Some(tls_config), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with a remote socket using the given [`TcpConfig`]. If a
+    /// [`TlsConfig`] is provided, TLS negotiation will be attempted.
+    pub async fn connect_with_config(
+        addr: impl AsyncToSocketAddrs,
+        tls_config: Option<&TlsConfig>,
+        tcp_config: &TcpConfig,
+    ) -> Result<Self> {
+        Self::from_tcp(TcpStream::connect(addr), tls_config, tcp_config).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_net::TcpListener;
+    use socket2::SockRef;
+
+    use super::*;
+    use crate::futures_stream::tests::*;
+
+    #[test]
+    fn tcp() {
+        smol::block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let handle = smol::spawn(async move {
+                let (stream, _) = listener.accept().await.unwrap();
+                serve_hello(stream).await;
+            });
+
+            hello(SmolStream::connect(addr, None::<String>).await.unwrap()).await;
+            handle.await;
+        })
+    }
+
+    #[test]
+    fn secure_tcp() {
+        smol::block_on(async {
+            let acceptor = tls_acceptor();
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let handle = smol::spawn(async move {
+                let (stream, _) = listener.accept().await.unwrap();
+                serve_hello(acceptor.accept(stream).await.unwrap()).await;
+            });
+
+            let tls_config = TlsConfig::new("localhost").trust_all();
+            hello(SmolStream::connect_tls(addr, &tls_config).await.unwrap()).await;
+            handle.await;
+        })
+    }
+
+    #[test]
+    fn tcp_config() {
+        smol::block_on(async {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let tcp_config = TcpConfig::default()
+                .with_keepalive(Duration::from_secs(60))
+                .with_nodelay(true);
+
+            let stream = SmolStream::connect_with_config(addr, None, &tcp_config)
+                .await
+                .unwrap();
+            match stream {
+                SmolStream::Tcp(tcp_stream) => {
+                    assert!(tcp_stream.nodelay().unwrap());
+                    assert!(SockRef::from(&tcp_stream).keepalive().unwrap());
+                }
+                other => panic!("expected TCP stream, got {:?}", other),
+            }
+        })
+    }
+}
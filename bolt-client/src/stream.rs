@@ -1,56 +1,115 @@
+#[cfg(unix)]
+use std::path::Path;
 use std::{
     fmt::Debug,
     io,
     pin::Pin,
-    sync::Arc,
     task::{Context, Poll},
 };
 
 use pin_project::pin_project;
+use socket2::SockRef;
+#[cfg(unix)]
+use tokio::net::UnixStream;
 use tokio::{
-    io::{AsyncRead, AsyncWrite, ReadBuf},
+    io::{AsyncRead, AsyncWrite, DuplexStream, ReadBuf},
     net::{TcpStream, ToSocketAddrs},
 };
-use tokio_rustls::{client::TlsStream, rustls::ClientConfig, webpki::DNSNameRef, TlsConnector};
+use tokio_rustls::{client::TlsStream, TlsConnector};
 
-use crate::error::*;
+use crate::{error::*, ConnectionUri, TcpConfig, TlsConfig};
 
-/// A convenient wrapper around a [`TcpStream`](tokio::net::TcpStream) or a
-/// [`TlsStream`](tokio_rustls::client::TlsStream).
+/// A convenient wrapper around a [`TcpStream`](tokio::net::TcpStream), a
+/// [`TlsStream`](tokio_rustls::client::TlsStream), a
+/// [`UnixStream`](tokio::net::UnixStream) (on Unix platforms), or an in-memory
+/// [`DuplexStream`](tokio::io::DuplexStream).
 #[cfg_attr(docsrs, doc(cfg(feature = "tokio-stream")))]
 #[pin_project(project = StreamProj)]
 #[derive(Debug)]
 pub enum Stream {
     Tcp(#[pin] TcpStream),
     SecureTcp(#[pin] Box<TlsStream<TcpStream>>),
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(unix)))]
+    Unix(#[pin] UnixStream),
+    Duplex(#[pin] DuplexStream),
 }
 
 impl Stream {
     /// Establish a connection with a remote socket. If a domain is provided, TLS
-    /// negotiation will be attempted.
+    /// negotiation will be attempted using the default [`TlsConfig`] for that domain.
     #[cfg_attr(docsrs, doc(cfg(feature = "tokio-stream")))]
     pub async fn connect(
         addr: impl ToSocketAddrs,
         domain: Option<impl AsRef<str>>,
     ) -> Result<Self> {
-        match domain {
-            Some(domain) => {
-                let mut config = ClientConfig::new();
-                config
-                    .root_store
-                    .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
-                let dns_name_ref = DNSNameRef::try_from_ascii_str(domain.as_ref())
-                    .map_err(|_| Error::InvalidDNSName(domain.as_ref().to_string()))?;
-                let stream = TcpStream::connect(addr).await?;
-                Ok(Stream::SecureTcp(Box::new(
-                    TlsConnector::from(Arc::new(config))
-                        .connect(dns_name_ref, stream)
-                        .await?,
-                )))
+        let tls_config = domain.map(|domain| TlsConfig::new(domain.as_ref()));
+        Self::connect_with_config(addr, tls_config.as_ref(), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with the server identified by a [`ConnectionUri`],
+    /// negotiating TLS if required by its scheme.
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-stream")))]
+    pub async fn connect_uri(uri: &ConnectionUri) -> Result<Self> {
+        let tls_config = uri.tls_config();
+        Self::connect_with_config(uri.addr(), tls_config.as_ref(), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with a remote socket and negotiate TLS using the given
+    /// [`TlsConfig`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-stream")))]
+    pub async fn connect_tls(addr: impl ToSocketAddrs, tls_config: &TlsConfig) -> Result<Self> {
+        Self::connect_with_config(addr, Some(tls_config), &TcpConfig::default()).await
+    }
+
+    /// Establish a connection with a remote socket using the given [`TcpConfig`]. If a
+    /// [`TlsConfig`] is provided, TLS negotiation will be attempted.
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-stream")))]
+    pub async fn connect_with_config(
+        addr: impl ToSocketAddrs,
+        tls_config: Option<&TlsConfig>,
+        tcp_config: &TcpConfig,
+    ) -> Result<Self> {
+        let tls = match tls_config {
+            Some(tls_config) => Some((
+                TlsConnector::from(tls_config.client_config()),
+                tls_config.dns_name_ref()?,
+            )),
+            None => None,
+        };
+        let connect = async {
+            let stream = TcpStream::connect(addr).await?;
+            tcp_config.configure(SockRef::from(&stream))?;
+            match tls {
+                Some((connector, dns_name_ref)) => Ok(Stream::SecureTcp(Box::new(
+                    connector.connect(dns_name_ref, stream).await?,
+                ))),
+                None => Ok(Stream::Tcp(stream)),
             }
-            None => Ok(Stream::Tcp(TcpStream::connect(addr).await?)),
+        };
+        match tcp_config.connect_timeout() {
+            Some(timeout) => tokio::time::timeout(timeout, connect)
+                .await
+                .map_err(|_| Error::Timeout)?,
+            None => connect.await,
         }
     }
+
+    /// Establish a connection with a Unix domain socket at the given path.
+    #[cfg(unix)]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "tokio-stream", unix))))]
+    pub async fn connect_unix(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Stream::Unix(UnixStream::connect(path).await?))
+    }
+
+    /// Create an in-memory stream, returning it along with the [`DuplexStream`] at the
+    /// other end, which can be served by an in-process server or stub. Each direction
+    /// buffers at most `max_buf_size` bytes before writes wait for the other end to read.
+    #[cfg_attr(docsrs, doc(cfg(feature = "tokio-stream")))]
+    pub fn duplex(max_buf_size: usize) -> (Self, DuplexStream) {
+        let (client, server) = tokio::io::duplex(max_buf_size);
+        (Stream::Duplex(client), server)
+    }
 }
 
 impl AsyncRead for Stream {
@@ -62,15 +121,25 @@ impl AsyncRead for Stream {
         match self.project() {
             StreamProj::Tcp(tcp_stream) => AsyncRead::poll_read(tcp_stream, cx, buf),
             StreamProj::SecureTcp(tls_stream) => AsyncRead::poll_read(tls_stream, cx, buf),
+            #[cfg(unix)]
+            StreamProj::Unix(unix_stream) => AsyncRead::poll_read(unix_stream, cx, buf),
+            StreamProj::Duplex(duplex_stream) => AsyncRead::poll_read(duplex_stream, cx, buf),
         }
     }
 }
 
 impl AsyncWrite for Stream {
-    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
         match self.project() {
             StreamProj::Tcp(tcp_stream) => AsyncWrite::poll_write(tcp_stream, cx, buf),
             StreamProj::SecureTcp(tls_stream) => AsyncWrite::poll_write(tls_stream, cx, buf),
+            #[cfg(unix)]
+            StreamProj::Unix(unix_stream) => AsyncWrite::poll_write(unix_stream, cx, buf),
+            StreamProj::Duplex(duplex_stream) => AsyncWrite::poll_write(duplex_stream, cx, buf),
         }
     }
 
@@ -78,6 +147,9 @@ impl AsyncWrite for Stream {
         match self.project() {
             StreamProj::Tcp(tcp_stream) => AsyncWrite::poll_flush(tcp_stream, cx),
             StreamProj::SecureTcp(tls_stream) => AsyncWrite::poll_flush(tls_stream, cx),
+            #[cfg(unix)]
+            StreamProj::Unix(unix_stream) => AsyncWrite::poll_flush(unix_stream, cx),
+            StreamProj::Duplex(duplex_stream) => AsyncWrite::poll_flush(duplex_stream, cx),
         }
     }
 
@@ -85,6 +157,356 @@ impl AsyncWrite for Stream {
         match self.project() {
             StreamProj::Tcp(tcp_stream) => AsyncWrite::poll_shutdown(tcp_stream, cx),
             StreamProj::SecureTcp(tls_stream) => AsyncWrite::poll_shutdown(tls_stream, cx),
+            #[cfg(unix)]
+            StreamProj::Unix(unix_stream) => AsyncWrite::poll_shutdown(unix_stream, cx),
+            StreamProj::Duplex(duplex_stream) => AsyncWrite::poll_shutdown(duplex_stream, cx),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        convert::{TryFrom, TryInto},
+        sync::Arc,
+        time::Duration,
+    };
+
+    use bolt_proto::{message::Success, version::*, Message};
+    use bytes::Bytes;
+    use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+    use tokio_rustls::{
+        rustls::{
+            self, AllowAnyAuthenticatedClient, NoClientAuth, PrivateKey, RootCertStore,
+            ServerConfig,
+        },
+        TlsAcceptor,
+    };
+    use tokio_util::compat::*;
+
+    use super::*;
+    use crate::Client;
+
+    struct Identity {
+        certificate_pem: String,
+        private_key_pem: String,
+        certificate_der: Vec<u8>,
+        private_key_der: Vec<u8>,
+    }
+
+    fn certificate_authority() -> Certificate {
+        let mut params = CertificateParams::new(vec![]);
+        params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        Certificate::from_params(params).unwrap()
+    }
+
+    fn identity(name: &str, issuer: Option<&Certificate>) -> Identity {
+        let certificate =
+            Certificate::from_params(CertificateParams::new(vec![name.into()])).unwrap();
+        let (certificate_pem, certificate_der) = match issuer {
+            Some(issuer) => (
+                certificate.serialize_pem_with_signer(issuer).unwrap(),
+                certificate.serialize_der_with_signer(issuer).unwrap(),
+            ),
+            None => (
+                certificate.serialize_pem().unwrap(),
+                certificate.serialize_der().unwrap(),
+            ),
+        };
+        Identity {
+            certificate_pem,
+            private_key_pem: certificate.serialize_private_key_pem(),
+            certificate_der,
+            private_key_der: certificate.serialize_private_key_der(),
+        }
+    }
+
+    // Start a TLS server that accepts a single connection and echoes back 4 bytes.
+    async fn echo_server(
+        server: &Identity,
+        client_ca: Option<&Certificate>,
+    ) -> (String, tokio::task::JoinHandle<()>) {
+        let mut config = match client_ca {
+            Some(client_ca) => {
+                let mut roots = RootCertStore::empty();
+                roots
+                    .add(&rustls::Certificate(client_ca.serialize_der().unwrap()))
+                    .unwrap();
+                ServerConfig::new(AllowAnyAuthenticatedClient::new(roots))
+            }
+            None => ServerConfig::new(NoClientAuth::new()),
+        };
+        config
+            .set_single_cert(
+                vec![rustls::Certificate(server.certificate_der.clone())],
+                PrivateKey(server.private_key_der.clone()),
+            )
+            .unwrap();
+        let acceptor = TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            if let Ok(mut stream) = acceptor.accept(stream).await {
+                let mut buf = [0; 4];
+                if stream.read_exact(&mut buf).await.is_ok() {
+                    let _ = stream.write_all(&buf).await;
+                    let _ = stream.flush().await;
+                }
+            }
+        });
+        (addr, handle)
+    }
+
+    async fn echo(addr: &str, tls_config: &TlsConfig) -> Result<()> {
+        let mut stream = Stream::connect_tls(addr, tls_config).await?;
+        stream.write_all(b"ping").await?;
+        stream.flush().await?;
+        let mut buf = [0; 4];
+        stream.read_exact(&mut buf).await?;
+        assert_eq!(&buf, b"ping");
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn custom_root_certificate() {
+        let ca = certificate_authority();
+        let server = identity("localhost", Some(&ca));
+        let tls_config = TlsConfig::new("localhost")
+            .with_root_certificates(ca.serialize_pem().unwrap())
+            .unwrap();
+
+        let (addr, handle) = echo_server(&server, None).await;
+        assert!(echo(&addr, &tls_config).await.is_ok());
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn default_roots_reject_private_ca() {
+        let ca = certificate_authority();
+        let server = identity("localhost", Some(&ca));
+
+        let (addr, handle) = echo_server(&server, None).await;
+        assert!(echo(&addr, &TlsConfig::new("localhost")).await.is_err());
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn server_name_mismatch() {
+        let ca = certificate_authority();
+        let server = identity("localhost", Some(&ca));
+        let tls_config = TlsConfig::new("neo4j.example.com")
+            .with_root_certificates(ca.serialize_pem().unwrap())
+            .unwrap();
+
+        let (addr, handle) = echo_server(&server, None).await;
+        assert!(echo(&addr, &tls_config).await.is_err());
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn client_certificate() {
+        let ca = certificate_authority();
+        let server = identity("localhost", Some(&ca));
+        let client = identity("client", Some(&ca));
+        let tls_config = TlsConfig::new("localhost")
+            .with_root_certificates(ca.serialize_pem().unwrap())
+            .unwrap();
+
+        let (addr, handle) = echo_server(&server, Some(&ca)).await;
+        let mutual_tls_config = tls_config
+            .clone()
+            .with_client_certificate(&client.certificate_pem, &client.private_key_pem)
+            .unwrap();
+        assert!(echo(&addr, &mutual_tls_config).await.is_ok());
+        handle.await.unwrap();
+
+        let (addr, handle) = echo_server(&server, Some(&ca)).await;
+        assert!(echo(&addr, &tls_config).await.is_err());
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn trust_on_first_use() {
+        let tls_config = TlsConfig::new("localhost").trust_on_first_use();
+        let server = identity("localhost", None);
+
+        let (addr, handle) = echo_server(&server, None).await;
+        assert!(echo(&addr, &tls_config).await.is_ok());
+        handle.await.unwrap();
+
+        // Clones share the certificate trusted on first use
+        let (addr, handle) = echo_server(&server, None).await;
+        assert!(echo(&addr, &tls_config.clone()).await.is_ok());
+        handle.await.unwrap();
+
+        let other_server = identity("localhost", None);
+        let (addr, handle) = echo_server(&other_server, None).await;
+        assert!(echo(&addr, &tls_config).await.is_err());
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn trust_all() {
+        let server = identity("localhost", None);
+
+        let (addr, handle) = echo_server(&server, None).await;
+        assert!(echo(&addr, &TlsConfig::new("localhost")).await.is_err());
+        handle.await.unwrap();
+
+        let (addr, handle) = echo_server(&server, None).await;
+        assert!(echo(&addr, &TlsConfig::new("localhost").trust_all())
+            .await
+            .is_ok());
+        handle.await.unwrap();
+    }
+
+    #[test]
+    fn invalid_pem() {
+        assert!(matches!(
+            TlsConfig::new("localhost").with_root_certificates("not a certificate"),
+            Err(Error::InvalidCertificate(_))
+        ));
+        let client = identity("client", None);
+        assert!(matches!(
+            TlsConfig::new("localhost")
+                .with_client_certificate(&client.certificate_pem, "not a key"),
+            Err(Error::InvalidPrivateKey)
+        ));
+    }
+
+    #[tokio::test]
+    async fn invalid_server_name() {
+        assert!(matches!(
+            Stream::connect_tls("127.0.0.1:7687", &TlsConfig::new("not a dns name!")).await,
+            Err(Error::InvalidDNSName(_))
+        ));
+    }
+
+    // Respond to a handshake with Bolt v4.1, and to the following message with SUCCESS.
+    async fn serve_hello(mut stream: impl AsyncRead + AsyncWrite + Unpin) {
+        let mut handshake = [0; 20];
+        stream.read_exact(&mut handshake).await.unwrap();
+        stream.write_all(&V4_1.to_be_bytes()).await.unwrap();
+        stream.flush().await.unwrap();
+
+        let message = Message::from_stream((&mut stream).compat()).await.unwrap();
+        assert!(matches!(message, Message::Hello(_)));
+        let chunks: Vec<Bytes> = Message::Success(Success::new(HashMap::new()))
+            .try_into()
+            .unwrap();
+        for chunk in chunks {
+            stream.write_all(&chunk).await.unwrap();
         }
+        stream.flush().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn duplex() {
+        let (stream, server) = Stream::duplex(1024);
+        let handle = tokio::spawn(serve_hello(server));
+
+        let mut client = Client::new(stream.compat(), &[V4_1, 0, 0, 0])
+            .await
+            .unwrap();
+        assert_eq!(client.version(), V4_1);
+        assert!(Success::try_from(client.hello(None).await.unwrap()).is_ok());
+        handle.await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn unix() {
+        let path = std::env::temp_dir().join(format!("bolt-client-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            serve_hello(stream).await;
+        });
+
+        let stream = Stream::connect_unix(&path).await.unwrap();
+        let mut client = Client::new(stream.compat(), &[V4_1, 0, 0, 0])
+            .await
+            .unwrap();
+        assert!(Success::try_from(client.hello(None).await.unwrap()).is_ok());
+        handle.await.unwrap();
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn tcp_config() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let tcp_config = TcpConfig::default()
+            .with_keepalive(Duration::from_secs(60))
+            .with_nodelay(true);
+
+        let stream = Stream::connect_with_config(addr, None, &tcp_config)
+            .await
+            .unwrap();
+        match stream {
+            Stream::Tcp(tcp_stream) => {
+                assert!(tcp_stream.nodelay().unwrap());
+                assert!(SockRef::from(&tcp_stream).keepalive().unwrap());
+            }
+            other => panic!("expected TCP stream, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn connect_timeout() {
+        // The server accepts TCP connections but never completes a TLS handshake
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            while stream.read(&mut [0; 1024]).await.unwrap_or(0) > 0 {}
+        });
+
+        let tcp_config = TcpConfig::default().with_connect_timeout(Duration::from_millis(50));
+        let tls_config = TlsConfig::new("localhost").trust_all();
+        assert!(matches!(
+            Stream::connect_with_config(addr, Some(&tls_config), &tcp_config).await,
+            Err(Error::Timeout)
+        ));
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn handshake_timeout() {
+        let (stream, _server) = Stream::duplex(1024);
+        assert!(matches!(
+            Client::new_with_timeout(stream.compat(), &[V4_1, 0, 0, 0], Duration::from_millis(50))
+                .await,
+            Err(Error::Timeout)
+        ));
+    }
+
+    #[tokio::test]
+    async fn read_timeout() {
+        let (stream, mut server) = Stream::duplex(1024);
+        let handle = tokio::spawn(async move {
+            let mut handshake = [0; 20];
+            server.read_exact(&mut handshake).await.unwrap();
+            server.write_all(&V4_1.to_be_bytes()).await.unwrap();
+            // Never respond to any messages
+            while server.read(&mut [0; 1024]).await.unwrap() > 0 {}
+        });
+
+        let mut client = Client::new(stream.compat(), &[V4_1, 0, 0, 0])
+            .await
+            .unwrap();
+        client.set_read_timeout(Some(Duration::from_millis(50)));
+        assert!(!client.is_broken());
+        assert!(matches!(client.hello(None).await, Err(Error::Timeout)));
+        assert!(client.is_broken());
+        drop(client);
+        handle.await.unwrap();
     }
 }
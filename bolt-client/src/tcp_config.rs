@@ -0,0 +1,54 @@
+use std::{io, time::Duration};
+
+use socket2::{SockRef, TcpKeepalive};
+
+/// Socket settings used when opening a TCP connection with any of the provided streams.
+#[derive(Debug, Clone, Default)]
+pub struct TcpConfig {
+    connect_timeout: Option<Duration>,
+    keepalive: Option<Duration>,
+    nodelay: bool,
+}
+
+impl TcpConfig {
+    /// Fail with [`Error::Timeout`](crate::error::Error::Timeout) if the connection,
+    /// including TLS negotiation, is not established within the given duration.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Enable TCP keepalive, sending probes after the connection has been idle for the
+    /// given duration.
+    pub fn with_keepalive(mut self, idle: Duration) -> Self {
+        self.keepalive = Some(idle);
+        self
+    }
+
+    /// Set the `TCP_NODELAY` option, disabling Nagle's algorithm.
+    pub fn with_nodelay(mut self, nodelay: bool) -> Self {
+        self.nodelay = nodelay;
+        self
+    }
+
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    pub fn keepalive(&self) -> Option<Duration> {
+        self.keepalive
+    }
+
+    pub fn nodelay(&self) -> bool {
+        self.nodelay
+    }
+
+    // Apply the socket options to a newly connected socket
+    pub(crate) fn configure(&self, socket: SockRef<'_>) -> io::Result<()> {
+        socket.set_nodelay(self.nodelay)?;
+        if let Some(keepalive) = self.keepalive {
+            socket.set_tcp_keepalive(&TcpKeepalive::new().with_time(keepalive))?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,196 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    io::BufReader,
+    sync::{Arc, Mutex},
+};
+
+use rustls::{
+    internal::pemfile, Certificate, ClientConfig, RootCertStore, ServerCertVerified,
+    ServerCertVerifier, TLSError,
+};
+use webpki::DNSNameRef;
+
+use crate::error::*;
+
+/// Settings used to negotiate TLS when opening a secure [`Stream`](crate::Stream).
+///
+/// By default, the server's certificate chain is verified against the Mozilla root
+/// certificates from [`webpki-roots`](webpki_roots) and the provided server name is
+/// sent via SNI and checked against the certificate. Clones share the same underlying
+/// configuration, including any certificate remembered by
+/// [`trust_on_first_use`](TlsConfig::trust_on_first_use).
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+        feature = "tokio-stream",
+        feature = "async-std-stream",
+        feature = "smol-stream",
+        feature = "blocking"
+    )))
+)]
+#[derive(Clone)]
+pub struct TlsConfig {
+    server_name: String,
+    client_config: Arc<ClientConfig>,
+}
+
+impl TlsConfig {
+    /// Create a new TLS configuration that verifies the server using the Mozilla root
+    /// certificates. The server name is sent via SNI and must match the server's
+    /// certificate, independently of the address used to connect.
+    pub fn new(server_name: impl Into<String>) -> Self {
+        let mut client_config = ClientConfig::new();
+        client_config
+            .root_store
+            .add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+        Self {
+            server_name: server_name.into(),
+            client_config: Arc::new(client_config),
+        }
+    }
+
+    /// Trust only the PEM-encoded root certificates provided, instead of the Mozilla
+    /// root certificates. Useful for servers whose certificates are issued by a private
+    /// certificate authority.
+    pub fn with_root_certificates(mut self, pem: impl AsRef<[u8]>) -> Result<Self> {
+        let mut root_store = RootCertStore::empty();
+        for certificate in read_certificates(pem.as_ref())? {
+            root_store
+                .add(&certificate)
+                .map_err(|err| Error::InvalidCertificate(err.to_string()))?;
+        }
+        Arc::make_mut(&mut self.client_config).root_store = root_store;
+        Ok(self)
+    }
+
+    /// Present the given PEM-encoded certificate chain and private key to the server,
+    /// for servers that require client authentication (mutual TLS). The private key may
+    /// be in PKCS#8 or PKCS#1 (RSA) format.
+    pub fn with_client_certificate(
+        mut self,
+        certificate_chain_pem: impl AsRef<[u8]>,
+        private_key_pem: impl AsRef<[u8]>,
+    ) -> Result<Self> {
+        let certificate_chain = read_certificates(certificate_chain_pem.as_ref())?;
+        let private_key_pem = private_key_pem.as_ref();
+        let private_key = pemfile::pkcs8_private_keys(&mut BufReader::new(private_key_pem))
+            .ok()
+            .filter(|keys| !keys.is_empty())
+            .or_else(|| pemfile::rsa_private_keys(&mut BufReader::new(private_key_pem)).ok())
+            .and_then(|keys| keys.into_iter().next())
+            .ok_or(Error::InvalidPrivateKey)?;
+        Arc::make_mut(&mut self.client_config)
+            .set_single_client_cert(certificate_chain, private_key)
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        Ok(self)
+    }
+
+    /// Accept whichever certificate the server presents on the first connection made
+    /// with this configuration (or any of its clones), and reject connections to servers
+    /// presenting a different certificate afterwards. The certificate chain itself is not
+    /// verified.
+    ///
+    /// This is intended for development against servers using self-signed certificates.
+    pub fn trust_on_first_use(mut self) -> Self {
+        Arc::make_mut(&mut self.client_config)
+            .dangerous()
+            .set_certificate_verifier(Arc::new(TrustOnFirstUse::default()));
+        self
+    }
+
+    /// Accept any certificate the server presents, without verification.
+    ///
+    /// This offers no protection against man-in-the-middle attacks, and should only be
+    /// used for development.
+    pub fn trust_all(mut self) -> Self {
+        Arc::make_mut(&mut self.client_config)
+            .dangerous()
+            .set_certificate_verifier(Arc::new(TrustAll));
+        self
+    }
+
+    /// Create a configuration for another server name, sharing the certificates and
+    /// verification settings of this one. Useful for connecting to the other members of
+    /// a cluster.
+    pub fn for_server_name(&self, server_name: impl Into<String>) -> Self {
+        Self {
+            server_name: server_name.into(),
+            client_config: Arc::clone(&self.client_config),
+        }
+    }
+
+    /// Get the server name sent via SNI and used to verify the server's certificate.
+    pub fn server_name(&self) -> &str {
+        &self.server_name
+    }
+
+    pub(crate) fn dns_name_ref(&self) -> Result<DNSNameRef<'_>> {
+        DNSNameRef::try_from_ascii_str(&self.server_name)
+            .map_err(|_| Error::InvalidDNSName(self.server_name.clone()))
+    }
+
+    pub(crate) fn client_config(&self) -> Arc<ClientConfig> {
+        Arc::clone(&self.client_config)
+    }
+}
+
+impl Debug for TlsConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsConfig")
+            .field("server_name", &self.server_name)
+            .finish_non_exhaustive()
+    }
+}
+
+fn read_certificates(pem: &[u8]) -> Result<Vec<Certificate>> {
+    match pemfile::certs(&mut BufReader::new(pem)) {
+        Ok(certificates) if !certificates.is_empty() => Ok(certificates),
+        _ => Err(Error::InvalidCertificate(String::from(
+            "no PEM-encoded certificates found",
+        ))),
+    }
+}
+
+#[derive(Default)]
+struct TrustOnFirstUse {
+    known_certificate: Mutex<Option<Certificate>>,
+}
+
+impl ServerCertVerifier for TrustOnFirstUse {
+    fn verify_server_cert(
+        &self,
+        _roots: &RootCertStore,
+        presented_certs: &[Certificate],
+        _dns_name: DNSNameRef<'_>,
+        _ocsp_response: &[u8],
+    ) -> std::result::Result<ServerCertVerified, TLSError> {
+        let presented = presented_certs
+            .first()
+            .ok_or(TLSError::NoCertificatesPresented)?;
+        let mut known_certificate = self.known_certificate.lock().unwrap();
+        match known_certificate.as_ref() {
+            Some(known) if known != presented => Err(TLSError::General(String::from(
+                "server certificate does not match the certificate trusted on first use",
+            ))),
+            Some(_) => Ok(ServerCertVerified::assertion()),
+            None => {
+                *known_certificate = Some(presented.clone());
+                Ok(ServerCertVerified::assertion())
+            }
+        }
+    }
+}
+
+struct TrustAll;
+
+impl ServerCertVerifier for TrustAll {
+    fn verify_server_cert(
+        &self,
+        _roots: &RootCertStore,
+        _presented_certs: &[Certificate],
+        _dns_name: DNSNameRef<'_>,
+        _ocsp_response: &[u8],
+    ) -> std::result::Result<ServerCertVerified, TLSError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
@@ -97,10 +97,17 @@ pub fn serialize_derive(input: TokenStream) -> TokenStream {
     .into()
 }
 
-#[proc_macro_derive(Deserialize)]
+// Structs marked with #[validate] must provide a `validate(&self) -> Result<()>` method,
+// which is called to reject invalid field values after deserialization.
+#[proc_macro_derive(Deserialize, attributes(validate))]
 pub fn deserialize_derive(input: TokenStream) -> TokenStream {
-    let ast = &syn::parse(input).unwrap();
+    let ast: &syn::DeriveInput = &syn::parse(input).unwrap();
     let (name, type_args, where_clause, fields) = get_struct_info(ast);
+    let validate = if ast.attrs.iter().any(|attr| attr.path.is_ident("validate")) {
+        quote!(value.validate()?;)
+    } else {
+        quote!()
+    };
 
     let deserialize_fields =
         fields
@@ -122,9 +129,11 @@ pub fn deserialize_derive(input: TokenStream) -> TokenStream {
 
             fn try_from(remaining_bytes_arc: ::std::sync::Arc<::std::sync::Mutex<::bytes::Bytes>>) -> crate::error::Result<Self> {
                 use ::std::convert::{TryFrom, TryInto};
-                Ok(#name {
+                let value = #name {
                     #(#deserialize_fields)*
-                })
+                };
+                #validate
+                Ok(value)
             }
         }
     ).into()
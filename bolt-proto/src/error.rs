@@ -24,6 +24,18 @@ pub enum ConversionError {
     FromValue(Value),
     #[error("invalid conversion from message {0:?}")]
     FromMessage(Message),
+    #[error("unknown time zone: {0}")]
+    UnknownTimeZone(String),
+    #[error("temporal value out of range: {0:?}")]
+    TemporalOutOfRange(Value),
+    #[error("invalid ISO-8601 duration: {0}")]
+    InvalidDuration(String),
+    #[error("unknown coordinate reference system: SRID {0}")]
+    UnknownCrs(i32),
+    #[error("invalid point: {0:?}")]
+    InvalidPoint(Value),
+    #[error("invalid path: {0:?}")]
+    InvalidPath(Value),
 }
 
 #[derive(Debug, Error)]
@@ -0,0 +1,304 @@
+//! Collecting the nodes and relationships returned by queries into a graph.
+//!
+//! Queries that return paths or patterns tend to return the same nodes and relationships
+//! many times over, spread across records. A [`Graph`] walks returned values, including
+//! those nested in lists, maps and paths, and keeps a single copy of each node and
+//! relationship, keyed by its identity.
+//!
+//! With the `petgraph` feature, a [`Graph`] can be converted into a
+//! [`petgraph::Graph`](https://docs.rs/petgraph/*/petgraph/graph/struct.Graph.html) to run graph
+//! algorithms on it.
+//!
+//! # Example
+//! ```
+//! use std::collections::HashMap;
+//!
+//! use bolt_proto::{graph::Graph, value::*, Value};
+//!
+//! let alice = Node::new(1, vec![String::from("Person")], HashMap::<String, Value>::new());
+//! let bob = Node::new(2, vec![String::from("Person")], HashMap::<String, Value>::new());
+//! let knows = UnboundRelationship::new(3, String::from("KNOWS"), HashMap::<String, Value>::new());
+//! // The result of `MATCH p = (:Person)-[:KNOWS]->(:Person) RETURN p, nodes(p)`
+//! let fields = vec![
+//!     Value::from(Path::new(vec![alice.clone(), bob.clone()], vec![knows], vec![1, 1])),
+//!     Value::from(vec![alice, bob]),
+//! ];
+//!
+//! let mut graph = Graph::new();
+//! graph.extend(fields);
+//! assert_eq!(graph.node_count(), 2);
+//! assert_eq!(graph.relationship(3).unwrap().start_node_identity(), 1);
+//! assert_eq!(graph.nodes_with_label("Person").count(), 2);
+//! ```
+use std::{collections::HashMap, iter::FromIterator};
+
+use crate::value::{Node, Relationship};
+use crate::Value;
+
+/// Nodes and relationships collected from query results, without duplicates.
+///
+/// Nodes and relationships are kept in the order they were first seen. Relationships found
+/// on their own don't need their start and end nodes to be in the graph.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Graph {
+    nodes: Vec<Node>,
+    node_indices: HashMap<i64, usize>,
+    relationships: Vec<Relationship>,
+    relationship_indices: HashMap<i64, usize>,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the nodes and relationships in the given value, including any found in lists, maps
+    /// or paths. Unbound relationships found on their own are ignored, since their start and end
+    /// nodes are unknown.
+    pub fn insert(&mut self, value: &Value) {
+        match value {
+            Value::Node(node) => self.insert_node(node),
+            Value::Relationship(relationship) => self.insert_relationship(relationship.clone()),
+            Value::Path(path) => {
+                path.nodes().iter().for_each(|node| self.insert_node(node));
+                for (_, relationship, _) in path.segments() {
+                    self.insert_relationship(relationship);
+                }
+            }
+            Value::List(list) => list.value.iter().for_each(|value| self.insert(value)),
+            Value::Map(map) => map.value.values().for_each(|value| self.insert(value)),
+            _ => {}
+        }
+    }
+
+    fn insert_node(&mut self, node: &Node) {
+        if !self.node_indices.contains_key(&node.node_identity()) {
+            self.node_indices
+                .insert(node.node_identity(), self.nodes.len());
+            self.nodes.push(node.clone());
+        }
+    }
+
+    fn insert_relationship(&mut self, relationship: Relationship) {
+        if !self
+            .relationship_indices
+            .contains_key(&relationship.rel_identity())
+        {
+            self.relationship_indices
+                .insert(relationship.rel_identity(), self.relationships.len());
+            self.relationships.push(relationship);
+        }
+    }
+
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    pub fn relationships(&self) -> &[Relationship] {
+        &self.relationships
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn relationship_count(&self) -> usize {
+        self.relationships.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty() && self.relationships.is_empty()
+    }
+
+    /// Get the node with the given identity, if it was collected.
+    pub fn node(&self, node_identity: i64) -> Option<&Node> {
+        self.node_indices
+            .get(&node_identity)
+            .map(|&index| &self.nodes[index])
+    }
+
+    /// Get the relationship with the given identity, if it was collected.
+    pub fn relationship(&self, rel_identity: i64) -> Option<&Relationship> {
+        self.relationship_indices
+            .get(&rel_identity)
+            .map(|&index| &self.relationships[index])
+    }
+
+    pub fn nodes_with_label<'a>(&'a self, label: &'a str) -> impl Iterator<Item = &'a Node> {
+        self.nodes
+            .iter()
+            .filter(move |node| node.labels().iter().any(|l| l == label))
+    }
+
+    pub fn relationships_with_type<'a>(
+        &'a self,
+        rel_type: &'a str,
+    ) -> impl Iterator<Item = &'a Relationship> {
+        self.relationships
+            .iter()
+            .filter(move |relationship| relationship.rel_type() == rel_type)
+    }
+
+    /// Get the relationships starting at the node with the given identity.
+    pub fn outgoing(&self, node_identity: i64) -> impl Iterator<Item = &Relationship> {
+        self.relationships
+            .iter()
+            .filter(move |relationship| relationship.start_node_identity() == node_identity)
+    }
+
+    /// Get the relationships ending at the node with the given identity.
+    pub fn incoming(&self, node_identity: i64) -> impl Iterator<Item = &Relationship> {
+        self.relationships
+            .iter()
+            .filter(move |relationship| relationship.end_node_identity() == node_identity)
+    }
+}
+
+impl Extend<Value> for Graph {
+    fn extend<T: IntoIterator<Item = Value>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|value| self.insert(&value));
+    }
+}
+
+impl<'a> Extend<&'a Value> for Graph {
+    fn extend<T: IntoIterator<Item = &'a Value>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|value| self.insert(value));
+    }
+}
+
+impl FromIterator<Value> for Graph {
+    fn from_iter<T: IntoIterator<Item = Value>>(iter: T) -> Self {
+        let mut graph = Self::new();
+        graph.extend(iter);
+        graph
+    }
+}
+
+/// Relationships whose start or end node was not collected are left out, since petgraph
+/// requires both ends of an edge to be in the graph.
+#[cfg(feature = "petgraph")]
+impl From<Graph> for petgraph::Graph<Node, Relationship> {
+    fn from(graph: Graph) -> Self {
+        use petgraph::graph::NodeIndex;
+
+        let mut petgraph = Self::with_capacity(graph.nodes.len(), graph.relationships.len());
+        // Nodes keep their order, so their indices in the graph are their indices in petgraph
+        for node in graph.nodes {
+            petgraph.add_node(node);
+        }
+        for relationship in graph.relationships {
+            let start = graph.node_indices.get(&relationship.start_node_identity());
+            let end = graph.node_indices.get(&relationship.end_node_identity());
+            if let (Some(&start), Some(&end)) = (start, end) {
+                petgraph.add_edge(NodeIndex::new(start), NodeIndex::new(end), relationship);
+            }
+        }
+        petgraph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::FromIterator;
+
+    use crate::value::{Path, UnboundRelationship};
+
+    use super::*;
+
+    fn node(node_identity: i64, label: &str) -> Node {
+        Node::new(
+            node_identity,
+            vec![label.to_string()],
+            HashMap::<String, Value>::new(),
+        )
+    }
+
+    fn unbound(rel_identity: i64, rel_type: &str) -> UnboundRelationship {
+        UnboundRelationship::new(
+            rel_identity,
+            rel_type.to_string(),
+            HashMap::<String, Value>::new(),
+        )
+    }
+
+    // (1:Person)-[10:KNOWS]->(2:Person)-[11:LIKES]->(3:Movie), the same KNOWS relationship
+    // traversed backwards, and a relationship to a node that was not returned
+    fn values() -> Vec<Value> {
+        vec![
+            Value::from(Path::new(
+                vec![node(1, "Person"), node(2, "Person"), node(3, "Movie")],
+                vec![unbound(10, "KNOWS"), unbound(11, "LIKES")],
+                vec![1, 1, 2, 2],
+            )),
+            Value::from(HashMap::<String, Value>::from_iter(vec![(
+                String::from("friend"),
+                Value::from(node(2, "Person")),
+            )])),
+            Value::from(Path::new(
+                vec![node(2, "Person"), node(1, "Person")],
+                vec![unbound(10, "KNOWS")],
+                vec![-1, 1],
+            )),
+            Value::from(vec![
+                Value::from(Relationship::new(
+                    12,
+                    3,
+                    4,
+                    String::from("SEQUEL"),
+                    HashMap::<String, Value>::new(),
+                )),
+                Value::from(unbound(13, "IGNORED")),
+            ]),
+        ]
+    }
+
+    #[test]
+    fn collect() {
+        let graph: Graph = values().into_iter().collect();
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.relationship_count(), 3);
+        assert_eq!(
+            graph
+                .nodes()
+                .iter()
+                .map(Node::node_identity)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+
+        let knows = graph.relationship(10).unwrap();
+        assert_eq!(
+            (knows.start_node_identity(), knows.end_node_identity()),
+            (1, 2)
+        );
+        assert_eq!(graph.node(3), Some(&node(3, "Movie")));
+        assert_eq!(graph.node(4), None);
+        assert_eq!(graph.relationship(13), None);
+
+        assert_eq!(graph.nodes_with_label("Person").count(), 2);
+        assert_eq!(graph.relationships_with_type("LIKES").count(), 1);
+        assert_eq!(graph.outgoing(2).count(), 1);
+        assert_eq!(graph.incoming(2).count(), 1);
+    }
+
+    #[cfg(feature = "petgraph")]
+    #[test]
+    fn petgraph() {
+        let graph: petgraph::Graph<Node, Relationship> =
+            values().into_iter().collect::<Graph>().into();
+        // The relationship between nodes 3 and 4 is left out, since node 4 was not returned
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 2);
+
+        let start = graph
+            .node_indices()
+            .find(|&index| graph[index].node_identity() == 1)
+            .unwrap();
+        let mut bfs = petgraph::visit::Bfs::new(&graph, start);
+        let mut reachable = Vec::new();
+        while let Some(index) = bfs.next(&graph) {
+            reachable.push(graph[index].node_identity());
+        }
+        assert_eq!(reachable, vec![1, 2, 3]);
+    }
+}
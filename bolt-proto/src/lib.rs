@@ -5,6 +5,7 @@ pub use serialization::{Marker, Signature};
 pub use value::Value;
 
 pub mod error;
+pub mod graph;
 pub mod message;
 mod serialization;
 pub mod value;
@@ -56,7 +57,7 @@ macro_rules! impl_empty_message_tests {
             use ::std::convert::TryFrom;
             use ::std::sync::{Arc, Mutex};
 
-            use crate::serialization::*;
+            use $crate::serialization::*;
 
             use super::*;
 
@@ -17,6 +17,8 @@ pub use goodbye::Goodbye;
 pub use hello::Hello;
 pub use ignored::Ignored;
 pub use init::Init;
+pub use logoff::Logoff;
+pub use logon::Logon;
 pub use pull::Pull;
 pub use pull_all::PullAll;
 pub use record::Record;
@@ -25,9 +27,11 @@ pub use rollback::Rollback;
 pub use run::Run;
 pub use run_with_metadata::RunWithMetadata;
 pub use success::Success;
+pub use telemetry::Telemetry;
 
 use crate::error::*;
 use crate::serialization::*;
+use crate::value::convert_date_times;
 
 pub(crate) mod ack_failure;
 pub(crate) mod begin;
@@ -39,6 +43,8 @@ pub(crate) mod goodbye;
 pub(crate) mod hello;
 pub(crate) mod ignored;
 pub(crate) mod init;
+pub(crate) mod logoff;
+pub(crate) mod logon;
 pub(crate) mod pull;
 pub(crate) mod pull_all;
 pub(crate) mod record;
@@ -47,6 +53,7 @@ pub(crate) mod rollback;
 pub(crate) mod run;
 pub(crate) mod run_with_metadata;
 pub(crate) mod success;
+pub(crate) mod telemetry;
 
 // This is the default maximum chunk size in the official driver, minus header length
 const CHUNK_SIZE: usize = 16383 - mem::size_of::<u16>();
@@ -76,9 +83,75 @@ pub enum Message {
     // V4+-compatible message types
     Discard(Discard),
     Pull(Pull),
+
+    // V5.1+-compatible message types
+    Logon(Logon),
+    Logoff,
+
+    // V5.4+-compatible message types
+    Telemetry(Telemetry),
 }
 
 impl Message {
+    /// Convert the date-times in this message's fields, parameters and metadata to the
+    /// encoding understood by the server. See
+    /// [`Value::convert_date_times`](crate::Value::convert_date_times).
+    pub fn convert_date_times(self, utc: bool) -> Result<Message> {
+        match self {
+            Message::Init(mut init) => {
+                init.auth_token = convert_date_times(init.auth_token, utc)?;
+                Ok(Message::Init(init))
+            }
+            Message::Run(mut run) => {
+                run.parameters = convert_date_times(run.parameters, utc)?;
+                Ok(Message::Run(run))
+            }
+            Message::Record(mut record) => {
+                record.fields = record
+                    .fields
+                    .into_iter()
+                    .map(|value| value.convert_date_times(utc))
+                    .collect::<Result<_>>()?;
+                Ok(Message::Record(record))
+            }
+            Message::Success(mut success) => {
+                success.metadata = convert_date_times(success.metadata, utc)?;
+                Ok(Message::Success(success))
+            }
+            Message::Failure(mut failure) => {
+                failure.metadata = convert_date_times(failure.metadata, utc)?;
+                Ok(Message::Failure(failure))
+            }
+            Message::Hello(mut hello) => {
+                hello.metadata = convert_date_times(hello.metadata, utc)?;
+                Ok(Message::Hello(hello))
+            }
+            Message::RunWithMetadata(mut run_with_metadata) => {
+                run_with_metadata.parameters =
+                    convert_date_times(run_with_metadata.parameters, utc)?;
+                run_with_metadata.metadata = convert_date_times(run_with_metadata.metadata, utc)?;
+                Ok(Message::RunWithMetadata(run_with_metadata))
+            }
+            Message::Begin(mut begin) => {
+                begin.metadata = convert_date_times(begin.metadata, utc)?;
+                Ok(Message::Begin(begin))
+            }
+            Message::Discard(mut discard) => {
+                discard.metadata = convert_date_times(discard.metadata, utc)?;
+                Ok(Message::Discard(discard))
+            }
+            Message::Pull(mut pull) => {
+                pull.metadata = convert_date_times(pull.metadata, utc)?;
+                Ok(Message::Pull(pull))
+            }
+            Message::Logon(mut logon) => {
+                logon.metadata = convert_date_times(logon.metadata, utc)?;
+                Ok(Message::Logon(logon))
+            }
+            other => Ok(other),
+        }
+    }
+
     pub async fn from_stream(mut stream: impl AsyncRead + Unpin) -> Result<Message> {
         let mut bytes = BytesMut::new();
         let mut chunk_len = 0;
@@ -97,7 +170,7 @@ impl Message {
             stream.read_exact(&mut u16_bytes).await?;
             chunk_len = u16::from_be_bytes(u16_bytes);
         }
-        Message::try_from(Arc::new(Mutex::new(bytes.freeze())))
+        Message::try_from_bytes(bytes.freeze())
     }
 }
 
@@ -122,6 +195,9 @@ impl Marker for Message {
             Message::Rollback => Rollback.get_marker(),
             Message::Discard(discard) => discard.get_marker(),
             Message::Pull(pull) => pull.get_marker(),
+            Message::Logon(logon) => logon.get_marker(),
+            Message::Logoff => Logoff.get_marker(),
+            Message::Telemetry(telemetry) => telemetry.get_marker(),
         }
     }
 }
@@ -147,6 +223,9 @@ impl Signature for Message {
             Message::Rollback => Rollback.get_signature(),
             Message::Discard(discard) => discard.get_signature(),
             Message::Pull(pull) => pull.get_signature(),
+            Message::Logon(logon) => logon.get_signature(),
+            Message::Logoff => Logoff.get_signature(),
+            Message::Telemetry(telemetry) => telemetry.get_signature(),
         }
     }
 }
@@ -176,6 +255,9 @@ impl TryInto<Bytes> for Message {
             Message::Rollback => Rollback.try_into(),
             Message::Discard(discard) => discard.try_into(),
             Message::Pull(pull) => pull.try_into(),
+            Message::Logon(logon) => logon.try_into(),
+            Message::Logoff => Logoff.try_into(),
+            Message::Telemetry(telemetry) => telemetry.try_into(),
         }
     }
 }
@@ -238,6 +320,9 @@ impl TryFrom<Arc<Mutex<Bytes>>> for Message {
                 begin::SIGNATURE => Ok(Message::Begin(Begin::try_from(input_arc)?)),
                 commit::SIGNATURE => Ok(Message::Commit),
                 rollback::SIGNATURE => Ok(Message::Rollback),
+                logon::SIGNATURE => Ok(Message::Logon(Logon::try_from(input_arc)?)),
+                logoff::SIGNATURE => Ok(Message::Logoff),
+                telemetry::SIGNATURE => Ok(Message::Telemetry(Telemetry::try_from(input_arc)?)),
                 _ => Err(DeserializationError::InvalidSignatureByte(signature).into()),
             }
         })
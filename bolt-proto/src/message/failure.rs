@@ -15,7 +15,7 @@ pub struct Failure {
 impl_message_with_metadata!(Failure);
 impl_try_from_message!(Failure, Failure);
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use std::convert::TryFrom;
     use std::iter::FromIterator;
@@ -32,7 +32,7 @@ mod tests {
     fn new_msg() -> Failure {
         Failure::new(HashMap::from_iter(vec![(
             "failing_since".to_string(),
-            Value::from(NaiveDate::from_ymd(1985, 6, 26)),
+            Value::from(NaiveDate::from_ymd_opt(1985, 6, 26).unwrap()),
         )]))
     }
 
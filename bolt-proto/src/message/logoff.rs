@@ -0,0 +1,10 @@
+use bolt_proto_derive::*;
+
+pub(crate) const MARKER: u8 = 0xB0;
+pub(crate) const SIGNATURE: u8 = 0x6B;
+
+#[derive(Debug, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+pub struct Logoff;
+
+#[cfg(test)]
+crate::impl_empty_message_tests!(Logoff);
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use bolt_proto_derive::*;
+
+use crate::{impl_message_with_metadata, impl_try_from_message, Value};
+
+pub(crate) const MARKER: u8 = 0xB1;
+pub(crate) const SIGNATURE: u8 = 0x6A;
+
+#[derive(Debug, Clone, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+pub struct Logon {
+    pub(crate) metadata: HashMap<String, Value>,
+}
+
+impl_message_with_metadata!(Logon);
+impl_try_from_message!(Logon, Logon);
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::iter::FromIterator;
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+
+    use crate::serialization::*;
+    use crate::value::*;
+
+    use super::*;
+
+    fn new_msg() -> Logon {
+        Logon::new(HashMap::from_iter(vec![(
+            "scheme".to_string(),
+            Value::from("none"),
+        )]))
+    }
+
+    #[test]
+    fn get_marker() {
+        assert_eq!(new_msg().get_marker().unwrap(), MARKER);
+    }
+
+    #[test]
+    fn get_signature() {
+        assert_eq!(new_msg().get_signature(), SIGNATURE);
+    }
+
+    #[test]
+    fn try_into_bytes() {
+        let msg = new_msg();
+        assert_eq!(
+            msg.try_into_bytes().unwrap(),
+            Bytes::from_static(&[
+                MARKER,
+                SIGNATURE,
+                map::MARKER_TINY | 1,
+                string::MARKER_TINY | 6,
+                b's',
+                b'c',
+                b'h',
+                b'e',
+                b'm',
+                b'e',
+                string::MARKER_TINY | 4,
+                b'n',
+                b'o',
+                b'n',
+                b'e',
+            ])
+        );
+    }
+
+    #[test]
+    fn try_from_bytes() {
+        let msg = new_msg();
+        let msg_bytes = &[
+            map::MARKER_TINY | 1,
+            string::MARKER_TINY | 6,
+            b's',
+            b'c',
+            b'h',
+            b'e',
+            b'm',
+            b'e',
+            string::MARKER_TINY | 4,
+            b'n',
+            b'o',
+            b'n',
+            b'e',
+        ];
+        assert_eq!(
+            Logon::try_from(Arc::new(Mutex::new(Bytes::from_static(msg_bytes)))).unwrap(),
+            msg
+        );
+    }
+}
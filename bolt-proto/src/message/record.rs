@@ -18,6 +18,10 @@ impl Record {
     pub fn fields(&self) -> &[Value] {
         &self.fields
     }
+
+    pub fn into_fields(self) -> Vec<Value> {
+        self.fields
+    }
 }
 
 impl_try_from_message!(Record, Record);
@@ -0,0 +1,77 @@
+use bolt_proto_derive::*;
+
+use crate::impl_try_from_message;
+
+pub(crate) const MARKER: u8 = 0xB1;
+pub(crate) const SIGNATURE: u8 = 0x54;
+
+#[derive(Debug, Clone, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+pub struct Telemetry {
+    pub(crate) api: i64,
+}
+
+impl Telemetry {
+    /// Transactions run through a driver's managed transaction functions.
+    pub const MANAGED_TRANSACTION: i64 = 0;
+    /// Transactions started and committed or rolled back explicitly.
+    pub const UNMANAGED_TRANSACTION: i64 = 1;
+    /// Auto-commit queries.
+    pub const AUTO_COMMIT_TRANSACTION: i64 = 2;
+    /// Queries run through a driver's single-call query API.
+    pub const EXECUTE_QUERY: i64 = 3;
+
+    pub fn new(api: i64) -> Self {
+        Self { api }
+    }
+
+    pub fn api(&self) -> i64 {
+        self.api
+    }
+}
+
+impl_try_from_message!(Telemetry, Telemetry);
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+
+    use crate::serialization::*;
+
+    use super::*;
+
+    fn new_msg() -> Telemetry {
+        Telemetry::new(Telemetry::AUTO_COMMIT_TRANSACTION)
+    }
+
+    #[test]
+    fn get_marker() {
+        assert_eq!(new_msg().get_marker().unwrap(), MARKER);
+    }
+
+    #[test]
+    fn get_signature() {
+        assert_eq!(new_msg().get_signature(), SIGNATURE);
+    }
+
+    #[test]
+    fn try_into_bytes() {
+        let msg = new_msg();
+        assert_eq!(
+            msg.try_into_bytes().unwrap(),
+            Bytes::from_static(&[MARKER, SIGNATURE, 0x02])
+        );
+    }
+
+    #[test]
+    fn try_from_bytes() {
+        let msg = new_msg();
+        let msg_bytes = &[0x02];
+        assert_eq!(
+            Telemetry::try_from(Arc::new(Mutex::new(Bytes::from_static(msg_bytes)))).unwrap(),
+            msg
+        );
+    }
+}
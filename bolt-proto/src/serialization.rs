@@ -1,9 +1,10 @@
 use std::convert::{TryFrom, TryInto};
 use std::sync::{Arc, Mutex};
 
-use bytes::{Buf, Bytes};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
 
 use crate::error::*;
+use crate::Value;
 
 pub trait Serialize: TryInto<Bytes, Error = Error> {
     fn try_into_bytes(self) -> Result<Bytes> {
@@ -11,7 +12,11 @@ pub trait Serialize: TryInto<Bytes, Error = Error> {
     }
 }
 
-pub trait Deserialize: TryFrom<Arc<Mutex<Bytes>>, Error = Error> {}
+pub trait Deserialize: TryFrom<Arc<Mutex<Bytes>>, Error = Error> {
+    fn try_from_bytes(bytes: Bytes) -> Result<Self> {
+        Self::try_from(Arc::new(Mutex::new(bytes)))
+    }
+}
 
 pub trait Marker {
     fn get_marker(&self) -> Result<u8>;
@@ -41,3 +46,28 @@ pub(crate) fn get_info_from_bytes(bytes: &mut impl Buf) -> Result<(u8, u8)> {
     let signature = bytes.get_u8();
     Ok((marker, signature))
 }
+
+// Serialize a structure from its fields, for structures whose number of fields depends on the
+// protocol version, which can't derive Serialize
+pub(crate) fn serialize_structure(marker: u8, signature: u8, fields: Vec<Value>) -> Result<Bytes> {
+    let fields = fields
+        .into_iter()
+        .map(Value::try_into_bytes)
+        .collect::<Result<Vec<_>>>()?;
+    let mut bytes = BytesMut::with_capacity(
+        std::mem::size_of::<u8>() * 2 + fields.iter().map(Bytes::len).sum::<usize>(),
+    );
+    bytes.put_u8(marker);
+    bytes.put_u8(signature);
+    for field in fields {
+        bytes.put(field);
+    }
+    Ok(bytes.freeze())
+}
+
+// Deserialize the next field of a structure that can't derive Deserialize
+pub(crate) fn deserialize_field<T: TryFrom<Value, Error = Error>>(
+    bytes: &Arc<Mutex<Bytes>>,
+) -> Result<T> {
+    T::try_from(Value::try_from(Arc::clone(bytes))?)
+}
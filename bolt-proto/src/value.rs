@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::convert::{TryFrom, TryInto};
 use std::hash::{Hash, Hasher};
 use std::ops::DerefMut;
@@ -6,11 +7,15 @@ use std::sync::{Arc, Mutex};
 
 use bytes::{Buf, Bytes};
 
+pub use access::ValueIndex;
 pub(crate) use boolean::Boolean;
 pub(crate) use byte_array::ByteArray;
+pub use crs::Crs;
 pub(crate) use date::Date;
 pub(crate) use date_time_offset::DateTimeOffset;
+pub(crate) use date_time_offset_utc::DateTimeOffsetUtc;
 pub(crate) use date_time_zoned::DateTimeZoned;
+pub(crate) use date_time_zoned_utc::DateTimeZonedUtc;
 pub use duration::Duration;
 pub(crate) use float::Float;
 pub(crate) use integer::Integer;
@@ -31,12 +36,19 @@ pub use unbound_relationship::UnboundRelationship;
 use crate::error::*;
 use crate::serialization::*;
 
+// The exclusive upper bound of the nanoseconds since midnight held by times
+pub(crate) const NANOS_PER_DAY: i64 = 86_400 * 1_000_000_000;
+
+pub(crate) mod access;
 pub(crate) mod boolean;
 pub(crate) mod byte_array;
 pub(crate) mod conversions;
+pub(crate) mod crs;
 pub(crate) mod date;
 pub(crate) mod date_time_offset;
+pub(crate) mod date_time_offset_utc;
 pub(crate) mod date_time_zoned;
+pub(crate) mod date_time_zoned_utc;
 pub(crate) mod duration;
 pub(crate) mod float;
 pub(crate) mod integer;
@@ -46,6 +58,7 @@ pub(crate) mod local_time;
 pub(crate) mod map;
 pub(crate) mod node;
 pub(crate) mod null;
+pub(crate) mod ordering;
 pub(crate) mod path;
 pub(crate) mod point_2d;
 pub(crate) mod point_3d;
@@ -63,7 +76,13 @@ pub(crate) mod unbound_relationship;
 /// provided, but may feel a bit clunky (for example, you can convert a
 /// `(`[`NaiveTime`](chrono::NaiveTime)`, impl `[`Offset`](chrono::Offset)`)` tuple into a
 /// [`Value::Time`]).
-#[derive(Debug, Clone, PartialEq)]
+///
+/// Values are equal, and ordered, the same way Cypher considers them equivalent and orders
+/// them in `ORDER BY`, so that integers and floats with the same numeric value are equal, and
+/// NaN is equal to itself and greater than all other numbers. Use [`Value::compare`] and
+/// [`Value::cypher_eq`] to compare values the same way as Cypher's comparison operators,
+/// which return null in more cases.
+#[derive(Debug, Clone)]
 pub enum Value {
     // V1-compatible value types
     Boolean(Boolean),
@@ -89,47 +108,146 @@ pub enum Value {
     Duration(Duration),
     Point2D(Point2D),
     Point3D(Point3D),
+
+    // Replace DateTimeOffset and DateTimeZoned in V5+, or in V4.3+ with the "utc" patch
+    DateTimeOffsetUtc(DateTimeOffsetUtc), // Same as DateTimeOffset, but in UTC seconds
+    DateTimeZonedUtc(DateTimeZonedUtc),   // Same as DateTimeZoned, but in UTC seconds
 }
 
-#[allow(clippy::derive_hash_xor_eq)]
-// We implement Hash here despite deriving PartialEq because f64 and HashMap cannot be
-// hashed and must panic
+// Values that are equal must hash the same, so values of different types that can be equal,
+// like integers and floats, or date-times with a UTC offset in legacy and UTC encodings, are
+// hashed in terms of what they represent. Nodes and relationships are hashed by their IDs.
 impl Hash for Value {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        ordering::group(self).hash(state);
         match self {
-            Value::Float(_)
-            | Value::Map(_)
-            | Value::Node(_)
-            | Value::Relationship(_)
-            | Value::UnboundRelationship(_)
-            | Value::Path(_)
-            | Value::Point2D(_)
-            | Value::Point3D(_) => panic!("Cannot hash a {:?}", self),
             Value::Boolean(boolean) => boolean.hash(state),
-            Value::Integer(integer) => integer.hash(state),
+            Value::Integer(integer) => integer.value.hash(state),
+            Value::Float(float) => hash_float(float.value, state),
             Value::Bytes(bytes) => bytes.hash(state),
             Value::List(list) => list.hash(state),
-            Value::Null => Null.hash(state),
+            Value::Map(map) => hash_map(&map.value, state),
+            Value::Null => {}
             Value::String(string) => string.hash(state),
+            Value::Node(node) => node.node_identity.hash(state),
+            Value::Relationship(rel) => rel.rel_identity.hash(state),
+            Value::UnboundRelationship(rel) => rel.rel_identity.hash(state),
+            Value::Path(path) => {
+                for node in &path.nodes {
+                    node.node_identity.hash(state);
+                }
+                for rel in &path.relationships {
+                    rel.rel_identity.hash(state);
+                }
+                path.sequence.hash(state);
+            }
             Value::Date(date) => date.hash(state),
             Value::Time(time) => time.hash(state),
-            Value::DateTimeOffset(date_time_offset) => date_time_offset.hash(state),
-            Value::DateTimeZoned(date_time_zoned) => date_time_zoned.hash(state),
+            Value::DateTimeOffset(_)
+            | Value::DateTimeZoned(_)
+            | Value::DateTimeOffsetUtc(_)
+            | Value::DateTimeZonedUtc(_) => ordering::date_time_key(self).hash(state),
             Value::LocalTime(local_time) => local_time.hash(state),
             Value::LocalDateTime(local_date_time) => local_date_time.hash(state),
             Value::Duration(duration) => duration.hash(state),
+            Value::Point2D(point) => {
+                point.srid.hash(state);
+                hash_float(point.x, state);
+                hash_float(point.y, state);
+            }
+            Value::Point3D(point) => {
+                point.srid.hash(state);
+                hash_float(point.x, state);
+                hash_float(point.y, state);
+                hash_float(point.z, state);
+            }
         }
     }
 }
 
-impl Eq for Value {
-    fn assert_receiver_is_total_eq(&self) {
-        if let Value::Float(_) | Value::Point2D(_) | Value::Point3D(_) = self {
-            panic!("{:?} does not impl Eq", self)
+// Floats with an integer value hash the same as that integer, which also makes 0.0 and -0.0
+// hash the same, and all NaNs hash the same
+fn hash_float<H: Hasher>(value: f64, state: &mut H) {
+    // 2^63, the smallest float greater than every i64
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if value.fract() == 0.0 && (-LIMIT..LIMIT).contains(&value) {
+        (value as i64).hash(state);
+    } else if value.is_nan() {
+        f64::NAN.to_bits().hash(state);
+    } else {
+        value.to_bits().hash(state);
+    }
+}
+
+// Maps are hashed in order of their keys, so that equal maps hash the same regardless of the
+// order they iterate in
+fn hash_map<H: Hasher>(map: &HashMap<std::string::String, Value>, state: &mut H) {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_unstable_by_key(|(key, _)| *key);
+    entries.hash(state);
+}
+
+impl Value {
+    /// Convert date-times with a UTC offset or time zone ID, including those nested in lists
+    /// and maps, to the encoding based on UTC seconds if `utc` is true, or to the legacy
+    /// encoding based on local seconds otherwise. Servers only understand the former if
+    /// they support Bolt v5.0 or later, or Bolt v4.3/v4.4 with the "utc" patch, and only
+    /// understand the latter otherwise.
+    ///
+    /// Legacy date-times whose local time is repeated in their time zone, such as 01:30 on
+    /// the day clocks are turned back, are converted to the earliest matching instant.
+    /// Date-times with a time zone ID can only be converted with the `chrono` feature,
+    /// which provides the time zone database, and fail with
+    /// [`ConversionError::UnknownTimeZone`] otherwise.
+    pub fn convert_date_times(self, utc: bool) -> Result<Value> {
+        match self {
+            Value::DateTimeOffset(date_time_offset) if utc => Ok(Value::DateTimeOffsetUtc(
+                DateTimeOffsetUtc::try_from(date_time_offset)?,
+            )),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeZoned(date_time_zoned) if utc => Ok(Value::DateTimeZonedUtc(
+                DateTimeZonedUtc::try_from(date_time_zoned)?,
+            )),
+            Value::DateTimeOffsetUtc(date_time_offset) if !utc => Ok(Value::DateTimeOffset(
+                DateTimeOffset::try_from(date_time_offset)?,
+            )),
+            #[cfg(feature = "chrono")]
+            Value::DateTimeZonedUtc(date_time_zoned) if !utc => Ok(Value::DateTimeZoned(
+                DateTimeZoned::try_from(date_time_zoned)?,
+            )),
+            // Converting requires the time zone database provided with the `chrono` feature
+            #[cfg(not(feature = "chrono"))]
+            Value::DateTimeZoned(DateTimeZoned { zone_id, .. }) if utc => {
+                Err(ConversionError::UnknownTimeZone(zone_id).into())
+            }
+            #[cfg(not(feature = "chrono"))]
+            Value::DateTimeZonedUtc(DateTimeZonedUtc { zone_id, .. }) if !utc => {
+                Err(ConversionError::UnknownTimeZone(zone_id).into())
+            }
+            Value::List(list) => Ok(Value::List(List {
+                value: list
+                    .value
+                    .into_iter()
+                    .map(|value| value.convert_date_times(utc))
+                    .collect::<Result<_>>()?,
+            })),
+            Value::Map(map) => Ok(Value::Map(Map {
+                value: convert_date_times(map.value, utc)?,
+            })),
+            other => Ok(other),
         }
     }
 }
 
+pub(crate) fn convert_date_times<K: Eq + Hash>(
+    map: HashMap<K, Value>,
+    utc: bool,
+) -> Result<HashMap<K, Value>> {
+    map.into_iter()
+        .map(|(key, value)| Ok((key, value.convert_date_times(utc)?)))
+        .collect()
+}
+
 impl Marker for Value {
     fn get_marker(&self) -> Result<u8> {
         match self {
@@ -154,6 +272,8 @@ impl Marker for Value {
             Value::Duration(duration) => duration.get_marker(),
             Value::Point2D(point_2d) => point_2d.get_marker(),
             Value::Point3D(point_3d) => point_3d.get_marker(),
+            Value::DateTimeOffsetUtc(date_time_offset) => date_time_offset.get_marker(),
+            Value::DateTimeZonedUtc(date_time_zoned) => date_time_zoned.get_marker(),
         }
     }
 }
@@ -186,6 +306,8 @@ impl TryInto<Bytes> for Value {
             Value::Duration(duration) => duration.try_into(),
             Value::Point2D(point_2d) => point_2d.try_into(),
             Value::Point3D(point_3d) => point_3d.try_into(),
+            Value::DateTimeOffsetUtc(date_time_offset) => date_time_offset.try_into(),
+            Value::DateTimeZonedUtc(date_time_zoned) => date_time_zoned.try_into(),
         }
     }
 }
@@ -262,11 +384,21 @@ impl TryFrom<Arc<Mutex<Bytes>>> for Value {
 
 fn deserialize_structure(input_arc: Arc<Mutex<Bytes>>) -> Result<Value> {
     catch_unwind(move || {
-        let (_marker, signature) = get_info_from_bytes(input_arc.lock().unwrap().deref_mut())?;
+        let (marker, signature) = get_info_from_bytes(input_arc.lock().unwrap().deref_mut())?;
         match signature {
+            // Bolt 5 graph structures have additional fields for element IDs
+            node::SIGNATURE if marker == node::MARKER_V5 => {
+                Ok(Value::Node(Node::try_from_v5(input_arc)?))
+            }
             node::SIGNATURE => Ok(Value::Node(Node::try_from(input_arc)?)),
+            relationship::SIGNATURE if marker == relationship::MARKER_V5 => {
+                Ok(Value::Relationship(Relationship::try_from_v5(input_arc)?))
+            }
             relationship::SIGNATURE => Ok(Value::Relationship(Relationship::try_from(input_arc)?)),
             path::SIGNATURE => Ok(Value::Path(Path::try_from(input_arc)?)),
+            unbound_relationship::SIGNATURE if marker == unbound_relationship::MARKER_V5 => Ok(
+                Value::UnboundRelationship(UnboundRelationship::try_from_v5(input_arc)?),
+            ),
             unbound_relationship::SIGNATURE => Ok(Value::UnboundRelationship(
                 UnboundRelationship::try_from(input_arc)?,
             )),
@@ -285,20 +417,27 @@ fn deserialize_structure(input_arc: Arc<Mutex<Bytes>>) -> Result<Value> {
             duration::SIGNATURE => Ok(Value::Duration(Duration::try_from(input_arc)?)),
             point_2d::SIGNATURE => Ok(Value::Point2D(Point2D::try_from(input_arc)?)),
             point_3d::SIGNATURE => Ok(Value::Point3D(Point3D::try_from(input_arc)?)),
+            date_time_offset_utc::SIGNATURE => Ok(Value::DateTimeOffsetUtc(
+                DateTimeOffsetUtc::try_from(input_arc)?,
+            )),
+            date_time_zoned_utc::SIGNATURE => Ok(Value::DateTimeZonedUtc(
+                DateTimeZonedUtc::try_from(input_arc)?,
+            )),
             _ => Err(DeserializationError::InvalidSignatureByte(signature).into()),
         }
     })
     .map_err(|_| DeserializationError::Panicked)?
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use std::collections::HashMap;
     use std::iter::FromIterator;
 
-    use chrono::{FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+    use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 
     use super::*;
+    use crate::error::{ConversionError, Error};
 
     #[test]
     fn null_from_bytes() {
@@ -326,17 +465,17 @@ mod tests {
     #[test]
     fn integer_from_bytes() {
         let tiny = Integer::from(110_i8);
-        let tiny_bytes = tiny.clone().try_into_bytes().unwrap();
+        let tiny_bytes = tiny.try_into_bytes().unwrap();
         let small = Integer::from(-50_i8);
-        let small_bytes = small.clone().try_into_bytes().unwrap();
+        let small_bytes = small.try_into_bytes().unwrap();
         let medium = Integer::from(8000_i16);
-        let medium_bytes = medium.clone().try_into_bytes().unwrap();
+        let medium_bytes = medium.try_into_bytes().unwrap();
         let medium_negative = Integer::from(-18621_i16);
-        let medium_negative_bytes = medium_negative.clone().try_into_bytes().unwrap();
+        let medium_negative_bytes = medium_negative.try_into_bytes().unwrap();
         let large = Integer::from(-1_000_000_000_i32);
-        let large_bytes = large.clone().try_into_bytes().unwrap();
+        let large_bytes = large.try_into_bytes().unwrap();
         let very_large = Integer::from(9_000_000_000_000_000_000_i64);
-        let very_large_bytes = very_large.clone().try_into_bytes().unwrap();
+        let very_large_bytes = very_large.try_into_bytes().unwrap();
         assert_eq!(
             Value::try_from(Arc::new(Mutex::new(tiny_bytes))).unwrap(),
             Value::Integer(tiny)
@@ -365,9 +504,9 @@ mod tests {
 
     #[test]
     fn float_from_bytes() {
-        let min = Float::from(std::f64::MIN_POSITIVE);
+        let min = Float::from(f64::MIN_POSITIVE);
         let min_bytes = min.clone().try_into_bytes().unwrap();
-        let max = Float::from(std::f64::MAX);
+        let max = Float::from(f64::MAX);
         let max_bytes = max.clone().try_into_bytes().unwrap();
         let e = Float::from(std::f64::consts::E);
         let e_bytes = e.clone().try_into_bytes().unwrap();
@@ -583,7 +722,7 @@ mod tests {
 
     #[test]
     fn path_from_bytes() {
-        let path = Path::new(vec![get_node()], vec![get_unbound_rel()], vec![100, 101]);
+        let path = Path::new(vec![get_node()], vec![get_unbound_rel()], vec![1, 0]);
         let path_bytes: Bytes = path.clone().try_into_bytes().unwrap();
 
         assert_eq!(
@@ -602,9 +741,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn element_ids_from_bytes() {
+        let node = get_node().with_element_id("4:db:24");
+        let node_bytes: Bytes = node.clone().try_into_bytes().unwrap();
+        assert_eq!(node_bytes[0], node::MARKER_V5);
+        assert_eq!(
+            Value::try_from(Arc::new(Mutex::new(node_bytes))).unwrap(),
+            Value::Node(node.clone())
+        );
+        assert_ne!(Value::Node(node), Value::Node(get_node()));
+
+        let rel = get_rel().with_element_ids("5:db:24", "4:db:32", "4:db:128");
+        let rel_bytes: Bytes = rel.clone().try_into_bytes().unwrap();
+        assert_eq!(rel_bytes[0], relationship::MARKER_V5);
+        let value = Value::try_from(Arc::new(Mutex::new(rel_bytes))).unwrap();
+        assert_eq!(value, Value::Relationship(rel));
+        match value {
+            Value::Relationship(rel) => {
+                assert_eq!(rel.element_id(), Some("5:db:24"));
+                assert_eq!(rel.start_node_element_id(), Some("4:db:32"));
+                assert_eq!(rel.end_node_element_id(), Some("4:db:128"));
+            }
+            _ => unreachable!(),
+        }
+
+        let path = Path::new(
+            vec![get_node().with_element_id("4:db:24")],
+            vec![get_unbound_rel().with_element_id("5:db:128")],
+            vec![1, 0],
+        );
+        let path_bytes: Bytes = path.clone().try_into_bytes().unwrap();
+        assert_eq!(
+            Value::try_from(Arc::new(Mutex::new(path_bytes))).unwrap(),
+            Value::Path(path.clone())
+        );
+        let (_, rel, _) = path.segments().next().unwrap();
+        assert_eq!(rel.element_id(), Some("5:db:128"));
+        assert_eq!(rel.start_node_element_id(), Some("4:db:24"));
+        assert_eq!(rel.end_node_element_id(), Some("4:db:24"));
+    }
+
     #[test]
     fn date_from_bytes() {
-        let christmas = Date::from(NaiveDate::from_ymd(2020, 12, 25));
+        let christmas = Date::from(NaiveDate::from_ymd_opt(2020, 12, 25).unwrap());
         let christmas_bytes: Bytes = christmas.clone().try_into_bytes().unwrap();
         assert_eq!(
             Value::try_from(Arc::new(Mutex::new(christmas_bytes))).unwrap(),
@@ -614,11 +794,11 @@ mod tests {
 
     #[test]
     fn time_from_bytes() {
-        let midnight_utc = Time::from((NaiveTime::from_hms_nano(0, 0, 0, 0), Utc));
+        let midnight_utc = Time::from((NaiveTime::from_hms_nano_opt(0, 0, 0, 0).unwrap(), Utc));
         let midnight_utc_bytes = midnight_utc.clone().try_into_bytes().unwrap();
         let about_four_pm_pacific = Time::from((
-            NaiveTime::from_hms_nano(16, 4, 35, 235),
-            FixedOffset::east(-8 * 3600),
+            NaiveTime::from_hms_nano_opt(16, 4, 35, 235).unwrap(),
+            FixedOffset::east_opt(-8 * 3600).unwrap(),
         ));
         let about_four_pm_pacific_bytes = about_four_pm_pacific.clone().try_into_bytes().unwrap();
         assert_eq!(
@@ -634,8 +814,12 @@ mod tests {
     #[test]
     fn date_time_offset_from_bytes() {
         let date_time = DateTimeOffset::from(
-            FixedOffset::east(-5 * 3600)
-                .from_utc_datetime(&NaiveDate::from_ymd(2050, 12, 31).and_hms_nano(23, 59, 59, 10)),
+            FixedOffset::east_opt(-5 * 3600).unwrap().from_utc_datetime(
+                &NaiveDate::from_ymd_opt(2050, 12, 31)
+                    .unwrap()
+                    .and_hms_nano_opt(23, 59, 59, 10)
+                    .unwrap(),
+            ),
         );
         let date_time_bytes = date_time.clone().try_into_bytes().unwrap();
         assert_eq!(
@@ -647,7 +831,10 @@ mod tests {
     #[test]
     fn date_time_zoned_from_bytes() {
         let date_time = DateTimeZoned::from((
-            NaiveDate::from_ymd(2030, 8, 3).and_hms_milli(14, 30, 1, 2),
+            NaiveDate::from_ymd_opt(2030, 8, 3)
+                .unwrap()
+                .and_hms_milli_opt(14, 30, 1, 2)
+                .unwrap(),
             chrono_tz::Asia::Ulaanbaatar,
         ));
         let date_time_bytes = date_time.clone().try_into_bytes().unwrap();
@@ -659,7 +846,7 @@ mod tests {
 
     #[test]
     fn local_time_from_bytes() {
-        let local_time = LocalTime::from(NaiveTime::from_hms_nano(23, 59, 59, 999));
+        let local_time = LocalTime::from(NaiveTime::from_hms_nano_opt(23, 59, 59, 999).unwrap());
         let local_time_bytes = local_time.clone().try_into_bytes().unwrap();
         assert_eq!(
             Value::try_from(Arc::new(Mutex::new(local_time_bytes))).unwrap(),
@@ -669,8 +856,12 @@ mod tests {
 
     #[test]
     fn local_date_time_from_bytes() {
-        let local_date_time =
-            LocalDateTime::from(NaiveDate::from_ymd(1999, 2, 27).and_hms_nano(1, 0, 0, 9999));
+        let local_date_time = LocalDateTime::from(
+            NaiveDate::from_ymd_opt(1999, 2, 27)
+                .unwrap()
+                .and_hms_nano_opt(1, 0, 0, 9999)
+                .unwrap(),
+        );
         let local_date_time_bytes = local_date_time.clone().try_into_bytes().unwrap();
         assert_eq!(
             Value::try_from(Arc::new(Mutex::new(local_date_time_bytes))).unwrap(),
@@ -705,6 +896,215 @@ mod tests {
         );
     }
 
+    #[test]
+    fn invalid_temporal_from_bytes() {
+        let unknown_zone = DateTimeZoned {
+            epoch_seconds: 0,
+            nanos: 0,
+            zone_id: "Mars/Olympus_Mons".to_string(),
+        };
+        // Zone IDs are only checked on conversion
+        let unknown_zone_bytes = unknown_zone.clone().try_into_bytes().unwrap();
+        assert_eq!(
+            Value::try_from(Arc::new(Mutex::new(unknown_zone_bytes))).unwrap(),
+            Value::DateTimeZoned(unknown_zone)
+        );
+
+        let nanos_out_of_range = DateTimeZonedUtc {
+            epoch_seconds: 0,
+            nanos: 1_000_000_000,
+            zone_id: "Europe/Paris".to_string(),
+        };
+        let nanos_out_of_range_bytes = nanos_out_of_range.try_into_bytes().unwrap();
+        assert!(matches!(
+            Value::try_from(Arc::new(Mutex::new(nanos_out_of_range_bytes))),
+            Err(Error::ConversionError(ConversionError::TemporalOutOfRange(
+                _
+            )))
+        ));
+
+        let after_midnight = LocalTime {
+            nanos_since_midnight: NANOS_PER_DAY,
+        };
+        let after_midnight_bytes = after_midnight.try_into_bytes().unwrap();
+        assert!(matches!(
+            Value::try_from(Arc::new(Mutex::new(after_midnight_bytes))),
+            Err(Error::ConversionError(ConversionError::TemporalOutOfRange(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn temporal_conversion_errors() {
+        let far_future = Value::Date(Date {
+            days_since_epoch: i64::MAX,
+        });
+        assert!(matches!(
+            NaiveDate::try_from(far_future),
+            Err(Error::ConversionError(ConversionError::TemporalOutOfRange(
+                _
+            )))
+        ));
+
+        let unknown_zone = Value::DateTimeZoned(DateTimeZoned {
+            epoch_seconds: 0,
+            nanos: 0,
+            zone_id: "Mars/Olympus_Mons".to_string(),
+        });
+        assert!(matches!(
+            DateTime::<chrono_tz::Tz>::try_from(unknown_zone.clone()),
+            Err(Error::ConversionError(ConversionError::UnknownTimeZone(_)))
+        ));
+        assert!(matches!(
+            DateTime::<FixedOffset>::try_from(unknown_zone),
+            Err(Error::ConversionError(ConversionError::UnknownTimeZone(_)))
+        ));
+
+        let out_of_range = Value::LocalDateTime(LocalDateTime {
+            epoch_seconds: i64::MAX,
+            nanos: 0,
+        });
+        assert!(matches!(
+            NaiveDateTime::try_from(out_of_range),
+            Err(Error::ConversionError(ConversionError::TemporalOutOfRange(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn date_time_offset_utc_from_bytes() {
+        let date_time = DateTimeOffsetUtc::from(
+            FixedOffset::east_opt(9 * 3600)
+                .unwrap()
+                .with_ymd_and_hms(2024, 3, 10, 2, 30, 0)
+                .unwrap(),
+        );
+        let date_time_bytes = date_time.clone().try_into_bytes().unwrap();
+        assert_eq!(
+            Value::try_from(Arc::new(Mutex::new(date_time_bytes))).unwrap(),
+            Value::DateTimeOffsetUtc(date_time)
+        );
+    }
+
+    #[test]
+    fn convert_date_times() {
+        let offset_date_time = FixedOffset::east_opt(-4 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2021, 11, 7, 1, 30, 0)
+            .unwrap();
+        let legacy = Value::from(offset_date_time);
+        let utc = legacy.clone().convert_date_times(true).unwrap();
+        assert!(matches!(utc, Value::DateTimeOffsetUtc(_)));
+        assert_eq!(
+            DateTime::<FixedOffset>::try_from(legacy.clone()).unwrap(),
+            offset_date_time
+        );
+        assert_eq!(
+            DateTime::<FixedOffset>::try_from(utc.clone()).unwrap(),
+            offset_date_time
+        );
+        assert_eq!(utc.convert_date_times(false).unwrap(), legacy);
+
+        // 01:30 happens twice in New York on this day. Only the UTC encoding can tell the
+        // second one apart.
+        let second_time = chrono_tz::America::New_York
+            .with_ymd_and_hms(2021, 11, 7, 1, 30, 0)
+            .latest()
+            .unwrap();
+        let utc = Value::DateTimeZonedUtc(DateTimeZonedUtc::from(second_time));
+        assert_eq!(
+            DateTime::<chrono_tz::Tz>::try_from(utc.clone()).unwrap(),
+            second_time
+        );
+        let legacy = utc.convert_date_times(false).unwrap();
+        assert_eq!(
+            DateTime::<chrono_tz::Tz>::try_from(legacy).unwrap(),
+            second_time - chrono::Duration::hours(1)
+        );
+
+        let nested = Value::from(vec![Value::from(HashMap::<&str, Value>::from_iter(vec![
+            ("at", Value::from(offset_date_time)),
+        ]))]);
+        match nested.convert_date_times(true).unwrap() {
+            Value::List(list) => match &list.value[0] {
+                Value::Map(map) => assert!(matches!(
+                    map.value.values().next(),
+                    Some(Value::DateTimeOffsetUtc(_))
+                )),
+                other => panic!("expected a map, got {:?}", other),
+            },
+            other => panic!("expected a list, got {:?}", other),
+        }
+    }
+
+    fn hash_of(value: &Value) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn hash() {
+        let map = Value::from(HashMap::<&str, Value>::from_iter(vec![
+            ("node", Value::from(get_node())),
+            ("rel", Value::from(get_rel())),
+            ("unbound_rel", Value::from(get_unbound_rel())),
+            (
+                "path",
+                Value::from(Path::new(vec![get_node()], vec![], vec![])),
+            ),
+            ("point2d", Value::from(Point2D::new(7203, 1.5, -0.0))),
+            (
+                "point3d",
+                Value::from(Point3D::new(9157, 1.5, 0.0, f64::NAN)),
+            ),
+            ("float", Value::from(1.5)),
+        ]));
+        // A list containing a map used to panic when hashed
+        let list = Value::from(vec![map.clone(), Value::Null]);
+        assert_eq!(hash_of(&list), hash_of(&list.clone()));
+
+        let mut set = std::collections::HashSet::new();
+        assert!(set.insert(list.clone()));
+        assert!(!set.insert(list));
+        assert!(set.insert(map));
+
+        // Values that are equal hash the same
+        for (a, b) in [
+            (Value::from(1), Value::from(1.0)),
+            (Value::from(0.0), Value::from(-0.0)),
+            (Value::from(f64::NAN), Value::from(-f64::NAN)),
+            (
+                Value::from(Point2D::new(7203, 0.0, 2.5)),
+                Value::from(Point2D::new(7203, -0.0, 2.5)),
+            ),
+            (
+                Value::from(DateTime::parse_from_rfc3339("2021-01-01T12:00:00+01:00").unwrap()),
+                Value::from(DateTime::parse_from_rfc3339("2021-01-01T12:00:00+01:00").unwrap())
+                    .convert_date_times(true)
+                    .unwrap(),
+            ),
+        ] {
+            assert_eq!(a, b);
+            assert_eq!(hash_of(&a), hash_of(&b));
+        }
+
+        // Maps hash the same regardless of insertion order
+        let entries = (0..50).map(|n| (n.to_string(), n)).collect::<Vec<_>>();
+        let forward = Value::from(HashMap::<std::string::String, i64>::from_iter(
+            entries.clone(),
+        ));
+        let backward = Value::from(HashMap::<std::string::String, i64>::from_iter(
+            entries.into_iter().rev(),
+        ));
+        assert_eq!(hash_of(&forward), hash_of(&backward));
+
+        assert_ne!(hash_of(&Value::from(1)), hash_of(&Value::from("1")));
+        assert_ne!(hash_of(&Value::from(1.5)), hash_of(&Value::from(1)));
+    }
+
     #[test]
     #[ignore]
     fn value_size() {
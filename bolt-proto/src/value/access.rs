@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+
+use crate::value::*;
+
+impl Value {
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(boolean) => Some(boolean.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            Value::Integer(integer) => Some(integer.value),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of a float, or of an integer converted to a float, which may lose
+    /// precision for integers of magnitude greater than 2<sup>53</sup>.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Float(float) => Some(float.value),
+            Value::Integer(integer) => Some(integer.value as f64),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(&string.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(&bytes.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(list) => Some(&list.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_map(&self) -> Option<&HashMap<std::string::String, Value>> {
+        match self {
+            Value::Map(map) => Some(&map.value),
+            _ => None,
+        }
+    }
+
+    pub fn as_node(&self) -> Option<&Node> {
+        match self {
+            Value::Node(node) => Some(node),
+            _ => None,
+        }
+    }
+
+    pub fn as_relationship(&self) -> Option<&Relationship> {
+        match self {
+            Value::Relationship(rel) => Some(rel),
+            _ => None,
+        }
+    }
+
+    pub fn as_unbound_relationship(&self) -> Option<&UnboundRelationship> {
+        match self {
+            Value::UnboundRelationship(rel) => Some(rel),
+            _ => None,
+        }
+    }
+
+    pub fn as_path(&self) -> Option<&Path> {
+        match self {
+            Value::Path(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    pub fn as_time(&self) -> Option<&Time> {
+        match self {
+            Value::Time(time) => Some(time),
+            _ => None,
+        }
+    }
+
+    pub fn as_duration(&self) -> Option<&Duration> {
+        match self {
+            Value::Duration(duration) => Some(duration),
+            _ => None,
+        }
+    }
+
+    pub fn as_point_2d(&self) -> Option<&Point2D> {
+        match self {
+            Value::Point2D(point) => Some(point),
+            _ => None,
+        }
+    }
+
+    pub fn as_point_3d(&self) -> Option<&Point3D> {
+        match self {
+            Value::Point3D(point) => Some(point),
+            _ => None,
+        }
+    }
+
+    /// Look up an element of a list by its position, or an entry of a map or a property of a
+    /// node or relationship by its key. Returns `None` if the value can't be indexed this way
+    /// or the element doesn't exist.
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use bolt_proto::Value;
+    /// let value = Value::from(vec![HashMap::from([("name", "Alice")])]);
+    /// assert_eq!(value.get(0).and_then(|map| map.get("name")), Some(&Value::from("Alice")));
+    /// assert_eq!(value.get("name"), None);
+    /// ```
+    pub fn get<I: ValueIndex>(&self, index: I) -> Option<&Value> {
+        index.index_into(self)
+    }
+
+    /// Look up a value by a JSON pointer, as defined in
+    /// [RFC 6901](https://tools.ietf.org/html/rfc6901), descending through lists, maps, and
+    /// properties of nodes and relationships. Reference tokens are separated by `/`, with
+    /// `~1` standing for a `/` and `~0` for a `~` within a token. The empty pointer refers to
+    /// the value itself.
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use bolt_proto::Value;
+    /// let value = Value::from(HashMap::from([("friends", vec!["Alice", "Bob"])]));
+    /// assert_eq!(value.pointer("/friends/1"), Some(&Value::from("Bob")));
+    /// assert_eq!(value.pointer("/friends/2"), None);
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        pointer[1..].split('/').try_fold(self, |value, token| {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            match value {
+                Value::List(_) => parse_index(&token).and_then(|index| value.get(index)),
+                _ => value.get(token.as_str()),
+            }
+        })
+    }
+}
+
+// Array indices in a JSON pointer are written in decimal without leading zeros
+fn parse_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() > 1) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// A type that can index into a [`Value`] with [`Value::get`]: [`usize`] for elements of
+/// lists, and string types for entries of maps and properties of nodes and relationships.
+pub trait ValueIndex: private::Sealed {
+    #[doc(hidden)]
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value>;
+}
+
+impl ValueIndex for usize {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        value.as_list()?.get(*self)
+    }
+}
+
+impl ValueIndex for str {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        match value {
+            Value::Map(map) => map.value.get(self),
+            Value::Node(node) => node.properties.get(self),
+            Value::Relationship(rel) => rel.properties.get(self),
+            Value::UnboundRelationship(rel) => rel.properties.get(self),
+            _ => None,
+        }
+    }
+}
+
+impl ValueIndex for std::string::String {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        self.as_str().index_into(value)
+    }
+}
+
+impl<T: ValueIndex + ?Sized> ValueIndex for &T {
+    fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+        (**self).index_into(value)
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for usize {}
+    impl Sealed for str {}
+    impl Sealed for std::string::String {}
+    impl<T: Sealed + ?Sized> Sealed for &T {}
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::FromIterator;
+
+    use super::*;
+
+    fn get_node() -> Value {
+        Value::Node(Node::new(
+            1,
+            vec!["Person".to_string()],
+            HashMap::from_iter(vec![
+                ("name".to_string(), Value::from("Alice")),
+                (
+                    "friends".to_string(),
+                    Value::from(vec![
+                        Value::from(HashMap::from([("name", "Bob")])),
+                        Value::from(HashMap::from([("a/b~c", 3)])),
+                    ]),
+                ),
+            ]),
+        ))
+    }
+
+    #[test]
+    fn accessors() {
+        assert!(Value::Null.is_null());
+        assert!(!Value::from(false).is_null());
+        assert_eq!(Value::from(true).as_bool(), Some(true));
+        assert_eq!(Value::from(-5).as_i64(), Some(-5));
+        assert_eq!(Value::from(-5).as_f64(), Some(-5.0));
+        assert_eq!(Value::from(2.5).as_f64(), Some(2.5));
+        assert_eq!(Value::from(2.5).as_i64(), None);
+        assert_eq!(Value::from("hello").as_str(), Some("hello"));
+        assert_eq!(Value::from(1).as_str(), None);
+        assert_eq!(
+            Value::from(vec![1_u8, 2, 3]).as_bytes(),
+            Some(&[1_u8, 2, 3][..])
+        );
+        assert_eq!(
+            Value::from(vec![1, 2]).as_list(),
+            Some(&[Value::from(1), Value::from(2)][..])
+        );
+        assert_eq!(
+            Value::from(HashMap::from([("a", 1)]))
+                .as_map()
+                .and_then(|map| map.get("a")),
+            Some(&Value::from(1))
+        );
+        assert_eq!(get_node().as_node().map(Node::node_identity), Some(1));
+        assert!(get_node().as_relationship().is_none());
+        assert_eq!(
+            Value::from(Duration::new(1, 2, 3, 4)).as_duration(),
+            Some(&Duration::new(1, 2, 3, 4))
+        );
+    }
+
+    #[test]
+    fn get() {
+        let node = get_node();
+        assert_eq!(node.get("name"), Some(&Value::from("Alice")));
+        let key = "name".to_string();
+        assert_eq!(node.get(&key), Some(&Value::from("Alice")));
+        assert_eq!(node.get(key), Some(&Value::from("Alice")));
+        assert_eq!(node.get("age"), None);
+        assert_eq!(node.get(0), None);
+
+        let friends = node.get("friends").unwrap();
+        assert_eq!(
+            friends.get(0).and_then(|friend| friend.get("name")),
+            Some(&Value::from("Bob"))
+        );
+        assert_eq!(friends.get(2), None);
+        assert_eq!(friends.get("0"), None);
+
+        let rel = Value::Relationship(Relationship::new(
+            2,
+            1,
+            3,
+            "KNOWS".to_string(),
+            HashMap::from_iter(vec![("since".to_string(), Value::from(2001))]),
+        ));
+        assert_eq!(rel.get("since"), Some(&Value::from(2001)));
+    }
+
+    #[test]
+    fn pointer() {
+        let node = get_node();
+        assert_eq!(node.pointer(""), Some(&node));
+        assert_eq!(node.pointer("/name"), Some(&Value::from("Alice")));
+        assert_eq!(node.pointer("/friends/0/name"), Some(&Value::from("Bob")));
+        assert_eq!(node.pointer("/friends/1/a~1b~0c"), Some(&Value::from(3)));
+        assert_eq!(node.pointer("/friends/01/a~1b~0c"), None);
+        assert_eq!(node.pointer("/friends/+1/a~1b~0c"), None);
+        assert_eq!(node.pointer("/friends/2/name"), None);
+        assert_eq!(node.pointer("/name/0"), None);
+        assert_eq!(node.pointer("name"), None);
+        assert_eq!(node.pointer("/"), None);
+    }
+}
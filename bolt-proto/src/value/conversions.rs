@@ -2,12 +2,16 @@ use std::collections::HashMap;
 use std::convert::TryFrom;
 use std::hash::BuildHasher;
 
-use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone};
-use chrono_tz::Tz;
-
 use crate::error::*;
 use crate::value::*;
 
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "geo-types")]
+mod geo_types;
+#[cfg(feature = "time")]
+mod time;
+
 // ------------------------- Into Value -------------------------
 
 impl From<bool> for Value {
@@ -58,7 +62,7 @@ where
 
 impl<K, V, S> From<HashMap<K, V, S>> for Value
 where
-    K: Into<String>,
+    K: Into<std::string::String>,
     V: Into<Value>,
     S: BuildHasher,
 {
@@ -103,45 +107,6 @@ impl From<UnboundRelationship> for Value {
     }
 }
 
-impl From<NaiveDate> for Value {
-    fn from(value: NaiveDate) -> Self {
-        Value::Date(Date::from(value))
-    }
-}
-
-// No timezone-aware time in chrono, so provide a separate conversion
-impl<O: Offset> From<(NaiveTime, O)> for Value {
-    fn from(pair: (NaiveTime, O)) -> Self {
-        Value::Time(Time::from(pair))
-    }
-}
-
-impl<T: TimeZone> From<DateTime<T>> for Value {
-    fn from(value: DateTime<T>) -> Self {
-        Value::DateTimeOffset(DateTimeOffset::from(value))
-    }
-}
-
-// Can't decide between Offset or Zoned variant at runtime if using a T: TimeZone, so
-// provide a separate conversion
-impl From<(NaiveDateTime, chrono_tz::Tz)> for Value {
-    fn from(pair: (NaiveDateTime, chrono_tz::Tz)) -> Self {
-        Value::DateTimeZoned(DateTimeZoned::from(pair))
-    }
-}
-
-impl From<NaiveTime> for Value {
-    fn from(value: NaiveTime) -> Self {
-        Value::LocalTime(LocalTime::from(value))
-    }
-}
-
-impl From<NaiveDateTime> for Value {
-    fn from(value: NaiveDateTime) -> Self {
-        Value::LocalDateTime(LocalDateTime::from(value))
-    }
-}
-
 impl From<Duration> for Value {
     fn from(value: Duration) -> Self {
         Value::Duration(value)
@@ -273,7 +238,7 @@ where
                 let mut new_map =
                     HashMap::with_capacity_and_hasher(map.value.len(), Default::default());
                 for (k, v) in map.value {
-                    new_map.insert(k.value, V::try_from(v)?);
+                    new_map.insert(k, V::try_from(v)?);
                 }
                 Ok(new_map)
             }
@@ -293,9 +258,7 @@ where
             Value::Map(map) => {
                 let mut new_map =
                     HashMap::with_capacity_and_hasher(map.value.len(), Default::default());
-                for (k, v) in map.value {
-                    new_map.insert(k.value, v);
-                }
+                new_map.extend(map.value);
                 Ok(new_map)
             }
             _ => Err(ConversionError::FromValue(value).into()),
@@ -322,114 +285,24 @@ impl_try_from_value!(Path, Path);
 
 impl_try_from_value!(UnboundRelationship, UnboundRelationship);
 
-impl TryFrom<Value> for NaiveDate {
-    type Error = Error;
-
-    fn try_from(value: Value) -> Result<Self> {
-        match value {
-            Value::Date(date) => {
-                Ok(NaiveDate::from_ymd(1970, 1, 1) + chrono::Duration::days(date.days_since_epoch))
-            }
-            _ => Err(ConversionError::FromValue(value).into()),
-        }
-    }
-}
-
 impl_try_from_value!(Time, Time);
 
-impl TryFrom<Value> for DateTime<FixedOffset> {
-    type Error = Error;
-
-    fn try_from(value: Value) -> Result<Self> {
-        match value {
-            Value::DateTimeOffset(date_time_offset) => Ok(FixedOffset::east(
-                date_time_offset.offset_seconds,
-            )
-            .timestamp(
-                date_time_offset.epoch_seconds,
-                date_time_offset.nanos as u32,
-            )),
-            Value::DateTimeZoned(date_time_zoned) => {
-                // Time zone guaranteed to be valid in existing objects, ok to unwrap
-                let timezone: Tz = date_time_zoned.zone_id.parse().unwrap();
-                let timezone: FixedOffset = timezone
-                    // Get the fixed offset (e.g. Pacific Daylight vs. Pacific Standard)
-                    // for the given point in time
-                    .offset_from_utc_datetime(
-                        &NaiveDateTime::from_timestamp_opt(date_time_zoned.epoch_seconds, 0)
-                            // epoch_seconds is guaranteed to be a valid timestamp, ok to
-                            // unwrap
-                            .unwrap(),
-                    )
-                    .fix();
-                Ok(timezone
-                    .timestamp_opt(date_time_zoned.epoch_seconds, date_time_zoned.nanos as u32)
-                    // epoch_seconds and nanos are guaranteed to be valid in existing
-                    // objects, ok to unwrap
-                    .unwrap())
-            }
-            _ => Err(ConversionError::FromValue(value).into()),
-        }
-    }
-}
-
-impl TryFrom<Value> for DateTime<Tz> {
-    type Error = Error;
-
-    fn try_from(value: Value) -> Result<Self> {
-        match value {
-            Value::DateTimeZoned(date_time_zoned) => {
-                // Time zone guaranteed to be valid in existing objects, ok to unwrap
-                let timezone: Tz = date_time_zoned.zone_id.parse().unwrap();
-                Ok(timezone
-                    .timestamp_opt(date_time_zoned.epoch_seconds, date_time_zoned.nanos as u32)
-                    // epoch_seconds and nanos are guaranteed to be valid in existing
-                    // objects, ok to unwrap
-                    .unwrap())
-            }
-            _ => Err(ConversionError::FromValue(value).into()),
-        }
-    }
-}
-
-impl TryFrom<Value> for NaiveTime {
-    type Error = Error;
-
-    fn try_from(value: Value) -> Result<Self> {
-        match value {
-            Value::LocalTime(local_time) => {
-                let seconds = (local_time.nanos_since_midnight / 1_000_000_000) as u32;
-                let nanos = (local_time.nanos_since_midnight % 1_000_000_000) as u32;
-                // We created the LocalTime from a NaiveTime, so it can easily be
-                // converted back without worrying about a panic occurring
-                Ok(NaiveTime::from_num_seconds_from_midnight(seconds, nanos))
-            }
-            _ => Err(ConversionError::FromValue(value).into()),
-        }
-    }
-}
+impl_try_from_value!(Duration, Duration);
 
-impl TryFrom<Value> for NaiveDateTime {
+// Only durations without months can be converted, since months are not well-defined in terms
+// of seconds, and our Duration can hold quantities that are impossible to hold in a
+// std::time::Duration (like negative durations).
+impl TryFrom<Value> for std::time::Duration {
     type Error = Error;
 
     fn try_from(value: Value) -> Result<Self> {
         match value {
-            // We created the LocalDateTime from a NaiveDateTime, so it can easily be
-            // converted back without worrying about a panic occurring
-            Value::LocalDateTime(local_date_time) => Ok(NaiveDateTime::from_timestamp(
-                local_date_time.epoch_seconds,
-                local_date_time.nanos as u32,
-            )),
+            Value::Duration(duration) => std::time::Duration::try_from(duration),
             _ => Err(ConversionError::FromValue(value).into()),
         }
     }
 }
 
-// We cannot convert to std::time::Duration, since months are not well-defined in terms of
-// seconds, and our Duration can hold quantities that are impossible to hold in a
-// std::time::Duration (like negative durations).
-impl_try_from_value!(Duration, Duration);
-
 impl_try_from_value!(Point2D, Point2D);
 
 impl_try_from_value!(Point3D, Point3D);
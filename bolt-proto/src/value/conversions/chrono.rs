@@ -0,0 +1,312 @@
+use std::convert::TryFrom;
+
+use chrono::{
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike,
+};
+use chrono_tz::Tz;
+
+use crate::error::*;
+use crate::value::{date_time_zoned::parse_zone, *};
+
+impl From<NaiveDate> for Date {
+    fn from(naive_date: NaiveDate) -> Self {
+        Self {
+            days_since_epoch: (naive_date - NaiveDate::from_ymd_opt(1970, 1, 1).unwrap())
+                .num_days(),
+        }
+    }
+}
+
+impl Time {
+    pub fn naive_time(&self) -> NaiveTime {
+        let seconds = (self.nanos_since_midnight / 1_000_000_000) as u32;
+        let nanos = (self.nanos_since_midnight % 1_000_000_000) as u32;
+        // Does not panic since seconds and nanos came from a NaiveTime, or were validated
+        // when deserialized
+        NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos).unwrap()
+    }
+
+    pub fn offset(&self) -> FixedOffset {
+        // Likewise, the offset is known to be valid
+        FixedOffset::east_opt(self.zone_offset).unwrap()
+    }
+}
+
+// No timezone-aware time in chrono, so provide separate conversion instead
+impl<O: Offset> From<(NaiveTime, O)> for Time {
+    fn from(pair: (NaiveTime, O)) -> Self {
+        Self {
+            // Will not overflow: u32::MAX * 1_000_000_000 + u32::MAX < i64::MAX
+            nanos_since_midnight: pair.0.num_seconds_from_midnight() as i64 * 1_000_000_000
+                + pair.0.nanosecond() as i64,
+            zone_offset: pair.1.fix().local_minus_utc(),
+        }
+    }
+}
+
+impl<T: TimeZone> From<DateTime<T>> for DateTimeOffset {
+    fn from(date_time: DateTime<T>) -> Self {
+        Self {
+            // Legacy date-times hold seconds since the epoch in local time
+            epoch_seconds: date_time.naive_local().and_utc().timestamp(),
+            nanos: date_time.nanosecond() as i64,
+            offset_seconds: date_time.offset().fix().local_minus_utc(),
+        }
+    }
+}
+
+impl<T: TimeZone> From<DateTime<T>> for DateTimeOffsetUtc {
+    fn from(date_time: DateTime<T>) -> Self {
+        Self {
+            epoch_seconds: date_time.timestamp(),
+            nanos: date_time.nanosecond() as i64,
+            offset_seconds: date_time.offset().fix().local_minus_utc(),
+        }
+    }
+}
+
+// Can't impl<T: TimeZone> From<DateTime<T>> for DateTimeZoned, since we can't get a
+// timezone name from an Offset. Provide separate conversion instead
+impl From<(NaiveDateTime, Tz)> for DateTimeZoned {
+    fn from(pair: (NaiveDateTime, Tz)) -> Self {
+        Self {
+            epoch_seconds: pair.0.and_utc().timestamp(),
+            nanos: pair.0.nanosecond() as i64,
+            zone_id: pair.1.name().to_string(),
+        }
+    }
+}
+
+impl From<DateTime<Tz>> for DateTimeZonedUtc {
+    fn from(date_time: DateTime<Tz>) -> Self {
+        Self {
+            epoch_seconds: date_time.timestamp(),
+            nanos: date_time.nanosecond() as i64,
+            zone_id: date_time.timezone().name().to_string(),
+        }
+    }
+}
+
+impl From<NaiveTime> for LocalTime {
+    fn from(naive_time: NaiveTime) -> Self {
+        Self {
+            // Will not overflow: u32::MAX * 1_000_000_000 + u32::MAX < i64::MAX
+            nanos_since_midnight: naive_time.num_seconds_from_midnight() as i64 * 1_000_000_000
+                + naive_time.nanosecond() as i64,
+        }
+    }
+}
+
+impl From<NaiveDateTime> for LocalDateTime {
+    fn from(date_time: NaiveDateTime) -> Self {
+        Self {
+            epoch_seconds: date_time.and_utc().timestamp(),
+            nanos: date_time.nanosecond() as i64,
+        }
+    }
+}
+
+impl From<chrono::Duration> for Duration {
+    fn from(duration: chrono::Duration) -> Self {
+        // Months are not well-defined in terms of seconds so let's not use them here. The
+        // nanoseconds of negative durations are negative, so they are carried into the seconds,
+        // which can't overflow since they are less than a day.
+        Self::checked_new(
+            0,
+            duration.num_seconds() / (3600 * 24),
+            duration.num_seconds() % (3600 * 24),
+            duration.subsec_nanos() as i64,
+        )
+        .unwrap()
+    }
+}
+
+// Durations with months cannot be converted, since months are not well-defined in terms of
+// seconds.
+impl TryFrom<Duration> for chrono::Duration {
+    type Error = Error;
+
+    fn try_from(duration: Duration) -> Result<Self> {
+        if duration.months != 0 {
+            return Err(ConversionError::FromValue(Value::Duration(duration)).into());
+        }
+        match duration.total_seconds().and_then(|seconds| {
+            chrono::Duration::try_seconds(seconds)?
+                .checked_add(&chrono::Duration::nanoseconds(duration.nanos as i64))
+        }) {
+            Some(duration) => Ok(duration),
+            None => Err(ConversionError::TemporalOutOfRange(Value::Duration(duration)).into()),
+        }
+    }
+}
+
+// ------------------------- Into Value -------------------------
+
+impl From<NaiveDate> for Value {
+    fn from(value: NaiveDate) -> Self {
+        Value::Date(Date::from(value))
+    }
+}
+
+// No timezone-aware time in chrono, so provide a separate conversion
+impl<O: Offset> From<(NaiveTime, O)> for Value {
+    fn from(pair: (NaiveTime, O)) -> Self {
+        Value::Time(Time::from(pair))
+    }
+}
+
+impl<T: TimeZone> From<DateTime<T>> for Value {
+    fn from(value: DateTime<T>) -> Self {
+        Value::DateTimeOffset(DateTimeOffset::from(value))
+    }
+}
+
+// Can't decide between Offset or Zoned variant at runtime if using a T: TimeZone, so
+// provide a separate conversion
+impl From<(NaiveDateTime, chrono_tz::Tz)> for Value {
+    fn from(pair: (NaiveDateTime, chrono_tz::Tz)) -> Self {
+        Value::DateTimeZoned(DateTimeZoned::from(pair))
+    }
+}
+
+impl From<NaiveTime> for Value {
+    fn from(value: NaiveTime) -> Self {
+        Value::LocalTime(LocalTime::from(value))
+    }
+}
+
+impl From<NaiveDateTime> for Value {
+    fn from(value: NaiveDateTime) -> Self {
+        Value::LocalDateTime(LocalDateTime::from(value))
+    }
+}
+
+impl From<chrono::Duration> for Value {
+    fn from(value: chrono::Duration) -> Self {
+        Value::Duration(Duration::from(value))
+    }
+}
+
+// ------------------------- From Value -------------------------
+
+impl TryFrom<Value> for NaiveDate {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Date(ref date) => chrono::Duration::try_days(date.days_since_epoch)
+                .and_then(|days| {
+                    NaiveDate::from_ymd_opt(1970, 1, 1)
+                        .unwrap()
+                        .checked_add_signed(days)
+                })
+                .ok_or_else(|| ConversionError::TemporalOutOfRange(value).into()),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+impl TryFrom<Value> for DateTime<FixedOffset> {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let date_time = match value {
+            Value::DateTimeOffset(ref date_time_offset) => {
+                // Legacy date-times hold seconds since the epoch in local time
+                FixedOffset::east_opt(date_time_offset.offset_seconds).and_then(|offset| {
+                    DateTime::from_timestamp(
+                        date_time_offset.epoch_seconds,
+                        date_time_offset.nanos as u32,
+                    )
+                    .and_then(|local| offset.from_local_datetime(&local.naive_utc()).single())
+                })
+            }
+            Value::DateTimeOffsetUtc(ref date_time_offset) => {
+                FixedOffset::east_opt(date_time_offset.offset_seconds).and_then(|offset| {
+                    offset
+                        .timestamp_opt(
+                            date_time_offset.epoch_seconds,
+                            date_time_offset.nanos as u32,
+                        )
+                        .single()
+                })
+            }
+            Value::DateTimeZoned(_) | Value::DateTimeZonedUtc(_) => {
+                return DateTime::<Tz>::try_from(value)
+                    .map(|date_time| date_time.with_timezone(&date_time.offset().fix()));
+            }
+            _ => return Err(ConversionError::FromValue(value).into()),
+        };
+        date_time.ok_or_else(|| ConversionError::TemporalOutOfRange(value).into())
+    }
+}
+
+impl TryFrom<Value> for DateTime<Tz> {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let date_time = match value {
+            // Local times repeated when clocks are turned back are ambiguous in the legacy
+            // encoding, and resolve to the earliest instant
+            Value::DateTimeZoned(ref date_time_zoned) => {
+                let timezone = parse_zone(&date_time_zoned.zone_id)?;
+                DateTime::from_timestamp(
+                    date_time_zoned.epoch_seconds,
+                    date_time_zoned.nanos as u32,
+                )
+                .and_then(|local| timezone.from_local_datetime(&local.naive_utc()).earliest())
+            }
+            Value::DateTimeZonedUtc(ref date_time_zoned) => parse_zone(&date_time_zoned.zone_id)?
+                .timestamp_opt(date_time_zoned.epoch_seconds, date_time_zoned.nanos as u32)
+                .single(),
+            _ => return Err(ConversionError::FromValue(value).into()),
+        };
+        date_time.ok_or_else(|| ConversionError::TemporalOutOfRange(value).into())
+    }
+}
+
+impl TryFrom<Value> for NaiveTime {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::LocalTime(ref local_time)
+                if (0..NANOS_PER_DAY).contains(&local_time.nanos_since_midnight) =>
+            {
+                let seconds = (local_time.nanos_since_midnight / 1_000_000_000) as u32;
+                let nanos = (local_time.nanos_since_midnight % 1_000_000_000) as u32;
+                NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanos)
+                    .ok_or_else(|| ConversionError::TemporalOutOfRange(value).into())
+            }
+            Value::LocalTime(_) => Err(ConversionError::TemporalOutOfRange(value).into()),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+impl TryFrom<Value> for NaiveDateTime {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::LocalDateTime(ref local_date_time) => DateTime::from_timestamp(
+                local_date_time.epoch_seconds,
+                local_date_time.nanos as u32,
+            )
+            .map(|date_time| date_time.naive_utc())
+            .ok_or_else(|| ConversionError::TemporalOutOfRange(value).into()),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+impl TryFrom<Value> for chrono::Duration {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Duration(duration) => chrono::Duration::try_from(duration),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::convert::TryFrom;
+
+use geo_types::Point;
+
+use crate::error::*;
+use crate::value::{Crs, Point2D};
+use crate::Value;
+
+// Points from geo-types don't carry a coordinate reference system, so they are taken to be
+// cartesian. Use Point2D::try_new to create a geographic point from longitude and latitude.
+impl From<Point<f64>> for Point2D {
+    fn from(point: Point<f64>) -> Self {
+        Self::new(Crs::Cartesian.srid(), point.x(), point.y())
+    }
+}
+
+impl From<Point2D> for Point<f64> {
+    fn from(point: Point2D) -> Self {
+        Point::new(point.x, point.y)
+    }
+}
+
+impl From<Point<f64>> for Value {
+    fn from(value: Point<f64>) -> Self {
+        Value::Point2D(Point2D::from(value))
+    }
+}
+
+impl TryFrom<Value> for Point<f64> {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Point2D(point) => Ok(Point::from(point)),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn point() {
+        let value = Value::from(Point::new(1.5, -2.25));
+        assert_eq!(value, Value::Point2D(Point2D::new(7203, 1.5, -2.25)));
+        assert_eq!(Point::try_from(value).unwrap(), Point::new(1.5, -2.25));
+
+        let geographic = Point2D::try_new(Crs::Wgs84, 12.78, 56.7).unwrap();
+        assert_eq!(
+            Point::try_from(Value::from(geographic)).unwrap(),
+            Point::new(12.78, 56.7)
+        );
+        assert!(matches!(
+            Point::<f64>::try_from(Value::from(1)),
+            Err(Error::ConversionError(ConversionError::FromValue(_)))
+        ));
+    }
+}
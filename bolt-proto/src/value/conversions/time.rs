@@ -0,0 +1,376 @@
+use std::convert::TryFrom;
+
+use time::{OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+use crate::error::*;
+use crate::value::{
+    Date, DateTimeOffset, DateTimeOffsetUtc, Duration, LocalDateTime, LocalTime, Time,
+    NANOS_PER_DAY,
+};
+use crate::Value;
+
+// Julian day number of 1970-01-01
+const UNIX_EPOCH_JULIAN_DAY: i64 = 2_440_588;
+
+fn nanos_since_midnight(time: time::Time) -> i64 {
+    let (hour, minute, second, nanosecond) = time.as_hms_nano();
+    (hour as i64 * 3600 + minute as i64 * 60 + second as i64) * 1_000_000_000 + nanosecond as i64
+}
+
+fn time_from_nanos(nanos_since_midnight: i64) -> Option<time::Time> {
+    if !(0..NANOS_PER_DAY).contains(&nanos_since_midnight) {
+        return None;
+    }
+    let seconds = nanos_since_midnight / 1_000_000_000;
+    time::Time::from_hms_nano(
+        (seconds / 3600) as u8,
+        (seconds / 60 % 60) as u8,
+        (seconds % 60) as u8,
+        (nanos_since_midnight % 1_000_000_000) as u32,
+    )
+    .ok()
+}
+
+// Seconds since the epoch in local time, as held by legacy date-times and local date-times
+fn local_seconds(date_time: PrimitiveDateTime) -> i64 {
+    date_time.assume_utc().unix_timestamp()
+}
+
+fn from_local_seconds(epoch_seconds: i64, nanos: i64) -> Option<PrimitiveDateTime> {
+    let date_time = OffsetDateTime::from_unix_timestamp(epoch_seconds)
+        .ok()?
+        .replace_nanosecond(u32::try_from(nanos).ok()?)
+        .ok()?;
+    Some(PrimitiveDateTime::new(date_time.date(), date_time.time()))
+}
+
+impl From<time::Date> for Date {
+    fn from(date: time::Date) -> Self {
+        Self {
+            days_since_epoch: date.to_julian_day() as i64 - UNIX_EPOCH_JULIAN_DAY,
+        }
+    }
+}
+
+// No timezone-aware time in the time crate, so provide separate conversion instead. The time
+// crate allows offsets of up to 25:59:59, which Bolt does not, so these conversions can fail.
+impl TryFrom<(time::Time, UtcOffset)> for Time {
+    type Error = Error;
+
+    fn try_from(pair: (time::Time, UtcOffset)) -> Result<Self> {
+        let time = Self {
+            nanos_since_midnight: nanos_since_midnight(pair.0),
+            zone_offset: pair.1.whole_seconds(),
+        };
+        time.validate()?;
+        Ok(time)
+    }
+}
+
+impl TryFrom<OffsetDateTime> for DateTimeOffset {
+    type Error = Error;
+
+    fn try_from(date_time: OffsetDateTime) -> Result<Self> {
+        let date_time = Self {
+            // Legacy date-times hold seconds since the epoch in local time
+            epoch_seconds: local_seconds(PrimitiveDateTime::new(
+                date_time.date(),
+                date_time.time(),
+            )),
+            nanos: date_time.nanosecond() as i64,
+            offset_seconds: date_time.offset().whole_seconds(),
+        };
+        date_time.validate()?;
+        Ok(date_time)
+    }
+}
+
+impl TryFrom<OffsetDateTime> for DateTimeOffsetUtc {
+    type Error = Error;
+
+    fn try_from(date_time: OffsetDateTime) -> Result<Self> {
+        let date_time = Self {
+            epoch_seconds: date_time.unix_timestamp(),
+            nanos: date_time.nanosecond() as i64,
+            offset_seconds: date_time.offset().whole_seconds(),
+        };
+        date_time.validate()?;
+        Ok(date_time)
+    }
+}
+
+impl From<time::Time> for LocalTime {
+    fn from(time: time::Time) -> Self {
+        Self {
+            nanos_since_midnight: nanos_since_midnight(time),
+        }
+    }
+}
+
+impl From<PrimitiveDateTime> for LocalDateTime {
+    fn from(date_time: PrimitiveDateTime) -> Self {
+        Self {
+            epoch_seconds: local_seconds(date_time),
+            nanos: date_time.nanosecond() as i64,
+        }
+    }
+}
+
+impl From<time::Duration> for Duration {
+    fn from(duration: time::Duration) -> Self {
+        // Months are not well-defined in terms of seconds so let's not use them here. The
+        // nanoseconds of negative durations are negative, so they are carried into the seconds,
+        // which can't overflow since they are less than a day.
+        Self::checked_new(
+            0,
+            duration.whole_seconds() / (3600 * 24),
+            duration.whole_seconds() % (3600 * 24),
+            duration.subsec_nanoseconds() as i64,
+        )
+        .unwrap()
+    }
+}
+
+// Durations with months cannot be converted, since months are not well-defined in terms of
+// seconds.
+impl TryFrom<Duration> for time::Duration {
+    type Error = Error;
+
+    fn try_from(duration: Duration) -> Result<Self> {
+        if duration.months != 0 {
+            return Err(ConversionError::FromValue(Value::Duration(duration)).into());
+        }
+        match duration.total_seconds().and_then(|seconds| {
+            time::Duration::seconds(seconds)
+                .checked_add(time::Duration::nanoseconds(duration.nanos as i64))
+        }) {
+            Some(duration) => Ok(duration),
+            None => Err(ConversionError::TemporalOutOfRange(Value::Duration(duration)).into()),
+        }
+    }
+}
+
+// ------------------------- Into Value -------------------------
+
+impl From<time::Date> for Value {
+    fn from(value: time::Date) -> Self {
+        Value::Date(Date::from(value))
+    }
+}
+
+impl TryFrom<(time::Time, UtcOffset)> for Value {
+    type Error = Error;
+
+    fn try_from(pair: (time::Time, UtcOffset)) -> Result<Self> {
+        Time::try_from(pair).map(Value::Time)
+    }
+}
+
+impl TryFrom<OffsetDateTime> for Value {
+    type Error = Error;
+
+    fn try_from(value: OffsetDateTime) -> Result<Self> {
+        DateTimeOffset::try_from(value).map(Value::DateTimeOffset)
+    }
+}
+
+impl From<time::Time> for Value {
+    fn from(value: time::Time) -> Self {
+        Value::LocalTime(LocalTime::from(value))
+    }
+}
+
+impl From<PrimitiveDateTime> for Value {
+    fn from(value: PrimitiveDateTime) -> Self {
+        Value::LocalDateTime(LocalDateTime::from(value))
+    }
+}
+
+impl From<time::Duration> for Value {
+    fn from(value: time::Duration) -> Self {
+        Value::Duration(Duration::from(value))
+    }
+}
+
+// ------------------------- From Value -------------------------
+
+impl TryFrom<Value> for time::Date {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Date(ref date) => date
+                .days_since_epoch
+                .checked_add(UNIX_EPOCH_JULIAN_DAY)
+                .and_then(|julian_day| i32::try_from(julian_day).ok())
+                .and_then(|julian_day| time::Date::from_julian_day(julian_day).ok())
+                .ok_or_else(|| ConversionError::TemporalOutOfRange(value).into()),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+impl TryFrom<Value> for (time::Time, UtcOffset) {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Time(ref time) => time_from_nanos(time.nanos_since_midnight)
+                .zip(UtcOffset::from_whole_seconds(time.zone_offset).ok())
+                .ok_or_else(|| ConversionError::TemporalOutOfRange(value).into()),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+// Date-times with a time zone ID cannot be converted, since the time crate has no time zone
+// database.
+impl TryFrom<Value> for OffsetDateTime {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        let (local_seconds, nanos, offset_seconds) = match value {
+            Value::DateTimeOffset(ref date_time_offset) => (
+                Some(date_time_offset.epoch_seconds),
+                date_time_offset.nanos,
+                date_time_offset.offset_seconds,
+            ),
+            Value::DateTimeOffsetUtc(ref date_time_offset) => (
+                date_time_offset
+                    .epoch_seconds
+                    .checked_add(date_time_offset.offset_seconds as i64),
+                date_time_offset.nanos,
+                date_time_offset.offset_seconds,
+            ),
+            _ => return Err(ConversionError::FromValue(value).into()),
+        };
+        local_seconds
+            .and_then(|local_seconds| from_local_seconds(local_seconds, nanos))
+            .zip(UtcOffset::from_whole_seconds(offset_seconds).ok())
+            .map(|(local, offset)| local.assume_offset(offset))
+            .ok_or_else(|| ConversionError::TemporalOutOfRange(value).into())
+    }
+}
+
+impl TryFrom<Value> for time::Time {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::LocalTime(ref local_time) => time_from_nanos(local_time.nanos_since_midnight)
+                .ok_or_else(|| ConversionError::TemporalOutOfRange(value).into()),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+impl TryFrom<Value> for PrimitiveDateTime {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::LocalDateTime(ref local_date_time) => {
+                from_local_seconds(local_date_time.epoch_seconds, local_date_time.nanos)
+                    .ok_or_else(|| ConversionError::TemporalOutOfRange(value).into())
+            }
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+impl TryFrom<Value> for time::Duration {
+    type Error = Error;
+
+    fn try_from(value: Value) -> Result<Self> {
+        match value {
+            Value::Duration(duration) => time::Duration::try_from(duration),
+            _ => Err(ConversionError::FromValue(value).into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{date, datetime, offset, time};
+
+    use super::*;
+
+    #[test]
+    fn date() {
+        let value = Value::from(date!(2020 - 12 - 25));
+        assert_eq!(
+            value,
+            Value::Date(Date {
+                days_since_epoch: 18_621
+            })
+        );
+        assert_eq!(time::Date::try_from(value).unwrap(), date!(2020 - 12 - 25));
+        assert!(matches!(
+            time::Date::try_from(Value::Date(Date {
+                days_since_epoch: i64::MAX
+            })),
+            Err(Error::ConversionError(ConversionError::TemporalOutOfRange(
+                _
+            )))
+        ));
+    }
+
+    #[test]
+    fn times() {
+        let value = Value::try_from((time!(16:04:35.000_000_235), offset!(-8))).unwrap();
+        assert_eq!(
+            <(time::Time, UtcOffset)>::try_from(value).unwrap(),
+            (time!(16:04:35.000_000_235), offset!(-8))
+        );
+        assert!(matches!(
+            Value::try_from((time!(16:04:35), offset!(+25))),
+            Err(Error::ConversionError(ConversionError::TemporalOutOfRange(
+                _
+            )))
+        ));
+
+        let value = Value::from(time!(23:59:59.999_999_999));
+        assert_eq!(
+            time::Time::try_from(value).unwrap(),
+            time!(23:59:59.999_999_999)
+        );
+    }
+
+    #[test]
+    fn date_times() {
+        let local = datetime!(1999-02-27 01:00:00.000_009_999);
+        assert_eq!(
+            PrimitiveDateTime::try_from(Value::from(local)).unwrap(),
+            local
+        );
+
+        let date_time = datetime!(2050-12-31 23:59:59.000_000_010 -5);
+        let legacy = Value::try_from(date_time).unwrap();
+        assert_eq!(OffsetDateTime::try_from(legacy.clone()).unwrap(), date_time);
+        let utc = legacy.convert_date_times(true).unwrap();
+        assert_eq!(
+            utc,
+            Value::DateTimeOffsetUtc(DateTimeOffsetUtc::try_from(date_time).unwrap())
+        );
+        assert_eq!(OffsetDateTime::try_from(utc).unwrap(), date_time);
+
+        let out_of_range = datetime!(2050-12-31 23:59:59 -25);
+        assert!(DateTimeOffset::try_from(out_of_range).is_err());
+        assert!(DateTimeOffsetUtc::try_from(out_of_range).is_err());
+    }
+
+    #[test]
+    fn duration() {
+        let duration = time::Duration::new(-90_061, -5);
+        let value = Value::from(duration);
+        assert_eq!(
+            value,
+            Value::Duration(Duration::new(0, -1, -3662, 999_999_995))
+        );
+        assert_eq!(time::Duration::try_from(value).unwrap(), duration);
+        assert!(matches!(
+            time::Duration::try_from(Value::Duration(Duration::new(1, 0, 0, 0))),
+            Err(Error::ConversionError(ConversionError::FromValue(_)))
+        ));
+    }
+}
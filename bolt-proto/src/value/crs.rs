@@ -0,0 +1,133 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::error::*;
+
+// Radius of the Earth used by Neo4j when computing distances between geographic points
+const EARTH_RADIUS_METERS: f64 = 6_378_140.0;
+
+/// A coordinate reference system supported by Neo4j, identified in points by its SRID.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Crs {
+    /// Geographic coordinates as longitude and latitude in degrees (SRID 4326).
+    Wgs84,
+    /// Geographic coordinates as longitude and latitude in degrees, and height in meters (SRID 4979).
+    Wgs84_3D,
+    /// Euclidean coordinates in two dimensions (SRID 7203).
+    Cartesian,
+    /// Euclidean coordinates in three dimensions (SRID 9157).
+    Cartesian3D,
+}
+
+impl Crs {
+    pub fn srid(&self) -> i32 {
+        match self {
+            Crs::Wgs84 => 4326,
+            Crs::Wgs84_3D => 4979,
+            Crs::Cartesian => 7203,
+            Crs::Cartesian3D => 9157,
+        }
+    }
+
+    /// The name Neo4j uses for this coordinate reference system, e.g. `wgs-84-3d`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Crs::Wgs84 => "wgs-84",
+            Crs::Wgs84_3D => "wgs-84-3d",
+            Crs::Cartesian => "cartesian",
+            Crs::Cartesian3D => "cartesian-3d",
+        }
+    }
+
+    pub fn dimension(&self) -> usize {
+        match self {
+            Crs::Wgs84 | Crs::Cartesian => 2,
+            Crs::Wgs84_3D | Crs::Cartesian3D => 3,
+        }
+    }
+
+    pub fn is_geographic(&self) -> bool {
+        matches!(self, Crs::Wgs84 | Crs::Wgs84_3D)
+    }
+
+    // Checks coordinates the same way Neo4j does when creating a point
+    pub(crate) fn validate(&self, coordinates: &[f64]) -> bool {
+        if coordinates.len() != self.dimension() || coordinates.iter().any(|c| !c.is_finite()) {
+            return false;
+        }
+        !self.is_geographic() || (-90.0..=90.0).contains(&coordinates[1])
+    }
+
+    // Distance between two points in this coordinate reference system, in the same way as
+    // Neo4j's point.distance: Euclidean for cartesian points, and the haversine formula for
+    // geographic points. For 3D points, the great-circle distance is taken at the average
+    // height of the points, and combined with the difference in height as Euclidean.
+    pub(crate) fn distance(&self, from: &[f64], to: &[f64]) -> f64 {
+        if !self.is_geographic() {
+            return from
+                .iter()
+                .zip(to)
+                .map(|(a, b)| (b - a).powi(2))
+                .sum::<f64>()
+                .sqrt();
+        }
+
+        let (from_long, from_lat) = (from[0].to_radians(), from[1].to_radians());
+        let (to_long, to_lat) = (to[0].to_radians(), to[1].to_radians());
+        let alpha = ((to_lat - from_lat) / 2.0).sin().powi(2)
+            + from_lat.cos() * to_lat.cos() * ((to_long - from_long) / 2.0).sin().powi(2);
+        let angle = 2.0 * alpha.sqrt().atan2((1.0 - alpha).sqrt());
+        match (from.get(2), to.get(2)) {
+            (Some(from_height), Some(to_height)) => {
+                let radius = EARTH_RADIUS_METERS + (from_height + to_height) / 2.0;
+                ((radius * angle).powi(2) + (to_height - from_height).powi(2)).sqrt()
+            }
+            _ => EARTH_RADIUS_METERS * angle,
+        }
+    }
+}
+
+impl TryFrom<i32> for Crs {
+    type Error = Error;
+
+    fn try_from(srid: i32) -> Result<Self> {
+        match srid {
+            4326 => Ok(Crs::Wgs84),
+            4979 => Ok(Crs::Wgs84_3D),
+            7203 => Ok(Crs::Cartesian),
+            9157 => Ok(Crs::Cartesian3D),
+            _ => Err(ConversionError::UnknownCrs(srid).into()),
+        }
+    }
+}
+
+impl fmt::Display for Crs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn srid() {
+        for crs in &[Crs::Wgs84, Crs::Wgs84_3D, Crs::Cartesian, Crs::Cartesian3D] {
+            assert_eq!(Crs::try_from(crs.srid()).unwrap(), *crs);
+        }
+        assert!(matches!(
+            Crs::try_from(120),
+            Err(Error::ConversionError(ConversionError::UnknownCrs(120)))
+        ));
+    }
+
+    #[test]
+    fn validate() {
+        assert!(Crs::Wgs84.validate(&[-180.0, 90.0]));
+        assert!(!Crs::Wgs84.validate(&[0.0, 90.5]));
+        assert!(!Crs::Wgs84.validate(&[0.0, 0.0, 0.0]));
+        assert!(Crs::Cartesian3D.validate(&[1.0, 1000.0, -5.0]));
+        assert!(!Crs::Cartesian.validate(&[f64::NAN, 0.0]));
+    }
+}
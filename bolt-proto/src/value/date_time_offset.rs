@@ -1,34 +1,39 @@
-use chrono::{DateTime, Offset, TimeZone, Timelike};
-
 use bolt_proto_derive::*;
 
+use crate::error::*;
+use crate::Value;
+
 pub(crate) const MARKER: u8 = 0xB3;
 pub(crate) const SIGNATURE: u8 = 0x46;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[validate]
 pub struct DateTimeOffset {
     pub(crate) epoch_seconds: i64,
     pub(crate) nanos: i64,
     pub(crate) offset_seconds: i32,
 }
 
-impl<T: TimeZone> From<DateTime<T>> for DateTimeOffset {
-    fn from(date_time: DateTime<T>) -> Self {
-        Self {
-            epoch_seconds: date_time.timestamp(),
-            nanos: date_time.nanosecond() as i64,
-            offset_seconds: date_time.offset().fix().local_minus_utc(),
+impl DateTimeOffset {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(0..1_000_000_000).contains(&self.nanos)
+            || !(-86_399..=86_399).contains(&self.offset_seconds)
+        {
+            return Err(
+                ConversionError::TemporalOutOfRange(Value::DateTimeOffset(self.clone())).into(),
+            );
         }
+        Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use std::convert::TryFrom;
     use std::sync::{Arc, Mutex};
 
     use bytes::Bytes;
-    use chrono::{DateTime, FixedOffset, NaiveDateTime};
+    use chrono::{DateTime, FixedOffset};
 
     use crate::serialization::*;
     use crate::value::integer::MARKER_INT_16;
@@ -36,9 +41,9 @@ mod tests {
     use super::*;
 
     fn get_chrono_date_time() -> DateTime<FixedOffset> {
-        DateTime::from_utc(
-            NaiveDateTime::from_timestamp(2000, 1000),
-            FixedOffset::east(-1200),
+        DateTime::from_naive_utc_and_offset(
+            DateTime::from_timestamp(2000, 1000).unwrap().naive_utc(),
+            FixedOffset::east_opt(-1200).unwrap(),
         )
     }
 
@@ -57,8 +62,8 @@ mod tests {
                 MARKER,
                 SIGNATURE,
                 MARKER_INT_16,
-                0x07,
-                0xD0,
+                0x03,
+                0x20,
                 MARKER_INT_16,
                 0x03,
                 0xE8,
@@ -74,8 +79,8 @@ mod tests {
         let date_time_offset = DateTimeOffset::from(get_chrono_date_time());
         let date_time_bytes = &[
             MARKER_INT_16,
-            0x07,
-            0xD0,
+            0x03,
+            0x20,
             MARKER_INT_16,
             0x03,
             0xE8,
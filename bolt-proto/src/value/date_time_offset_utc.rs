@@ -0,0 +1,144 @@
+use std::convert::TryFrom;
+
+use bolt_proto_derive::*;
+
+use crate::error::*;
+use crate::value::DateTimeOffset;
+use crate::Value;
+
+pub(crate) const MARKER: u8 = 0xB3;
+pub(crate) const SIGNATURE: u8 = 0x49;
+
+// Unlike DateTimeOffset, which holds seconds since the epoch in local time, this holds
+// seconds since the epoch in UTC. Sent by servers supporting Bolt v5.0 or later, or Bolt
+// v4.3/v4.4 with the "utc" patch.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[validate]
+pub struct DateTimeOffsetUtc {
+    pub(crate) epoch_seconds: i64,
+    pub(crate) nanos: i64,
+    pub(crate) offset_seconds: i32,
+}
+
+impl DateTimeOffsetUtc {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(0..1_000_000_000).contains(&self.nanos)
+            || !(-86_399..=86_399).contains(&self.offset_seconds)
+        {
+            return Err(
+                ConversionError::TemporalOutOfRange(Value::DateTimeOffsetUtc(self.clone())).into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<DateTimeOffset> for DateTimeOffsetUtc {
+    type Error = Error;
+
+    fn try_from(legacy: DateTimeOffset) -> Result<Self> {
+        match legacy
+            .epoch_seconds
+            .checked_sub(legacy.offset_seconds as i64)
+        {
+            Some(epoch_seconds) => Ok(Self {
+                epoch_seconds,
+                nanos: legacy.nanos,
+                offset_seconds: legacy.offset_seconds,
+            }),
+            None => Err(ConversionError::TemporalOutOfRange(Value::DateTimeOffset(legacy)).into()),
+        }
+    }
+}
+
+impl TryFrom<DateTimeOffsetUtc> for DateTimeOffset {
+    type Error = Error;
+
+    fn try_from(utc: DateTimeOffsetUtc) -> Result<Self> {
+        match utc.epoch_seconds.checked_add(utc.offset_seconds as i64) {
+            Some(epoch_seconds) => Ok(Self {
+                epoch_seconds,
+                nanos: utc.nanos,
+                offset_seconds: utc.offset_seconds,
+            }),
+            None => Err(ConversionError::TemporalOutOfRange(Value::DateTimeOffsetUtc(utc)).into()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+    use chrono::{DateTime, FixedOffset};
+
+    use crate::serialization::*;
+    use crate::value::integer::MARKER_INT_16;
+
+    use super::*;
+
+    fn get_chrono_date_time() -> DateTime<FixedOffset> {
+        DateTime::from_naive_utc_and_offset(
+            DateTime::from_timestamp(2000, 1000).unwrap().naive_utc(),
+            FixedOffset::east_opt(-1200).unwrap(),
+        )
+    }
+
+    #[test]
+    fn get_marker() {
+        let date_time = DateTimeOffsetUtc::from(get_chrono_date_time());
+        assert_eq!(date_time.get_marker().unwrap(), MARKER);
+    }
+
+    #[test]
+    fn try_into_bytes() {
+        let date_time = DateTimeOffsetUtc::from(get_chrono_date_time());
+        assert_eq!(
+            date_time.try_into_bytes().unwrap(),
+            Bytes::from_static(&[
+                MARKER,
+                SIGNATURE,
+                MARKER_INT_16,
+                0x07,
+                0xD0,
+                MARKER_INT_16,
+                0x03,
+                0xE8,
+                MARKER_INT_16,
+                0xFB,
+                0x50,
+            ])
+        );
+    }
+
+    #[test]
+    fn try_from_bytes() {
+        let date_time = DateTimeOffsetUtc::from(get_chrono_date_time());
+        let date_time_bytes = &[
+            MARKER_INT_16,
+            0x07,
+            0xD0,
+            MARKER_INT_16,
+            0x03,
+            0xE8,
+            MARKER_INT_16,
+            0xFB,
+            0x50,
+        ];
+        assert_eq!(
+            DateTimeOffsetUtc::try_from(Arc::new(Mutex::new(Bytes::from_static(date_time_bytes))))
+                .unwrap(),
+            date_time
+        );
+    }
+
+    #[test]
+    fn legacy() {
+        let date_time = DateTimeOffsetUtc::from(get_chrono_date_time());
+        let legacy = DateTimeOffset::try_from(date_time.clone()).unwrap();
+        assert_eq!(legacy, DateTimeOffset::from(get_chrono_date_time()));
+        assert_eq!(legacy.epoch_seconds, 800);
+        assert_eq!(DateTimeOffsetUtc::try_from(legacy).unwrap(), date_time);
+    }
+}
@@ -1,31 +1,41 @@
-use chrono::{NaiveDateTime, Timelike};
-use chrono_tz::Tz;
-
 use bolt_proto_derive::*;
 
+use crate::error::*;
+use crate::Value;
+
 pub(crate) const MARKER: u8 = 0xB3;
 pub(crate) const SIGNATURE: u8 = 0x66;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[validate]
 pub struct DateTimeZoned {
     pub(crate) epoch_seconds: i64,
     pub(crate) nanos: i64,
     pub(crate) zone_id: String,
 }
 
-// Can't impl<T: TimeZone> From<DateTime<T>> for DateTimeZoned, since we can't get a
-// timezone name from an Offset. Provide separate conversion instead
-impl From<(NaiveDateTime, Tz)> for DateTimeZoned {
-    fn from(pair: (NaiveDateTime, Tz)) -> Self {
-        Self {
-            epoch_seconds: pair.0.timestamp(),
-            nanos: pair.0.nanosecond() as i64,
-            zone_id: pair.1.name().to_string(),
+impl DateTimeZoned {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(0..1_000_000_000).contains(&self.nanos) {
+            return Err(
+                ConversionError::TemporalOutOfRange(Value::DateTimeZoned(self.clone())).into(),
+            );
         }
+        Ok(())
     }
 }
 
-#[cfg(test)]
+// Look up a time zone by ID. Zone IDs are only checked when converting to or from the
+// UTC encoding or chrono types, since decoding must not depend on the time zone database
+// provided with the `chrono` feature.
+#[cfg(feature = "chrono")]
+pub(crate) fn parse_zone(zone_id: &str) -> Result<chrono_tz::Tz> {
+    zone_id
+        .parse()
+        .map_err(|_| ConversionError::UnknownTimeZone(zone_id.to_string()).into())
+}
+
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use std::convert::TryFrom;
     use std::sync::{Arc, Mutex};
@@ -41,7 +51,10 @@ mod tests {
 
     fn get_date_time() -> DateTimeZoned {
         DateTimeZoned::from((
-            NaiveDate::from_ymd(3500, 7, 29).and_hms_nano(13, 5, 1, 123_456),
+            NaiveDate::from_ymd_opt(3500, 7, 29)
+                .unwrap()
+                .and_hms_nano_opt(13, 5, 1, 123_456)
+                .unwrap(),
             chrono_tz::Antarctica::Rothera,
         ))
     }
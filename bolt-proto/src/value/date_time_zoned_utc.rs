@@ -0,0 +1,203 @@
+#[cfg(feature = "chrono")]
+use std::convert::TryFrom;
+
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, TimeZone};
+
+use bolt_proto_derive::*;
+
+use crate::error::*;
+#[cfg(feature = "chrono")]
+use crate::value::{date_time_zoned::parse_zone, DateTimeZoned};
+use crate::Value;
+
+pub(crate) const MARKER: u8 = 0xB3;
+pub(crate) const SIGNATURE: u8 = 0x69;
+
+// Unlike DateTimeZoned, which holds seconds since the epoch in local time and so cannot
+// tell apart the repeated hour at the end of daylight saving time, this holds seconds since
+// the epoch in UTC. Sent by servers supporting Bolt v5.0 or later, or Bolt v4.3/v4.4 with
+// the "utc" patch.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[validate]
+pub struct DateTimeZonedUtc {
+    pub(crate) epoch_seconds: i64,
+    pub(crate) nanos: i64,
+    pub(crate) zone_id: String,
+}
+
+impl DateTimeZonedUtc {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(0..1_000_000_000).contains(&self.nanos) {
+            return Err(
+                ConversionError::TemporalOutOfRange(Value::DateTimeZonedUtc(self.clone())).into(),
+            );
+        }
+        Ok(())
+    }
+}
+
+// Local times repeated when clocks are turned back are resolved to the earliest instant,
+// and local times skipped when clocks are turned forward cannot be converted.
+#[cfg(feature = "chrono")]
+impl TryFrom<DateTimeZoned> for DateTimeZonedUtc {
+    type Error = Error;
+
+    fn try_from(legacy: DateTimeZoned) -> Result<Self> {
+        let timezone = parse_zone(&legacy.zone_id)?;
+        let date_time = DateTime::from_timestamp(legacy.epoch_seconds, 0)
+            .and_then(|local| timezone.from_local_datetime(&local.naive_utc()).earliest());
+        match date_time {
+            Some(date_time) => Ok(Self {
+                epoch_seconds: date_time.timestamp(),
+                nanos: legacy.nanos,
+                zone_id: legacy.zone_id,
+            }),
+            None => Err(ConversionError::TemporalOutOfRange(Value::DateTimeZoned(legacy)).into()),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<DateTimeZonedUtc> for DateTimeZoned {
+    type Error = Error;
+
+    fn try_from(utc: DateTimeZonedUtc) -> Result<Self> {
+        let timezone = parse_zone(&utc.zone_id)?;
+        match timezone.timestamp_opt(utc.epoch_seconds, 0).single() {
+            Some(date_time) => Ok(Self {
+                epoch_seconds: date_time.naive_local().and_utc().timestamp(),
+                nanos: utc.nanos,
+                zone_id: utc.zone_id,
+            }),
+            None => Err(ConversionError::TemporalOutOfRange(Value::DateTimeZonedUtc(utc)).into()),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use bytes::Bytes;
+    use chrono::NaiveDate;
+    use chrono_tz::Tz;
+
+    use crate::serialization::*;
+    use crate::value::integer::MARKER_INT_32;
+    use crate::value::string;
+
+    use super::*;
+
+    // 01:30 on the day clocks are turned back in New York, which happens twice
+    fn get_date_times() -> (DateTime<Tz>, DateTime<Tz>) {
+        let local = NaiveDate::from_ymd_opt(2021, 11, 7)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+        match chrono_tz::America::New_York.from_local_datetime(&local) {
+            chrono::LocalResult::Ambiguous(earliest, latest) => (earliest, latest),
+            other => panic!("expected an ambiguous local time, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn get_marker() {
+        let date_time = DateTimeZonedUtc::from(get_date_times().0);
+        assert_eq!(date_time.get_marker().unwrap(), MARKER);
+    }
+
+    #[test]
+    fn try_into_bytes() {
+        let date_time = DateTimeZonedUtc::from(get_date_times().1);
+        assert_eq!(
+            date_time.try_into_bytes().unwrap(),
+            Bytes::from_static(&[
+                MARKER,
+                SIGNATURE,
+                MARKER_INT_32,
+                0x61,
+                0x87,
+                0x72,
+                0x68,
+                0x00,
+                string::MARKER_SMALL,
+                16,
+                b'A',
+                b'm',
+                b'e',
+                b'r',
+                b'i',
+                b'c',
+                b'a',
+                b'/',
+                b'N',
+                b'e',
+                b'w',
+                b'_',
+                b'Y',
+                b'o',
+                b'r',
+                b'k',
+            ])
+        );
+    }
+
+    #[test]
+    fn try_from_bytes() {
+        let date_time = DateTimeZonedUtc::from(get_date_times().1);
+        let date_time_bytes = &[
+            MARKER_INT_32,
+            0x61,
+            0x87,
+            0x72,
+            0x68,
+            0x00,
+            string::MARKER_SMALL,
+            16,
+            b'A',
+            b'm',
+            b'e',
+            b'r',
+            b'i',
+            b'c',
+            b'a',
+            b'/',
+            b'N',
+            b'e',
+            b'w',
+            b'_',
+            b'Y',
+            b'o',
+            b'r',
+            b'k',
+        ];
+        assert_eq!(
+            DateTimeZonedUtc::try_from(Arc::new(Mutex::new(Bytes::from_static(date_time_bytes))))
+                .unwrap(),
+            date_time
+        );
+    }
+
+    #[test]
+    fn legacy() {
+        let (earliest, latest) = get_date_times();
+        let earliest = DateTimeZonedUtc::from(earliest);
+        let latest = DateTimeZonedUtc::from(latest);
+
+        // Both instants have the same local time, so the legacy encoding is identical
+        let legacy = DateTimeZoned::try_from(latest).unwrap();
+        assert_eq!(legacy, DateTimeZoned::try_from(earliest.clone()).unwrap());
+        assert_eq!(DateTimeZonedUtc::try_from(legacy).unwrap(), earliest);
+
+        let unknown_zone = DateTimeZoned {
+            epoch_seconds: 0,
+            nanos: 0,
+            zone_id: "Mars/Olympus_Mons".to_string(),
+        };
+        assert!(matches!(
+            DateTimeZonedUtc::try_from(unknown_zone),
+            Err(Error::ConversionError(ConversionError::UnknownTimeZone(_)))
+        ));
+    }
+}
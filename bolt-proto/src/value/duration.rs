@@ -1,8 +1,19 @@
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
 use bolt_proto_derive::*;
 
+use crate::error::*;
+use crate::Value;
+
 pub(crate) const MARKER: u8 = 0xB4;
 pub(crate) const SIGNATURE: u8 = 0x45;
 
+const NANOS_PER_SECOND: i64 = 1_000_000_000;
+const SECONDS_PER_DAY: i64 = 3600 * 24;
+
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
 pub struct Duration {
     pub(crate) months: i64,
@@ -21,6 +32,17 @@ impl Duration {
         }
     }
 
+    /// Creates a duration, carrying whole seconds out of `nanos` so that it ends up between 0
+    /// and 999,999,999, as Neo4j does. Returns `None` if `seconds` overflows while carrying.
+    pub fn checked_new(months: i64, days: i64, seconds: i64, nanos: i64) -> Option<Self> {
+        Some(Self {
+            months,
+            days,
+            seconds: seconds.checked_add(nanos.div_euclid(NANOS_PER_SECOND))?,
+            nanos: nanos.rem_euclid(NANOS_PER_SECOND) as i32,
+        })
+    }
+
     pub fn months(&self) -> i64 {
         self.months
     }
@@ -36,6 +58,41 @@ impl Duration {
     pub fn nanos(&self) -> i32 {
         self.nanos
     }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Self::checked_new(
+            self.months.checked_add(other.months)?,
+            self.days.checked_add(other.days)?,
+            self.seconds.checked_add(other.seconds)?,
+            self.nanos as i64 + other.nanos as i64,
+        )
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        Self::checked_new(
+            self.months.checked_sub(other.months)?,
+            self.days.checked_sub(other.days)?,
+            self.seconds.checked_sub(other.seconds)?,
+            self.nanos as i64 - other.nanos as i64,
+        )
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Self::checked_new(
+            self.months.checked_neg()?,
+            self.days.checked_neg()?,
+            self.seconds.checked_neg()?,
+            -(self.nanos as i64),
+        )
+    }
+
+    // Seconds since the start of the duration, ignoring months, which are not well-defined in
+    // terms of seconds
+    pub(crate) fn total_seconds(&self) -> Option<i64> {
+        self.days
+            .checked_mul(SECONDS_PER_DAY)?
+            .checked_add(self.seconds)
+    }
 }
 
 impl From<std::time::Duration> for Duration {
@@ -57,6 +114,212 @@ impl From<std::time::Duration> for Duration {
     }
 }
 
+// Durations with months cannot be converted, since months are not well-defined in terms of
+// seconds, and neither can negative durations.
+impl TryFrom<Duration> for std::time::Duration {
+    type Error = Error;
+
+    fn try_from(duration: Duration) -> Result<Self> {
+        if duration.months != 0 {
+            return Err(ConversionError::FromValue(Value::Duration(duration)).into());
+        }
+        let normalized =
+            Duration::checked_new(0, duration.days, duration.seconds, duration.nanos as i64);
+        match normalized.and_then(|normalized| {
+            let seconds = u64::try_from(normalized.total_seconds()?).ok()?;
+            Some(std::time::Duration::new(seconds, normalized.nanos as u32))
+        }) {
+            Some(duration) => Ok(duration),
+            None => Err(ConversionError::TemporalOutOfRange(Value::Duration(duration)).into()),
+        }
+    }
+}
+
+impl Add for Duration {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        self.checked_add(&rhs)
+            .expect("overflow when adding durations")
+    }
+}
+
+impl Sub for Duration {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.checked_sub(&rhs)
+            .expect("overflow when subtracting durations")
+    }
+}
+
+impl Neg for Duration {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        self.checked_neg().expect("overflow when negating duration")
+    }
+}
+
+// Formats as an ISO-8601 duration, the same way Neo4j formats durations as strings, e.g.
+// P1Y2M3DT4H5M6.000000007S
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = self.seconds as i128 * NANOS_PER_SECOND as i128 + self.nanos as i128;
+        if self.months == 0 && self.days == 0 && time == 0 {
+            return f.write_str("PT0S");
+        }
+
+        f.write_str("P")?;
+        for (amount, unit) in [
+            (self.months / 12, 'Y'),
+            (self.months % 12, 'M'),
+            (self.days, 'D'),
+        ] {
+            if amount != 0 {
+                write!(f, "{}{}", amount, unit)?;
+            }
+        }
+
+        if time != 0 {
+            let sign = if time < 0 { "-" } else { "" };
+            let time = time.unsigned_abs();
+            let nanos = time % NANOS_PER_SECOND as u128;
+            let seconds = time / NANOS_PER_SECOND as u128;
+            f.write_str("T")?;
+            for (amount, unit) in [(seconds / 3600, 'H'), (seconds / 60 % 60, 'M')] {
+                if amount != 0 {
+                    write!(f, "{}{}{}", sign, amount, unit)?;
+                }
+            }
+            if !seconds.is_multiple_of(60) || nanos != 0 {
+                write!(f, "{}{}", sign, seconds % 60)?;
+                if nanos != 0 {
+                    let fraction = format!("{:09}", nanos);
+                    write!(f, ".{}", fraction.trim_end_matches('0'))?;
+                }
+                f.write_str("S")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Parses an ISO-8601 duration such as P1Y2M3DT4H5M6.000000007S or -P2W. Each component may
+// have its own sign, and only seconds may have a fractional part.
+impl FromStr for Duration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        parse(s).ok_or_else(|| ConversionError::InvalidDuration(s.to_string()).into())
+    }
+}
+
+fn parse(s: &str) -> Option<Duration> {
+    let (negative, rest) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let rest = rest.strip_prefix('P').or_else(|| rest.strip_prefix('p'))?;
+    let (date, time) = match rest.find(['T', 't']) {
+        Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+        None => (rest, None),
+    };
+
+    let (mut months, mut days, mut seconds, mut nanos) = (0i64, 0i64, 0i64, 0i64);
+    let date_components = components(date, &['Y', 'M', 'W', 'D'])?;
+    let time_components = match time {
+        // A time designator must be followed by at least one component
+        Some("") => return None,
+        Some(time) => components(time, &['H', 'M', 'S'])?,
+        None => Vec::new(),
+    };
+    // "P" on its own is not a valid duration
+    if date_components.is_empty() && time_components.is_empty() {
+        return None;
+    }
+
+    for (amount, fraction, unit) in date_components {
+        if fraction.is_some() {
+            return None;
+        }
+        let (total, multiplier) = match unit {
+            'Y' => (&mut months, 12),
+            'M' => (&mut months, 1),
+            'W' => (&mut days, 7),
+            _ => (&mut days, 1),
+        };
+        *total = total.checked_add(amount.checked_mul(multiplier)?)?;
+    }
+    for (amount, fraction, unit) in time_components {
+        let multiplier = match unit {
+            'H' => 3600,
+            'M' => 60,
+            _ => 1,
+        };
+        if let Some(fraction) = fraction {
+            if unit != 'S' {
+                return None;
+            }
+            nanos = fraction;
+        }
+        seconds = seconds.checked_add(amount.checked_mul(multiplier)?)?;
+    }
+
+    let duration = Duration::checked_new(months, days, seconds, nanos)?;
+    if negative {
+        duration.checked_neg()
+    } else {
+        Some(duration)
+    }
+}
+
+// Splits a run of components like 1Y-2M3D into (amount, signed fractional nanos, unit), requiring
+// units to appear at most once and in the given order
+fn components(mut s: &str, units: &[char]) -> Option<Vec<(i64, Option<i64>, char)>> {
+    let mut components = Vec::new();
+    let mut remaining_units = units;
+    while !s.is_empty() {
+        let end = s.find(|c: char| c.is_ascii_alphabetic())?;
+        let (number, unit) = (&s[..end], s[end..].chars().next()?.to_ascii_uppercase());
+        s = &s[end + 1..];
+
+        let position = remaining_units.iter().position(|&u| u == unit)?;
+        remaining_units = &remaining_units[position + 1..];
+
+        let (negative, digits) = match number.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, number.strip_prefix('+').unwrap_or(number)),
+        };
+        let (whole, fraction) = match digits.split_once(['.', ',']) {
+            Some((whole, fraction)) => (whole, Some(fraction)),
+            None => (digits, None),
+        };
+        if whole.is_empty() || !whole.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let mut amount: i64 = whole.parse().ok()?;
+        let mut fraction = match fraction {
+            Some(fraction) => {
+                if fraction.is_empty()
+                    || fraction.len() > 9
+                    || !fraction.bytes().all(|b| b.is_ascii_digit())
+                {
+                    return None;
+                }
+                Some(format!("{:0<9}", fraction).parse::<i64>().ok()?)
+            }
+            None => None,
+        };
+        if negative {
+            amount = -amount;
+            fraction = fraction.map(|nanos| -nanos);
+        }
+        components.push((amount, fraction, unit));
+    }
+    Some(components)
+}
+
 #[cfg(test)]
 mod tests {
     use std::convert::TryFrom;
@@ -137,4 +400,119 @@ mod tests {
             duration
         );
     }
+
+    #[test]
+    fn checked_new() {
+        let duration = Duration::new(0, 0, 1, 1_500_000_000);
+        assert_eq!((duration.seconds(), duration.nanos()), (1, 1_500_000_000));
+        let duration = Duration::checked_new(0, 0, 1, 1_500_000_000).unwrap();
+        assert_eq!((duration.seconds(), duration.nanos()), (2, 500_000_000));
+        let duration = Duration::checked_new(0, 0, 0, -5).unwrap();
+        assert_eq!((duration.seconds(), duration.nanos()), (-1, 999_999_995));
+        assert!(Duration::checked_new(0, 0, i64::MAX, 1_000_000_000).is_none());
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Duration::new(14, 3, 14706, 7).to_string(),
+            "P1Y2M3DT4H5M6.000000007S"
+        );
+        assert_eq!(Duration::new(0, 0, 0, 0).to_string(), "PT0S");
+        assert_eq!(Duration::new(-13, -2, 0, 0).to_string(), "P-1Y-1M-2D");
+        assert_eq!(Duration::new(0, 0, 0, 500_000_000).to_string(), "PT0.5S");
+        assert_eq!(
+            Duration::new(0, 0, -3662, 500_000_000).to_string(),
+            "PT-1H-1M-1.5S"
+        );
+        assert_eq!(Duration::new(0, 1, 60, 0).to_string(), "P1DT1M");
+    }
+
+    #[test]
+    fn from_str() {
+        for duration in &[
+            Duration::new(14, 3, 14706, 7),
+            Duration::new(0, 0, 0, 0),
+            Duration::new(-13, -2, 0, 0),
+            Duration::new(0, 0, -3662, 500_000_000),
+            get_duration(),
+        ] {
+            assert_eq!(&duration.to_string().parse::<Duration>().unwrap(), duration);
+        }
+        assert_eq!(
+            "-P2W1DT-0.25S".parse::<Duration>().unwrap(),
+            Duration::new(0, -15, 0, 250_000_000)
+        );
+        assert_eq!(
+            "p1y-1mt1h".parse::<Duration>().unwrap(),
+            Duration::new(11, 0, 3600, 0)
+        );
+
+        for invalid in &[
+            "", "P", "PT", "P1DT", "1D", "P1M1Y", "P1.5D", "PT1.5M", "PT1.S", "P1D2",
+        ] {
+            assert!(matches!(
+                invalid.parse::<Duration>(),
+                Err(Error::ConversionError(ConversionError::InvalidDuration(_)))
+            ));
+        }
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Duration::new(1, 2, 3, 600_000_000);
+        let b = Duration::new(4, 5, 6, 700_000_000);
+        assert_eq!(a.clone() + b.clone(), Duration::new(5, 7, 10, 300_000_000));
+        assert_eq!(
+            a.clone() - b.clone(),
+            Duration::new(-3, -3, -4, 900_000_000)
+        );
+        assert_eq!(-a.clone(), Duration::new(-1, -2, -4, 400_000_000));
+        assert_eq!(a.clone() - a.clone(), Duration::new(0, 0, 0, 0));
+        assert!(Duration::new(0, 0, i64::MAX, 0)
+            .checked_add(&Duration::new(0, 0, 0, 999_999_999))
+            .is_some());
+        assert!(Duration::new(0, 0, i64::MAX, 1)
+            .checked_add(&Duration::new(0, 0, 0, 999_999_999))
+            .is_none());
+        assert!(Duration::new(i64::MIN, 0, 0, 0).checked_neg().is_none());
+    }
+
+    #[test]
+    fn std_duration() {
+        let duration = std::time::Duration::new(90_061, 5);
+        assert_eq!(
+            std::time::Duration::try_from(Duration::from(duration)).unwrap(),
+            duration
+        );
+        assert!(matches!(
+            std::time::Duration::try_from(Duration::new(1, 0, 0, 0)),
+            Err(Error::ConversionError(ConversionError::FromValue(_)))
+        ));
+        assert_eq!(
+            std::time::Duration::try_from(Duration::new(0, 0, 2, -500_000_000)).unwrap(),
+            std::time::Duration::from_millis(1500)
+        );
+        assert!(matches!(
+            std::time::Duration::try_from(Duration::new(0, 1, -86_401, 0)),
+            Err(Error::ConversionError(ConversionError::TemporalOutOfRange(
+                _
+            )))
+        ));
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono_duration() {
+        let duration = chrono::Duration::new(-90_061, 5).unwrap();
+        let value = Value::from(duration);
+        assert_eq!(value, Value::Duration(Duration::new(0, -1, -3661, 5)));
+        assert_eq!(chrono::Duration::try_from(value).unwrap(), duration);
+        assert!(matches!(
+            chrono::Duration::try_from(Duration::new(0, i64::MAX, 0, 0)),
+            Err(Error::ConversionError(ConversionError::TemporalOutOfRange(
+                _
+            )))
+        ));
+    }
 }
@@ -70,7 +70,7 @@ mod tests {
 
     #[test]
     fn get_marker() {
-        let min = Float::from(std::f64::MIN_POSITIVE);
+        let min = Float::from(f64::MIN_POSITIVE);
         assert_eq!(min.get_marker().unwrap(), MARKER);
         let e = Float::from(std::f64::consts::E);
         assert_eq!(e.get_marker().unwrap(), MARKER);
@@ -92,7 +92,7 @@ mod tests {
             Float::try_from(Arc::new(Mutex::new(pi.clone().try_into_bytes().unwrap()))).unwrap(),
             pi
         );
-        let max = Float::from(std::f64::MAX);
+        let max = Float::from(f64::MAX);
         assert_eq!(
             Float::try_from(Arc::new(Mutex::new(max.clone().try_into_bytes().unwrap()))).unwrap(),
             max
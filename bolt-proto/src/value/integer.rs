@@ -157,40 +157,27 @@ mod tests {
     fn try_from_bytes() {
         let tiny = Integer::from(-16_i8);
         assert_eq!(
-            Integer::try_from(Arc::new(Mutex::new(tiny.clone().try_into_bytes().unwrap())))
-                .unwrap(),
+            Integer::try_from(Arc::new(Mutex::new(tiny.try_into_bytes().unwrap()))).unwrap(),
             tiny
         );
         let small = Integer::from(-50_i8);
         assert_eq!(
-            Integer::try_from(Arc::new(Mutex::new(
-                small.clone().try_into_bytes().unwrap()
-            )))
-            .unwrap(),
+            Integer::try_from(Arc::new(Mutex::new(small.try_into_bytes().unwrap()))).unwrap(),
             small
         );
         let medium = Integer::from(-8000_i16);
         assert_eq!(
-            Integer::try_from(Arc::new(Mutex::new(
-                medium.clone().try_into_bytes().unwrap()
-            )))
-            .unwrap(),
+            Integer::try_from(Arc::new(Mutex::new(medium.try_into_bytes().unwrap()))).unwrap(),
             medium
         );
         let large = Integer::from(-1_000_000_000_i32);
         assert_eq!(
-            Integer::try_from(Arc::new(Mutex::new(
-                large.clone().try_into_bytes().unwrap()
-            )))
-            .unwrap(),
+            Integer::try_from(Arc::new(Mutex::new(large.try_into_bytes().unwrap()))).unwrap(),
             large
         );
         let very_large = Integer::from(-9_000_000_000_000_000_000_i64);
         assert_eq!(
-            Integer::try_from(Arc::new(Mutex::new(
-                very_large.clone().try_into_bytes().unwrap()
-            )))
-            .unwrap(),
+            Integer::try_from(Arc::new(Mutex::new(very_large.try_into_bytes().unwrap()))).unwrap(),
             very_large
         );
     }
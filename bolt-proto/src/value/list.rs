@@ -148,7 +148,7 @@ mod tests {
         let tiny_list_item_bytes = Integer::from(100_000_000_000_i64).try_into_bytes().unwrap();
         let tiny_list_expected_bytes: Vec<u8> = vec![MARKER_TINY | 10]
             .into_iter()
-            .chain(tiny_list_item_bytes.repeat(10).into_iter())
+            .chain(tiny_list_item_bytes.repeat(10))
             .collect();
         assert_eq!(
             tiny_list.try_into_bytes().unwrap(),
@@ -157,7 +157,7 @@ mod tests {
         let small_list_item_bytes = String::from("item").try_into_bytes().unwrap();
         let small_list_expected_bytes: Vec<u8> = vec![MARKER_SMALL, 0x64] // marker, size
             .into_iter()
-            .chain(small_list_item_bytes.repeat(100).into_iter())
+            .chain(small_list_item_bytes.repeat(100))
             .collect();
         assert_eq!(
             small_list.try_into_bytes().unwrap(),
@@ -166,7 +166,7 @@ mod tests {
         let medium_list_item_bytes = Boolean::from(false).try_into_bytes().unwrap();
         let medium_list_expected_bytes: Vec<u8> = vec![MARKER_MEDIUM, 0x03, 0xE8] // marker, size
             .into_iter()
-            .chain(medium_list_item_bytes.repeat(1000).into_iter())
+            .chain(medium_list_item_bytes.repeat(1000))
             .collect();
         assert_eq!(
             medium_list.try_into_bytes().unwrap(),
@@ -181,7 +181,7 @@ mod tests {
         let large_list_item_bytes = Integer::from(1_i8).try_into_bytes().unwrap();
         let large_list_expected_bytes: Vec<u8> = vec![MARKER_LARGE, 0x00, 0x01, 0x86, 0xA0] // marker, size
             .into_iter()
-            .chain(large_list_item_bytes.repeat(100_000).into_iter())
+            .chain(large_list_item_bytes.repeat(100_000))
             .collect();
         assert_eq!(
             large_list.try_into_bytes().unwrap(),
@@ -1,25 +1,30 @@
 use bolt_proto_derive::*;
-use chrono::{NaiveDateTime, Timelike};
+
+use crate::error::*;
+use crate::Value;
 
 pub(crate) const MARKER: u8 = 0xB2;
 pub(crate) const SIGNATURE: u8 = 0x64;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[validate]
 pub struct LocalDateTime {
     pub(crate) epoch_seconds: i64,
     pub(crate) nanos: i64,
 }
 
-impl From<NaiveDateTime> for LocalDateTime {
-    fn from(date_time: NaiveDateTime) -> Self {
-        Self {
-            epoch_seconds: date_time.timestamp(),
-            nanos: date_time.nanosecond() as i64,
+impl LocalDateTime {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(0..1_000_000_000).contains(&self.nanos) {
+            return Err(
+                ConversionError::TemporalOutOfRange(Value::LocalDateTime(self.clone())).into(),
+            );
         }
+        Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use std::convert::TryFrom;
     use std::sync::{Arc, Mutex};
@@ -33,7 +38,12 @@ mod tests {
     use super::*;
 
     fn get_local_date_time() -> LocalDateTime {
-        LocalDateTime::from(NaiveDate::from_ymd(2050, 3, 15).and_hms_nano(13, 15, 5, 420))
+        LocalDateTime::from(
+            NaiveDate::from_ymd_opt(2050, 3, 15)
+                .unwrap()
+                .and_hms_nano_opt(13, 15, 5, 420)
+                .unwrap(),
+        )
     }
 
     #[test]
@@ -1,25 +1,28 @@
 use bolt_proto_derive::*;
-use chrono::{NaiveTime, Timelike};
+
+use crate::error::*;
+use crate::value::NANOS_PER_DAY;
+use crate::Value;
 
 pub(crate) const MARKER: u8 = 0xB1;
 pub(crate) const SIGNATURE: u8 = 0x74;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[validate]
 pub struct LocalTime {
     pub(crate) nanos_since_midnight: i64,
 }
 
-impl From<NaiveTime> for LocalTime {
-    fn from(naive_time: NaiveTime) -> Self {
-        Self {
-            // Will not overflow: u32::MAX * 1_000_000_000 + u32::MAX < i64::MAX
-            nanos_since_midnight: naive_time.num_seconds_from_midnight() as i64 * 1_000_000_000
-                + naive_time.nanosecond() as i64,
+impl LocalTime {
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(0..NANOS_PER_DAY).contains(&self.nanos_since_midnight) {
+            return Err(ConversionError::TemporalOutOfRange(Value::LocalTime(self.clone())).into());
         }
+        Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use std::convert::TryFrom;
     use std::sync::{Arc, Mutex};
@@ -33,7 +36,7 @@ mod tests {
     use super::*;
 
     fn get_chrono_naive_time() -> NaiveTime {
-        NaiveTime::from_hms_nano(12, 34, 24, 1029)
+        NaiveTime::from_hms_nano_opt(12, 34, 24, 1029).unwrap()
     }
 
     #[test]
@@ -19,7 +19,7 @@ pub(crate) const MARKER_LARGE: u8 = 0xDA;
 
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct Map {
-    pub(crate) value: HashMap<String, Value>,
+    pub(crate) value: HashMap<std::string::String, Value>,
 }
 
 impl Marker for Map {
@@ -46,7 +46,7 @@ impl TryInto<Bytes> for Map {
         let mut total_value_bytes: usize = 0;
         let mut value_bytes_vec: Vec<Bytes> = Vec::with_capacity(length);
         for (key, val) in self.value {
-            let key_bytes: Bytes = key.try_into()?;
+            let key_bytes: Bytes = String::from(key).try_into()?;
             let val_bytes: Bytes = val.try_into()?;
             total_value_bytes += key_bytes.len() + val_bytes.len();
             value_bytes_vec.push(key_bytes);
@@ -91,9 +91,9 @@ impl TryFrom<Arc<Mutex<Bytes>>> for Map {
                     return Err(DeserializationError::InvalidMarkerByte(marker).into());
                 }
             };
-            let mut hash_map: HashMap<String, Value> = HashMap::with_capacity(size);
+            let mut hash_map: HashMap<std::string::String, Value> = HashMap::with_capacity(size);
             for _ in 0..size {
-                let key = String::try_from(Arc::clone(&input_arc))?;
+                let key = String::try_from(Arc::clone(&input_arc))?.value;
                 let value = Value::try_from(Arc::clone(&input_arc))?;
                 hash_map.insert(key, value);
             }
@@ -105,7 +105,7 @@ impl TryFrom<Arc<Mutex<Bytes>>> for Map {
 
 impl<K, V, S> From<HashMap<K, V, S>> for Map
 where
-    K: Into<String>,
+    K: Into<std::string::String>,
     V: Into<Value>,
     S: BuildHasher,
 {
@@ -1,17 +1,25 @@
 use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::sync::{Arc, Mutex};
 
 use bolt_proto_derive::*;
+use bytes::Bytes;
 
+use crate::error::*;
+use crate::serialization::*;
 use crate::Value;
 
 pub(crate) const MARKER: u8 = 0xB3;
+pub(crate) const MARKER_V5: u8 = 0xB4;
 pub(crate) const SIGNATURE: u8 = 0x4E;
 
-#[derive(Debug, Clone, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Signature)]
 pub struct Node {
     pub(crate) node_identity: i64,
     pub(crate) labels: Vec<String>,
     pub(crate) properties: HashMap<String, Value>,
+    // Only sent by servers using Bolt 5.0 or later
+    pub(crate) element_id: Option<String>,
 }
 
 impl Node {
@@ -24,9 +32,16 @@ impl Node {
             node_identity,
             labels,
             properties: properties.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            element_id: None,
         }
     }
 
+    /// Set the element ID that identifies the node in Bolt 5.0 and later.
+    pub fn with_element_id(mut self, element_id: impl Into<String>) -> Self {
+        self.element_id = Some(element_id.into());
+        self
+    }
+
     pub fn node_identity(&self) -> i64 {
         self.node_identity
     }
@@ -38,4 +53,56 @@ impl Node {
     pub fn properties(&self) -> &HashMap<String, Value> {
         &self.properties
     }
+
+    pub fn element_id(&self) -> Option<&str> {
+        self.element_id.as_deref()
+    }
+
+    // Deserialize the Bolt 5 structure, which has the element ID as a trailing field
+    pub(crate) fn try_from_v5(remaining_bytes_arc: Arc<Mutex<Bytes>>) -> Result<Self> {
+        let mut node = Self::try_from(Arc::clone(&remaining_bytes_arc))?;
+        node.element_id = Some(deserialize_field(&remaining_bytes_arc)?);
+        Ok(node)
+    }
+}
+
+impl Marker for Node {
+    fn get_marker(&self) -> Result<u8> {
+        match self.element_id {
+            Some(_) => Ok(MARKER_V5),
+            None => Ok(MARKER),
+        }
+    }
+}
+
+impl Serialize for Node {}
+
+impl TryInto<Bytes> for Node {
+    type Error = Error;
+
+    fn try_into(self) -> Result<Bytes> {
+        let marker = self.get_marker()?;
+        let mut fields = vec![
+            Value::from(self.node_identity),
+            Value::from(self.labels),
+            Value::from(self.properties),
+        ];
+        fields.extend(self.element_id.map(Value::from));
+        serialize_structure(marker, SIGNATURE, fields)
+    }
+}
+
+impl Deserialize for Node {}
+
+impl TryFrom<Arc<Mutex<Bytes>>> for Node {
+    type Error = Error;
+
+    fn try_from(remaining_bytes_arc: Arc<Mutex<Bytes>>) -> Result<Self> {
+        Ok(Self {
+            node_identity: deserialize_field(&remaining_bytes_arc)?,
+            labels: deserialize_field(&remaining_bytes_arc)?,
+            properties: deserialize_field(&remaining_bytes_arc)?,
+            element_id: None,
+        })
+    }
 }
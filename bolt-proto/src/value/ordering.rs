@@ -0,0 +1,626 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::value::*;
+
+// Cypher orders values of different types in this order, with values of the same group
+// ordered among themselves. Integers and floats share a group, as do the different
+// encodings of date-times with a UTC offset or time zone ID (see date_time_key). Byte arrays
+// have no Cypher equivalent, so they're ordered after lists.
+pub(crate) fn group(value: &Value) -> u8 {
+    match value {
+        Value::Map(_) => 0,
+        Value::Node(_) => 1,
+        Value::Relationship(_) => 2,
+        Value::UnboundRelationship(_) => 3,
+        Value::List(_) => 4,
+        Value::Bytes(_) => 5,
+        Value::Path(_) => 6,
+        Value::DateTimeOffset(_)
+        | Value::DateTimeZoned(_)
+        | Value::DateTimeOffsetUtc(_)
+        | Value::DateTimeZonedUtc(_) => 7,
+        Value::LocalDateTime(_) => 8,
+        Value::Date(_) => 9,
+        Value::Time(_) => 10,
+        Value::LocalTime(_) => 11,
+        Value::Duration(_) => 12,
+        Value::Point2D(_) | Value::Point3D(_) => 13,
+        Value::String(_) => 14,
+        Value::Boolean(_) => 15,
+        Value::Integer(_) | Value::Float(_) => 16,
+        Value::Null => 17,
+    }
+}
+
+// Values are equal if they are equivalent in Cypher: integers and floats with the same
+// numeric value are equal, and unlike Cypher's `=` operator, NaN is equal to itself and null
+// is equal to null. See Value::cypher_eq for Cypher's `=` operator.
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Value {}
+
+impl PartialOrd for Value {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// Values are totally ordered in the same way as Cypher's ORDER BY, with ties between
+// values Cypher considers equal (such as nodes with the same ID) broken by their contents.
+impl Ord for Value {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Value::Map(a), Value::Map(b)) => cmp_maps(&a.value, &b.value),
+            (Value::Node(a), Value::Node(b)) => cmp_nodes(a, b),
+            (Value::Relationship(a), Value::Relationship(b)) => cmp_relationships(a, b),
+            (Value::UnboundRelationship(a), Value::UnboundRelationship(b)) => {
+                cmp_unbound_relationships(a, b)
+            }
+            (Value::List(a), Value::List(b)) => a.value.cmp(&b.value),
+            (Value::Bytes(a), Value::Bytes(b)) => a.value.cmp(&b.value),
+            (Value::Path(a), Value::Path(b)) => cmp_paths(a, b),
+            (Value::LocalDateTime(a), Value::LocalDateTime(b)) => {
+                (a.epoch_seconds, a.nanos).cmp(&(b.epoch_seconds, b.nanos))
+            }
+            (Value::Date(a), Value::Date(b)) => a.days_since_epoch.cmp(&b.days_since_epoch),
+            (Value::Time(a), Value::Time(b)) => time_key(a).cmp(&time_key(b)),
+            (Value::LocalTime(a), Value::LocalTime(b)) => {
+                a.nanos_since_midnight.cmp(&b.nanos_since_midnight)
+            }
+            (Value::Duration(a), Value::Duration(b)) => duration_key(a).cmp(&duration_key(b)),
+            (Value::String(a), Value::String(b)) => a.value.cmp(&b.value),
+            (Value::Boolean(a), Value::Boolean(b)) => a.value.cmp(&b.value),
+            (Value::Integer(a), Value::Integer(b)) => a.value.cmp(&b.value),
+            (Value::Integer(a), Value::Float(b)) => cmp_integer_float(a.value, b.value),
+            (Value::Float(a), Value::Integer(b)) => cmp_integer_float(b.value, a.value).reverse(),
+            (Value::Float(a), Value::Float(b)) => cmp_floats(a.value, b.value),
+            (Value::Null, Value::Null) => Ordering::Equal,
+            (a, b) => match (date_time_key(a), date_time_key(b)) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => match (point_key(a), point_key(b)) {
+                    (Some((a_srid, a)), Some((b_srid, b))) => a_srid
+                        .cmp(&b_srid)
+                        .then_with(|| cmp_slices(&a, &b, |a, b| cmp_floats(*a, *b))),
+                    _ => group(a).cmp(&group(b)),
+                },
+            },
+        }
+    }
+}
+
+impl Value {
+    /// Compare two values like Cypher's `<`, `<=`, `>` and `>=` operators do. Returns `None`
+    /// where Cypher would return null: if either value is null or NaN, if the values are of
+    /// different types (integers and floats may be compared with each other), or if values of
+    /// their type cannot be compared, like maps, nodes, relationships, paths, durations and
+    /// points. Lists are compared element by element.
+    pub fn compare(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::List(a), Value::List(b)) => {
+                for (a, b) in a.value.iter().zip(&b.value) {
+                    match a.compare(b)? {
+                        Ordering::Equal => {}
+                        ordering => return Some(ordering),
+                    }
+                }
+                Some(a.value.len().cmp(&b.value.len()))
+            }
+            (Value::Float(float), _) | (_, Value::Float(float)) if float.value.is_nan() => None,
+            (Value::Map(_), _)
+            | (Value::Node(_), _)
+            | (Value::Relationship(_), _)
+            | (Value::UnboundRelationship(_), _)
+            | (Value::Path(_), _)
+            | (Value::Duration(_), _)
+            | (Value::Point2D(_), _)
+            | (Value::Point3D(_), _)
+            | (Value::Null, _)
+            | (_, Value::Null) => None,
+            (a, b) if group(a) == group(b) => Some(a.cmp(b)),
+            _ => None,
+        }
+    }
+
+    /// Check two values for equality like Cypher's `=` operator does. Returns `None` where
+    /// Cypher would return null, if either value is null, or if lists or maps that are
+    /// otherwise equal contain nulls. Unlike [`PartialEq`], NaN is not equal to itself, and
+    /// nodes and relationships are equal if they have the same ID.
+    pub fn cypher_eq(&self, other: &Value) -> Option<bool> {
+        match (self, other) {
+            (Value::Null, _) | (_, Value::Null) => None,
+            (Value::List(a), Value::List(b)) => {
+                if a.value.len() != b.value.len() {
+                    return Some(false);
+                }
+                all_equal(a.value.iter().zip(&b.value))
+            }
+            (Value::Map(a), Value::Map(b)) => {
+                if a.value.len() != b.value.len() {
+                    return Some(false);
+                }
+                let mut pairs = Vec::with_capacity(a.value.len());
+                for (key, a) in &a.value {
+                    match b.value.get(key) {
+                        Some(b) => pairs.push((a, b)),
+                        None => return Some(false),
+                    }
+                }
+                all_equal(pairs)
+            }
+            (Value::Float(float), _) | (_, Value::Float(float)) if float.value.is_nan() => {
+                Some(false)
+            }
+            (Value::Node(a), Value::Node(b)) => Some(a.node_identity == b.node_identity),
+            (Value::Relationship(a), Value::Relationship(b)) => {
+                Some(a.rel_identity == b.rel_identity)
+            }
+            (a, b) => Some(a == b),
+        }
+    }
+}
+
+// False if any pair is unequal, otherwise null if any pair's equality is null
+fn all_equal<'a>(pairs: impl IntoIterator<Item = (&'a Value, &'a Value)>) -> Option<bool> {
+    let mut result = Some(true);
+    for (a, b) in pairs {
+        match a.cypher_eq(b) {
+            Some(false) => return Some(false),
+            None => result = None,
+            Some(true) => {}
+        }
+    }
+    result
+}
+
+// Maps are ordered by size, then by their keys in order, then by their values in key order
+fn cmp_maps<K: Ord>(a: &HashMap<K, Value>, b: &HashMap<K, Value>) -> Ordering {
+    fn sorted<K: Ord>(map: &HashMap<K, Value>) -> Vec<(&K, &Value)> {
+        let mut entries: Vec<_> = map.iter().collect();
+        entries.sort_unstable_by_key(|(key, _)| *key);
+        entries
+    }
+    let (a, b) = (sorted(a), sorted(b));
+    a.len()
+        .cmp(&b.len())
+        .then_with(|| a.iter().map(|(k, _)| k).cmp(b.iter().map(|(k, _)| k)))
+        .then_with(|| a.iter().map(|(_, v)| v).cmp(b.iter().map(|(_, v)| v)))
+}
+
+fn cmp_nodes(a: &Node, b: &Node) -> Ordering {
+    a.node_identity
+        .cmp(&b.node_identity)
+        .then_with(|| a.labels.cmp(&b.labels))
+        .then_with(|| cmp_maps(&a.properties, &b.properties))
+        .then_with(|| a.element_id.cmp(&b.element_id))
+}
+
+fn cmp_relationships(a: &Relationship, b: &Relationship) -> Ordering {
+    a.rel_identity
+        .cmp(&b.rel_identity)
+        .then_with(|| a.start_node_identity.cmp(&b.start_node_identity))
+        .then_with(|| a.end_node_identity.cmp(&b.end_node_identity))
+        .then_with(|| a.rel_type.cmp(&b.rel_type))
+        .then_with(|| cmp_maps(&a.properties, &b.properties))
+        .then_with(|| a.element_ids.cmp(&b.element_ids))
+}
+
+fn cmp_unbound_relationships(a: &UnboundRelationship, b: &UnboundRelationship) -> Ordering {
+    a.rel_identity
+        .cmp(&b.rel_identity)
+        .then_with(|| a.rel_type.cmp(&b.rel_type))
+        .then_with(|| cmp_maps(&a.properties, &b.properties))
+        .then_with(|| a.element_id.cmp(&b.element_id))
+}
+
+fn cmp_slices<T>(a: &[T], b: &[T], cmp: fn(&T, &T) -> Ordering) -> Ordering {
+    a.iter()
+        .zip(b)
+        .map(|(a, b)| cmp(a, b))
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| a.len().cmp(&b.len()))
+}
+
+// Paths are ordered by the nodes and relationships they traverse, in order
+fn cmp_paths(a: &Path, b: &Path) -> Ordering {
+    let by_start = match (a.start(), b.start()) {
+        (Some(a), Some(b)) => cmp_nodes(a, b),
+        (a, b) => a.is_some().cmp(&b.is_some()),
+    };
+    let mut a_segments = a.segments();
+    let mut b_segments = b.segments();
+    by_start
+        .then_with(|| loop {
+            match (a_segments.next(), b_segments.next()) {
+                (Some((_, a_rel, a_end)), Some((_, b_rel, b_end))) => {
+                    let ordering =
+                        cmp_relationships(&a_rel, &b_rel).then_with(|| cmp_nodes(a_end, b_end));
+                    if ordering.is_ne() {
+                        break ordering;
+                    }
+                }
+                (a, b) => break a.is_some().cmp(&b.is_some()),
+            }
+        })
+        // Paths traversing the same nodes and relationships may still differ, for example in
+        // unused nodes
+        .then_with(|| cmp_slices(&a.nodes, &b.nodes, cmp_nodes))
+        .then_with(|| {
+            cmp_slices(
+                &a.relationships,
+                &b.relationships,
+                cmp_unbound_relationships,
+            )
+        })
+        .then_with(|| a.sequence.cmp(&b.sequence))
+}
+
+// Times with a UTC offset are ordered by the instant they represent in UTC, then by offset
+fn time_key(time: &Time) -> (i64, i32) {
+    (
+        time.nanos_since_midnight - time.zone_offset as i64 * 1_000_000_000,
+        time.zone_offset,
+    )
+}
+
+// Durations are ordered by their length, taking a month to be its average length in the
+// Gregorian calendar, as Neo4j does. Durations whose nanoseconds haven't been carried into
+// seconds are ordered by the same length, and only then by their nanoseconds.
+fn duration_key(duration: &Duration) -> (i128, i64, i64, i32) {
+    const AVERAGE_SECONDS_PER_MONTH: i128 = 2_629_746;
+    (
+        (duration.months as i128 * AVERAGE_SECONDS_PER_MONTH
+            + duration.days as i128 * 86_400
+            + duration.seconds as i128)
+            * 1_000_000_000
+            + duration.nanos as i128,
+        duration.months,
+        duration.days,
+        duration.nanos,
+    )
+}
+
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) enum Zone<'a> {
+    Offset(i32),
+    Id(&'a str),
+}
+
+// Date-times with a UTC offset or time zone ID are ordered by the instant they represent,
+// then by their offset or time zone, regardless of whether they hold local or UTC seconds.
+// The exception is legacy date-times with a time zone ID, whose instant depends on the time
+// zone database. So that ordering, equality and hashing don't change with the `chrono`
+// feature, they are never equal to other date-times, and are ordered after them by their
+// local seconds. Use Value::convert_date_times to compare them with UTC date-times.
+pub(crate) fn date_time_key(value: &Value) -> Option<(bool, i128, i64, Zone<'_>)> {
+    match value {
+        Value::DateTimeOffset(date_time) => Some((
+            false,
+            date_time.epoch_seconds as i128 - date_time.offset_seconds as i128,
+            date_time.nanos,
+            Zone::Offset(date_time.offset_seconds),
+        )),
+        Value::DateTimeOffsetUtc(date_time) => Some((
+            false,
+            date_time.epoch_seconds as i128,
+            date_time.nanos,
+            Zone::Offset(date_time.offset_seconds),
+        )),
+        Value::DateTimeZoned(date_time) => Some((
+            true,
+            date_time.epoch_seconds as i128,
+            date_time.nanos,
+            Zone::Id(&date_time.zone_id),
+        )),
+        Value::DateTimeZonedUtc(date_time) => Some((
+            false,
+            date_time.epoch_seconds as i128,
+            date_time.nanos,
+            Zone::Id(&date_time.zone_id),
+        )),
+        _ => None,
+    }
+}
+
+fn point_key(value: &Value) -> Option<(i32, Vec<f64>)> {
+    match value {
+        Value::Point2D(point) => Some((point.srid, vec![point.x, point.y])),
+        Value::Point3D(point) => Some((point.srid, vec![point.x, point.y, point.z])),
+        _ => None,
+    }
+}
+
+// NaN is greater than all other numbers, and equal to itself
+fn cmp_floats(a: f64, b: f64) -> Ordering {
+    match (a.is_nan(), b.is_nan()) {
+        (true, true) => Ordering::Equal,
+        (true, false) => Ordering::Greater,
+        (false, true) => Ordering::Less,
+        // Does not panic, since neither is NaN
+        (false, false) => a.partial_cmp(&b).unwrap(),
+    }
+}
+
+// Compares exactly, without losing precision by converting the integer to a float
+fn cmp_integer_float(integer: i64, float: f64) -> Ordering {
+    // 2^63, the smallest float greater than every i64
+    const LIMIT: f64 = 9_223_372_036_854_775_808.0;
+    if float.is_nan() || float >= LIMIT {
+        return Ordering::Less;
+    }
+    if float < -LIMIT {
+        return Ordering::Greater;
+    }
+    let whole = float.trunc();
+    // Does not overflow, since -2^63 <= whole < 2^63
+    integer.cmp(&(whole as i64)).then_with(|| {
+        if float > whole {
+            Ordering::Less
+        } else if float < whole {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::FromIterator;
+
+    use super::*;
+
+    #[test]
+    fn order_of_types() {
+        let node = Node::new(1, vec![], HashMap::<std::string::String, Value>::new());
+        let ordered = vec![
+            Value::from(HashMap::<&str, i64>::new()),
+            Value::from(node.clone()),
+            Value::from(Relationship::new(
+                1,
+                1,
+                1,
+                "R".to_string(),
+                HashMap::<std::string::String, Value>::new(),
+            )),
+            Value::from(vec![1]),
+            Value::from(Path::new(vec![node], vec![], vec![])),
+            Value::DateTimeOffsetUtc(DateTimeOffsetUtc {
+                epoch_seconds: 0,
+                nanos: 0,
+                offset_seconds: 0,
+            }),
+            Value::LocalDateTime(LocalDateTime {
+                epoch_seconds: 0,
+                nanos: 0,
+            }),
+            Value::Date(Date {
+                days_since_epoch: 0,
+            }),
+            Value::Time(Time {
+                nanos_since_midnight: 0,
+                zone_offset: 0,
+            }),
+            Value::LocalTime(LocalTime {
+                nanos_since_midnight: 0,
+            }),
+            Value::from(Duration::new(0, 0, 0, 0)),
+            Value::from(Point2D::new(7203, 0.0, 0.0)),
+            Value::from("a"),
+            Value::from(false),
+            Value::from(1),
+            Value::Null,
+        ];
+        let mut sorted = ordered.clone();
+        sorted.reverse();
+        sorted.sort();
+        assert_eq!(sorted, ordered);
+    }
+
+    #[test]
+    fn numbers() {
+        assert_eq!(Value::from(1), Value::from(1.0));
+        assert_ne!(Value::from(1), Value::from(1.5));
+        assert_eq!(Value::from(0.0), Value::from(-0.0));
+        assert_eq!(Value::from(f64::NAN), Value::from(f64::NAN));
+        assert!(Value::from(f64::NAN) > Value::from(f64::INFINITY));
+        assert!(Value::from(f64::NAN) > Value::from(i64::MAX));
+        assert!(Value::from(1.5) > Value::from(1));
+        assert!(Value::from(-1.5) < Value::from(-1));
+        // i64::MAX cannot be represented as a float, and rounds up to 2^63 when converted
+        assert!(Value::from(i64::MAX) < Value::from(9_223_372_036_854_775_808.0));
+        assert!(Value::from(i64::MAX - 1) < Value::from(i64::MAX));
+        assert_eq!(
+            Value::from(i64::MIN),
+            Value::from(-9_223_372_036_854_775_808.0)
+        );
+
+        let mut numbers = vec![
+            Value::from(f64::NAN),
+            Value::from(2),
+            Value::from(1.5),
+            Value::from(f64::NEG_INFINITY),
+            Value::from(-3),
+        ];
+        numbers.sort();
+        assert_eq!(
+            numbers,
+            vec![
+                Value::from(f64::NEG_INFINITY),
+                Value::from(-3),
+                Value::from(1.5),
+                Value::from(2),
+                Value::from(f64::NAN),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested() {
+        assert!(Value::from(vec![1, 2]) < Value::from(vec![1, 2, 0]));
+        assert!(Value::from(vec![1, 3]) > Value::from(vec![1, 2, 0]));
+        assert_eq!(
+            Value::from(vec![Value::from(1), Value::from(2.0)]),
+            Value::from(vec![1, 2])
+        );
+        let small = Value::from(HashMap::<&str, i64>::from_iter(vec![("b", 1)]));
+        let large = Value::from(HashMap::<&str, i64>::from_iter(vec![("a", 1), ("b", 1)]));
+        assert!(small < large);
+        assert_eq!(
+            small,
+            Value::from(HashMap::<&str, f64>::from_iter(vec![("b", 1.0)]))
+        );
+    }
+
+    #[test]
+    fn temporal() {
+        let legacy = Value::DateTimeOffset(DateTimeOffset {
+            epoch_seconds: 3600,
+            nanos: 0,
+            offset_seconds: 3600,
+        });
+        let utc = Value::DateTimeOffsetUtc(DateTimeOffsetUtc {
+            epoch_seconds: 0,
+            nanos: 0,
+            offset_seconds: 3600,
+        });
+        let other_offset = Value::DateTimeOffsetUtc(DateTimeOffsetUtc {
+            epoch_seconds: 0,
+            nanos: 0,
+            offset_seconds: 0,
+        });
+        assert_eq!(legacy, utc);
+        assert_ne!(utc, other_offset);
+        assert!(other_offset < utc);
+        assert!(
+            Value::DateTimeOffsetUtc(DateTimeOffsetUtc {
+                epoch_seconds: 1,
+                nanos: 0,
+                offset_seconds: -3600,
+            }) > utc
+        );
+
+        // 12:00+01:00 is earlier than 11:30+00:00
+        let noon = Value::Time(Time {
+            nanos_since_midnight: 12 * 3_600_000_000_000,
+            zone_offset: 3600,
+        });
+        let half_past_eleven = Value::Time(Time {
+            nanos_since_midnight: 23 * 1_800_000_000_000,
+            zone_offset: 0,
+        });
+        assert!(noon < half_past_eleven);
+
+        // A month is longer than 30 days, but shorter than 31
+        assert!(Value::from(Duration::new(1, 0, 0, 0)) > Value::from(Duration::new(0, 30, 0, 0)));
+        assert!(Value::from(Duration::new(1, 0, 0, 0)) < Value::from(Duration::new(0, 31, 0, 0)));
+
+        // Nanoseconds that haven't been carried into seconds still count towards the length
+        assert!(Value::from(Duration::new(0, 0, 1, -5)) < Value::from(Duration::new(0, 0, 1, 0)));
+        assert!(
+            Value::from(Duration::new(0, 0, 0, 1_500_000_000))
+                > Value::from(Duration::new(0, 0, 1, 0))
+        );
+        assert_ne!(
+            Value::from(Duration::new(0, 0, 1, -5)).cmp(&Value::from(Duration::new(
+                0,
+                0,
+                0,
+                999_999_995
+            ))),
+            Ordering::Equal
+        );
+    }
+
+    #[test]
+    fn zoned_date_times() {
+        let utc = Value::DateTimeZonedUtc(DateTimeZonedUtc {
+            epoch_seconds: 1_600_000_000,
+            nanos: 0,
+            zone_id: "America/New_York".to_string(),
+        });
+        // The same instant, in New York's local time
+        let legacy = Value::DateTimeZoned(DateTimeZoned {
+            epoch_seconds: 1_600_000_000 - 4 * 3600,
+            nanos: 0,
+            zone_id: "America/New_York".to_string(),
+        });
+        let offset = Value::DateTimeOffsetUtc(DateTimeOffsetUtc {
+            epoch_seconds: 1_700_000_000,
+            nanos: 0,
+            offset_seconds: 0,
+        });
+
+        // Legacy zoned date-times are ordered after the others, whether or not they could
+        // be converted to UTC
+        assert_ne!(legacy, utc);
+        assert!(utc < offset);
+        assert!(offset < legacy);
+        assert_eq!(utc.compare(&legacy), Some(Ordering::Less));
+
+        #[cfg(feature = "chrono")]
+        assert_eq!(legacy.clone().convert_date_times(true).unwrap(), utc);
+    }
+
+    #[test]
+    fn compare() {
+        assert_eq!(
+            Value::from(1).compare(&Value::from(1.5)),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Value::from("b").compare(&Value::from("a")),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(Value::from(1).compare(&Value::from("a")), None);
+        assert_eq!(Value::from(1).compare(&Value::Null), None);
+        assert_eq!(Value::from(1).compare(&Value::from(f64::NAN)), None);
+        assert_eq!(
+            Value::from(Duration::new(1, 0, 0, 0)).compare(&Value::from(Duration::new(1, 0, 0, 0))),
+            None
+        );
+        assert_eq!(
+            Value::from(vec![Value::from(1), Value::Null])
+                .compare(&Value::from(vec![Value::from(2), Value::Null])),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Value::from(vec![Value::from(1), Value::Null])
+                .compare(&Value::from(vec![Value::from(1), Value::from(2)])),
+            None
+        );
+    }
+
+    #[test]
+    fn cypher_eq() {
+        assert_eq!(Value::from(1).cypher_eq(&Value::from(1.0)), Some(true));
+        assert_eq!(Value::from(1).cypher_eq(&Value::from("1")), Some(false));
+        assert_eq!(Value::Null.cypher_eq(&Value::Null), None);
+        assert_eq!(
+            Value::from(f64::NAN).cypher_eq(&Value::from(f64::NAN)),
+            Some(false)
+        );
+        assert_eq!(
+            Value::from(vec![Value::from(1), Value::Null])
+                .cypher_eq(&Value::from(vec![Value::from(1), Value::Null])),
+            None
+        );
+        assert_eq!(
+            Value::from(vec![Value::from(1), Value::Null])
+                .cypher_eq(&Value::from(vec![Value::from(2), Value::Null])),
+            Some(false)
+        );
+
+        let node = Node::new(1, vec![], HashMap::<std::string::String, Value>::new());
+        let updated = Node::new(1, vec![], HashMap::from_iter(vec![("key".to_string(), 1)]));
+        assert_ne!(Value::from(node.clone()), Value::from(updated.clone()));
+        assert_eq!(
+            Value::from(node).cypher_eq(&Value::from(updated)),
+            Some(true)
+        );
+    }
+}
@@ -1,11 +1,17 @@
 use bolt_proto_derive::*;
 
+use crate::error::*;
+use crate::value::relationship::ElementIds;
 use crate::value::*;
 
 pub(crate) const MARKER: u8 = 0xB3;
 pub(crate) const SIGNATURE: u8 = 0x50;
 
+// The sequence alternates between relationship and node indices. Relationship indices start
+// at 1 and are negative when the relationship is traversed against its direction, and node
+// indices start at 0, with the first node of the path always being the first in `nodes`.
 #[derive(Debug, Clone, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[validate]
 pub struct Path {
     pub(crate) nodes: Vec<Node>,
     pub(crate) relationships: Vec<UnboundRelationship>,
@@ -36,4 +42,190 @@ impl Path {
     pub fn sequence(&self) -> &[i64] {
         &self.sequence
     }
+
+    /// The first node in the path, or `None` if the path has no nodes.
+    pub fn start(&self) -> Option<&Node> {
+        self.nodes.first()
+    }
+
+    /// The last node in the path, or `None` if the path has no nodes.
+    pub fn end(&self) -> Option<&Node> {
+        match self.segments().last() {
+            Some((_, _, end)) => Some(end),
+            None => self.start(),
+        }
+    }
+
+    /// The number of relationships traversed by the path.
+    pub fn len(&self) -> usize {
+        self.sequence.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterates over the steps of the path as `(start, relationship, end)`, where `start` and
+    /// `end` are in the order the path visits them, and the relationship is bound to the IDs of
+    /// the nodes it actually starts and ends at (and to their element IDs, if the relationship
+    /// and both nodes have one). Paths received from the server are validated when
+    /// deserialized; for other paths, iteration stops at the first malformed step.
+    pub fn segments(&self) -> impl Iterator<Item = (&Node, Relationship, &Node)> + '_ {
+        let mut previous = self.nodes.first();
+        self.sequence.chunks_exact(2).map_while(move |step| {
+            let start = previous?;
+            let relationship = self.relationships.get(relationship_index(step[0])?)?;
+            let end = self.nodes.get(usize::try_from(step[1]).ok()?)?;
+            previous = Some(end);
+
+            let (from, to) = if step[0] > 0 {
+                (start, end)
+            } else {
+                (end, start)
+            };
+            Some((
+                start,
+                Relationship {
+                    rel_identity: relationship.rel_identity,
+                    start_node_identity: from.node_identity,
+                    end_node_identity: to.node_identity,
+                    rel_type: relationship.rel_type.clone(),
+                    properties: relationship.properties.clone(),
+                    element_ids: element_ids(relationship, from, to),
+                },
+                end,
+            ))
+        })
+    }
+
+    pub(crate) fn validate(&self) -> Result<()> {
+        if self.nodes.is_empty()
+            || !self.sequence.len().is_multiple_of(2)
+            || self.segments().count() != self.len()
+        {
+            return Err(ConversionError::InvalidPath(Value::Path(self.clone())).into());
+        }
+        Ok(())
+    }
+}
+
+fn element_ids(
+    relationship: &UnboundRelationship,
+    from: &Node,
+    to: &Node,
+) -> Option<Box<ElementIds>> {
+    Some(Box::new(ElementIds {
+        rel: relationship.element_id.clone()?,
+        start_node: from.element_id.clone()?,
+        end_node: to.element_id.clone()?,
+    }))
+}
+
+fn relationship_index(index: i64) -> Option<usize> {
+    usize::try_from(index.unsigned_abs()).ok()?.checked_sub(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::convert::TryFrom;
+    use std::iter::FromIterator;
+    use std::sync::{Arc, Mutex};
+
+    use crate::serialization::*;
+
+    use super::*;
+
+    fn get_node(node_identity: i64) -> Node {
+        Node::new(
+            node_identity,
+            vec!["Person".to_string()],
+            HashMap::<std::string::String, Value>::new(),
+        )
+    }
+
+    fn get_unbound_rel(rel_identity: i64) -> UnboundRelationship {
+        UnboundRelationship::new(
+            rel_identity,
+            "KNOWS".to_string(),
+            HashMap::from_iter(vec![("since".to_string(), 2020)]),
+        )
+    }
+
+    // (1)-[10]->(2)<-[11]-(3)-[12]->(1)
+    fn get_path() -> Path {
+        Path::new(
+            vec![get_node(1), get_node(2), get_node(3)],
+            vec![
+                get_unbound_rel(10),
+                get_unbound_rel(11),
+                get_unbound_rel(12),
+            ],
+            vec![1, 1, -2, 2, 3, 0],
+        )
+    }
+
+    #[test]
+    fn segments() {
+        let path = get_path();
+        assert_eq!(path.len(), 3);
+        assert_eq!(path.start(), Some(&get_node(1)));
+        assert_eq!(path.end(), Some(&get_node(1)));
+
+        let segments: Vec<_> = path
+            .segments()
+            .map(|(start, rel, end)| {
+                (
+                    start.node_identity(),
+                    rel.rel_identity(),
+                    rel.start_node_identity(),
+                    rel.end_node_identity(),
+                    end.node_identity(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            segments,
+            vec![(1, 10, 1, 2, 2), (2, 11, 3, 2, 3), (3, 12, 3, 1, 1)]
+        );
+
+        let (_, rel, _) = path.segments().next().unwrap();
+        assert_eq!(rel.rel_type(), "KNOWS");
+        assert_eq!(rel.properties().get("since"), Some(&Value::from(2020)));
+    }
+
+    #[test]
+    fn single_node() {
+        let path = Path::new(vec![get_node(1)], vec![], vec![]);
+        assert!(path.is_empty());
+        assert_eq!(path.start(), path.end());
+        assert_eq!(path.segments().count(), 0);
+    }
+
+    #[test]
+    fn validate() {
+        let path = get_path();
+        let bytes = path.clone().try_into_bytes().unwrap();
+        assert_eq!(
+            Path::try_from(Arc::new(Mutex::new(bytes.slice(2..)))).unwrap(),
+            path
+        );
+
+        for (nodes, sequence) in [
+            (vec![], vec![]),
+            (vec![get_node(1), get_node(2)], vec![1]),
+            (vec![get_node(1), get_node(2)], vec![0, 1]),
+            (vec![get_node(1), get_node(2)], vec![3, 1]),
+            (vec![get_node(1), get_node(2)], vec![-1, 2]),
+            (vec![get_node(1), get_node(2)], vec![1, -1]),
+        ] {
+            let path = Path::new(nodes, vec![get_unbound_rel(10)], sequence);
+            assert!(path.segments().count() <= path.len());
+            let bytes = path.try_into_bytes().unwrap();
+            assert!(matches!(
+                Path::try_from(Arc::new(Mutex::new(bytes.slice(2..)))),
+                Err(Error::ConversionError(ConversionError::InvalidPath(_)))
+            ));
+        }
+    }
 }
@@ -1,5 +1,11 @@
+use std::convert::TryFrom;
+
 use bolt_proto_derive::*;
 
+use crate::error::*;
+use crate::value::Crs;
+use crate::Value;
+
 pub(crate) const MARKER: u8 = 0xB3;
 pub(crate) const SIGNATURE: u8 = 0x58;
 
@@ -15,6 +21,25 @@ impl Point2D {
         Self { srid, x, y }
     }
 
+    /// Creates a point in the given coordinate reference system, failing if it is not
+    /// two-dimensional, or if the coordinates are not valid in it (e.g. a latitude beyond 90
+    /// degrees).
+    pub fn try_new(crs: Crs, x: f64, y: f64) -> Result<Self> {
+        let point = Self::new(crs.srid(), x, y);
+        if !crs.validate(&[x, y]) {
+            return Err(ConversionError::InvalidPoint(Value::Point2D(point)).into());
+        }
+        Ok(point)
+    }
+
+    pub fn crs(&self) -> Result<Crs> {
+        let crs = Crs::try_from(self.srid)?;
+        if crs.dimension() != 2 {
+            return Err(ConversionError::InvalidPoint(Value::Point2D(self.clone())).into());
+        }
+        Ok(crs)
+    }
+
     pub fn srid(&self) -> i32 {
         self.srid
     }
@@ -26,6 +51,31 @@ impl Point2D {
     pub fn y(&self) -> f64 {
         self.y
     }
+
+    /// The x coordinate, if this is a geographic point.
+    pub fn longitude(&self) -> Option<f64> {
+        self.is_geographic().then_some(self.x)
+    }
+
+    /// The y coordinate, if this is a geographic point.
+    pub fn latitude(&self) -> Option<f64> {
+        self.is_geographic().then_some(self.y)
+    }
+
+    /// The distance between two points, in meters for geographic points, computed the same way
+    /// as Neo4j's `point.distance`. Returns `None` if the points are in different or unknown
+    /// coordinate reference systems.
+    pub fn distance(&self, other: &Point2D) -> Option<f64> {
+        let crs = self.crs().ok()?;
+        if other.crs().ok()? != crs {
+            return None;
+        }
+        Some(crs.distance(&[self.x, self.y], &[other.x, other.y]))
+    }
+
+    fn is_geographic(&self) -> bool {
+        self.crs().is_ok_and(|crs| crs.is_geographic())
+    }
 }
 
 #[cfg(test)]
@@ -110,4 +160,45 @@ mod tests {
             point
         );
     }
+
+    #[test]
+    fn crs() {
+        let point = Point2D::try_new(Crs::Wgs84, 12.78, 56.7).unwrap();
+        assert_eq!(point.srid(), 4326);
+        assert_eq!(point.crs().unwrap(), Crs::Wgs84);
+        assert_eq!(point.longitude(), Some(12.78));
+        assert_eq!(point.latitude(), Some(56.7));
+
+        let point = Point2D::try_new(Crs::Cartesian, 12.78, 56.7).unwrap();
+        assert_eq!(point.longitude(), None);
+        assert_eq!(point.latitude(), None);
+
+        assert!(matches!(
+            Point2D::try_new(Crs::Wgs84, 0.0, -91.0),
+            Err(Error::ConversionError(ConversionError::InvalidPoint(_)))
+        ));
+        assert!(matches!(
+            Point2D::try_new(Crs::Cartesian3D, 0.0, 0.0),
+            Err(Error::ConversionError(ConversionError::InvalidPoint(_)))
+        ));
+        assert!(matches!(
+            get_point().crs(),
+            Err(Error::ConversionError(ConversionError::UnknownCrs(120)))
+        ));
+    }
+
+    #[test]
+    fn distance() {
+        let from = Point2D::try_new(Crs::Wgs84, 12.78, 56.7).unwrap();
+        let to = Point2D::try_new(Crs::Wgs84, 12.79, 56.71).unwrap();
+        assert!((from.distance(&to).unwrap() - 1_269.894_960_563_667).abs() < 1e-6);
+
+        let from = Point2D::try_new(Crs::Cartesian, 1.0, 1.0).unwrap();
+        let to = Point2D::try_new(Crs::Cartesian, 4.0, 5.0).unwrap();
+        assert_eq!(from.distance(&to), Some(5.0));
+
+        let geographic = Point2D::try_new(Crs::Wgs84, 4.0, 5.0).unwrap();
+        assert_eq!(from.distance(&geographic), None);
+        assert_eq!(get_point().distance(&get_point()), None);
+    }
 }
@@ -1,5 +1,11 @@
+use std::convert::TryFrom;
+
 use bolt_proto_derive::*;
 
+use crate::error::*;
+use crate::value::Crs;
+use crate::Value;
+
 pub(crate) const MARKER: u8 = 0xB4;
 pub(crate) const SIGNATURE: u8 = 0x59;
 
@@ -16,6 +22,25 @@ impl Point3D {
         Self { srid, x, y, z }
     }
 
+    /// Creates a point in the given coordinate reference system, failing if it is not
+    /// three-dimensional, or if the coordinates are not valid in it (e.g. a latitude beyond 90
+    /// degrees).
+    pub fn try_new(crs: Crs, x: f64, y: f64, z: f64) -> Result<Self> {
+        let point = Self::new(crs.srid(), x, y, z);
+        if !crs.validate(&[x, y, z]) {
+            return Err(ConversionError::InvalidPoint(Value::Point3D(point)).into());
+        }
+        Ok(point)
+    }
+
+    pub fn crs(&self) -> Result<Crs> {
+        let crs = Crs::try_from(self.srid)?;
+        if crs.dimension() != 3 {
+            return Err(ConversionError::InvalidPoint(Value::Point3D(self.clone())).into());
+        }
+        Ok(crs)
+    }
+
     pub fn srid(&self) -> i32 {
         self.srid
     }
@@ -31,6 +56,36 @@ impl Point3D {
     pub fn z(&self) -> f64 {
         self.z
     }
+
+    /// The x coordinate, if this is a geographic point.
+    pub fn longitude(&self) -> Option<f64> {
+        self.is_geographic().then_some(self.x)
+    }
+
+    /// The y coordinate, if this is a geographic point.
+    pub fn latitude(&self) -> Option<f64> {
+        self.is_geographic().then_some(self.y)
+    }
+
+    /// The z coordinate in meters, if this is a geographic point.
+    pub fn height(&self) -> Option<f64> {
+        self.is_geographic().then_some(self.z)
+    }
+
+    /// The distance between two points, in meters for geographic points, computed the same way
+    /// as Neo4j's `point.distance`. Returns `None` if the points are in different or unknown
+    /// coordinate reference systems.
+    pub fn distance(&self, other: &Point3D) -> Option<f64> {
+        let crs = self.crs().ok()?;
+        if other.crs().ok()? != crs {
+            return None;
+        }
+        Some(crs.distance(&[self.x, self.y, self.z], &[other.x, other.y, other.z]))
+    }
+
+    fn is_geographic(&self) -> bool {
+        self.crs().is_ok_and(|crs| crs.is_geographic())
+    }
 }
 
 #[cfg(test)]
@@ -133,4 +188,34 @@ mod tests {
             point
         );
     }
+
+    #[test]
+    fn crs() {
+        let point = Point3D::try_new(Crs::Wgs84_3D, 12.78, 56.7, 100.0).unwrap();
+        assert_eq!(point.srid(), 4979);
+        assert_eq!(point.crs().unwrap(), Crs::Wgs84_3D);
+        assert_eq!(point.longitude(), Some(12.78));
+        assert_eq!(point.latitude(), Some(56.7));
+        assert_eq!(point.height(), Some(100.0));
+
+        let point = Point3D::try_new(Crs::Cartesian3D, 12.78, 56.7, 100.0).unwrap();
+        assert_eq!(point.height(), None);
+
+        assert!(matches!(
+            Point3D::try_new(Crs::Wgs84, 0.0, 0.0, 0.0),
+            Err(Error::ConversionError(ConversionError::InvalidPoint(_)))
+        ));
+    }
+
+    #[test]
+    fn distance() {
+        // Neo4j's documented example for point.distance
+        let from = Point3D::try_new(Crs::Wgs84_3D, 12.78, 56.7, 100.0).unwrap();
+        let to = Point3D::try_new(Crs::Wgs84_3D, 12.79, 56.71, 100.0).unwrap();
+        assert!((from.distance(&to).unwrap() - 1_269.914_870_677_909_7).abs() < 1e-6);
+
+        let from = Point3D::try_new(Crs::Cartesian3D, 1.0, 1.0, 1.0).unwrap();
+        let to = Point3D::try_new(Crs::Cartesian3D, 3.0, 4.0, 7.0).unwrap();
+        assert_eq!(from.distance(&to), Some(7.0));
+    }
 }
@@ -1,19 +1,36 @@
 use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::sync::{Arc, Mutex};
 
 use bolt_proto_derive::*;
+use bytes::Bytes;
 
+use crate::error::*;
+use crate::serialization::*;
 use crate::Value;
 
 pub(crate) const MARKER: u8 = 0xB5;
+pub(crate) const MARKER_V5: u8 = 0xB8;
 pub(crate) const SIGNATURE: u8 = 0x52;
 
-#[derive(Debug, Clone, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Signature)]
 pub struct Relationship {
     pub(crate) rel_identity: i64,
     pub(crate) start_node_identity: i64,
     pub(crate) end_node_identity: i64,
     pub(crate) rel_type: String,
     pub(crate) properties: HashMap<String, Value>,
+    // Only sent by servers using Bolt 5.0 or later, boxed to keep the size of `Value` down
+    pub(crate) element_ids: Option<Box<ElementIds>>,
+}
+
+// The element IDs of a relationship and of its start and end nodes, which are always sent
+// together
+#[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd)]
+pub(crate) struct ElementIds {
+    pub(crate) rel: String,
+    pub(crate) start_node: String,
+    pub(crate) end_node: String,
 }
 
 impl Relationship {
@@ -30,9 +47,26 @@ impl Relationship {
             end_node_identity,
             rel_type,
             properties: properties.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            element_ids: None,
         }
     }
 
+    /// Set the element IDs that identify the relationship and its start and end nodes in
+    /// Bolt 5.0 and later.
+    pub fn with_element_ids(
+        mut self,
+        element_id: impl Into<String>,
+        start_node_element_id: impl Into<String>,
+        end_node_element_id: impl Into<String>,
+    ) -> Self {
+        self.element_ids = Some(Box::new(ElementIds {
+            rel: element_id.into(),
+            start_node: start_node_element_id.into(),
+            end_node: end_node_element_id.into(),
+        }));
+        self
+    }
+
     pub fn rel_identity(&self) -> i64 {
         self.rel_identity
     }
@@ -52,4 +86,78 @@ impl Relationship {
     pub fn properties(&self) -> &HashMap<String, Value> {
         &self.properties
     }
+
+    pub fn element_id(&self) -> Option<&str> {
+        self.element_ids.as_ref().map(|ids| ids.rel.as_str())
+    }
+
+    pub fn start_node_element_id(&self) -> Option<&str> {
+        self.element_ids.as_ref().map(|ids| ids.start_node.as_str())
+    }
+
+    pub fn end_node_element_id(&self) -> Option<&str> {
+        self.element_ids.as_ref().map(|ids| ids.end_node.as_str())
+    }
+
+    // Deserialize the Bolt 5 structure, which has the element IDs as trailing fields
+    pub(crate) fn try_from_v5(remaining_bytes_arc: Arc<Mutex<Bytes>>) -> Result<Self> {
+        let mut rel = Self::try_from(Arc::clone(&remaining_bytes_arc))?;
+        rel.element_ids = Some(Box::new(ElementIds {
+            rel: deserialize_field(&remaining_bytes_arc)?,
+            start_node: deserialize_field(&remaining_bytes_arc)?,
+            end_node: deserialize_field(&remaining_bytes_arc)?,
+        }));
+        Ok(rel)
+    }
+}
+
+impl Marker for Relationship {
+    fn get_marker(&self) -> Result<u8> {
+        match self.element_ids {
+            Some(_) => Ok(MARKER_V5),
+            None => Ok(MARKER),
+        }
+    }
+}
+
+impl Serialize for Relationship {}
+
+impl TryInto<Bytes> for Relationship {
+    type Error = Error;
+
+    fn try_into(self) -> Result<Bytes> {
+        let marker = self.get_marker()?;
+        let mut fields = vec![
+            Value::from(self.rel_identity),
+            Value::from(self.start_node_identity),
+            Value::from(self.end_node_identity),
+            Value::from(self.rel_type),
+            Value::from(self.properties),
+        ];
+        if let Some(ids) = self.element_ids {
+            fields.extend(vec![
+                Value::from(ids.rel),
+                Value::from(ids.start_node),
+                Value::from(ids.end_node),
+            ]);
+        }
+        serialize_structure(marker, SIGNATURE, fields)
+    }
+}
+
+impl Deserialize for Relationship {}
+
+impl TryFrom<Arc<Mutex<Bytes>>> for Relationship {
+    type Error = Error;
+
+    fn try_from(remaining_bytes_arc: Arc<Mutex<Bytes>>) -> Result<Self> {
+        Ok(Self {
+            rel_identity: deserialize_field(&remaining_bytes_arc)?,
+            start_node_identity: deserialize_field(&remaining_bytes_arc)?,
+            end_node_identity: deserialize_field(&remaining_bytes_arc)?,
+            rel_type: deserialize_field(&remaining_bytes_arc)?,
+            properties: deserialize_field(&remaining_bytes_arc)?,
+            element_ids: None,
+        })
+    }
 }
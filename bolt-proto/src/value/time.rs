@@ -1,47 +1,37 @@
-use chrono::{FixedOffset, NaiveTime, Offset, Timelike};
-
 use bolt_proto_derive::*;
 
+use crate::error::*;
+use crate::value::NANOS_PER_DAY;
+use crate::Value;
+
 pub(crate) const MARKER: u8 = 0xB2;
 pub(crate) const SIGNATURE: u8 = 0x54;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[validate]
 pub struct Time {
     pub(crate) nanos_since_midnight: i64,
     pub(crate) zone_offset: i32,
 }
 
 impl Time {
-    pub fn naive_time(&self) -> NaiveTime {
-        let seconds = (self.nanos_since_midnight / 1_000_000_000) as u32;
-        let nanos = (self.nanos_since_midnight % 1_000_000_000) as u32;
-        // Does not panic since seconds and nanos came from a NaiveTime already
-        NaiveTime::from_num_seconds_from_midnight(seconds, nanos)
-    }
-
-    pub fn offset(&self) -> FixedOffset {
-        FixedOffset::east(self.zone_offset)
-    }
-}
-
-// No timezone-aware time in chrono, so provide separate conversion instead
-impl<O: Offset> From<(NaiveTime, O)> for Time {
-    fn from(pair: (NaiveTime, O)) -> Self {
-        Self {
-            // Will not overflow: u32::MAX * 1_000_000_000 + u32::MAX < i64::MAX
-            nanos_since_midnight: pair.0.num_seconds_from_midnight() as i64 * 1_000_000_000
-                + pair.0.nanosecond() as i64,
-            zone_offset: pair.1.fix().local_minus_utc(),
+    pub(crate) fn validate(&self) -> Result<()> {
+        if !(0..NANOS_PER_DAY).contains(&self.nanos_since_midnight)
+            || !(-86_399..=86_399).contains(&self.zone_offset)
+        {
+            return Err(ConversionError::TemporalOutOfRange(Value::Time(self.clone())).into());
         }
+        Ok(())
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "chrono"))]
 mod tests {
     use std::convert::TryFrom;
     use std::sync::{Arc, Mutex};
 
     use bytes::Bytes;
+    use chrono::{FixedOffset, NaiveTime};
 
     use crate::serialization::*;
     use crate::value::integer::{MARKER_INT_16, MARKER_INT_64};
@@ -50,8 +40,8 @@ mod tests {
 
     fn get_time() -> Time {
         Time::from((
-            NaiveTime::from_hms_nano(1, 16, 40, 123),
-            FixedOffset::east(3600),
+            NaiveTime::from_hms_nano_opt(1, 16, 40, 123).unwrap(),
+            FixedOffset::east_opt(3600).unwrap(),
         ))
     }
 
@@ -110,7 +100,10 @@ mod tests {
     #[test]
     fn accessors() {
         let time = get_time();
-        assert_eq!(time.naive_time(), NaiveTime::from_hms_nano(1, 16, 40, 123));
-        assert_eq!(time.offset(), FixedOffset::east(3600));
+        assert_eq!(
+            time.naive_time(),
+            NaiveTime::from_hms_nano_opt(1, 16, 40, 123).unwrap()
+        );
+        assert_eq!(time.offset(), FixedOffset::east_opt(3600).unwrap());
     }
 }
@@ -1,17 +1,25 @@
 use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::sync::{Arc, Mutex};
 
 use bolt_proto_derive::*;
+use bytes::Bytes;
 
+use crate::error::*;
+use crate::serialization::*;
 use crate::Value;
 
 pub(crate) const MARKER: u8 = 0xB3;
+pub(crate) const MARKER_V5: u8 = 0xB4;
 pub(crate) const SIGNATURE: u8 = 0x72;
 
-#[derive(Debug, Clone, Eq, PartialEq, Signature, Marker, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, PartialEq, Signature)]
 pub struct UnboundRelationship {
     pub(crate) rel_identity: i64,
     pub(crate) rel_type: String,
     pub(crate) properties: HashMap<String, Value>,
+    // Only sent by servers using Bolt 5.0 or later
+    pub(crate) element_id: Option<String>,
 }
 
 impl UnboundRelationship {
@@ -24,9 +32,16 @@ impl UnboundRelationship {
             rel_identity,
             rel_type,
             properties: properties.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            element_id: None,
         }
     }
 
+    /// Set the element ID that identifies the relationship in Bolt 5.0 and later.
+    pub fn with_element_id(mut self, element_id: impl Into<String>) -> Self {
+        self.element_id = Some(element_id.into());
+        self
+    }
+
     pub fn rel_identity(&self) -> i64 {
         self.rel_identity
     }
@@ -38,4 +53,56 @@ impl UnboundRelationship {
     pub fn properties(&self) -> &HashMap<String, Value> {
         &self.properties
     }
+
+    pub fn element_id(&self) -> Option<&str> {
+        self.element_id.as_deref()
+    }
+
+    // Deserialize the Bolt 5 structure, which has the element ID as a trailing field
+    pub(crate) fn try_from_v5(remaining_bytes_arc: Arc<Mutex<Bytes>>) -> Result<Self> {
+        let mut rel = Self::try_from(Arc::clone(&remaining_bytes_arc))?;
+        rel.element_id = Some(deserialize_field(&remaining_bytes_arc)?);
+        Ok(rel)
+    }
+}
+
+impl Marker for UnboundRelationship {
+    fn get_marker(&self) -> Result<u8> {
+        match self.element_id {
+            Some(_) => Ok(MARKER_V5),
+            None => Ok(MARKER),
+        }
+    }
+}
+
+impl Serialize for UnboundRelationship {}
+
+impl TryInto<Bytes> for UnboundRelationship {
+    type Error = Error;
+
+    fn try_into(self) -> Result<Bytes> {
+        let marker = self.get_marker()?;
+        let mut fields = vec![
+            Value::from(self.rel_identity),
+            Value::from(self.rel_type),
+            Value::from(self.properties),
+        ];
+        fields.extend(self.element_id.map(Value::from));
+        serialize_structure(marker, SIGNATURE, fields)
+    }
+}
+
+impl Deserialize for UnboundRelationship {}
+
+impl TryFrom<Arc<Mutex<Bytes>>> for UnboundRelationship {
+    type Error = Error;
+
+    fn try_from(remaining_bytes_arc: Arc<Mutex<Bytes>>) -> Result<Self> {
+        Ok(Self {
+            rel_identity: deserialize_field(&remaining_bytes_arc)?,
+            rel_type: deserialize_field(&remaining_bytes_arc)?,
+            properties: deserialize_field(&remaining_bytes_arc)?,
+            element_id: None,
+        })
+    }
 }
@@ -8,3 +8,29 @@ pub const V3_0: u32 = 0x0003;
 pub const V4_0: u32 = 0x0004;
 /// Protocol version 4.1
 pub const V4_1: u32 = 0x0104;
+/// Protocol version 4.2
+pub const V4_2: u32 = 0x0204;
+/// Protocol version 4.3
+pub const V4_3: u32 = 0x0304;
+/// Protocol version 4.4
+pub const V4_4: u32 = 0x0404;
+/// Protocol version 5.0
+pub const V5_0: u32 = 0x0005;
+/// Protocol version 5.1
+pub const V5_1: u32 = 0x0105;
+/// Protocol version 5.2
+pub const V5_2: u32 = 0x0205;
+/// Protocol version 5.3
+pub const V5_3: u32 = 0x0305;
+/// Protocol version 5.4
+pub const V5_4: u32 = 0x0405;
+
+/// Encode a range of protocol versions for the handshake, covering the given version and
+/// up to `minor_versions_back` preceding minor versions of the same major version. For
+/// example, `range(V4_1, 1)` covers versions 4.1 and 4.0.
+///
+/// Version ranges are only understood by servers supporting Bolt v4.3 or later, which
+/// respond with the highest version in the range that they support.
+pub const fn range(version: u32, minor_versions_back: u8) -> u32 {
+    version | (minor_versions_back as u32) << 16
+}